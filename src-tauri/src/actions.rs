@@ -0,0 +1,85 @@
+use crate::commands::{finish_timebox, pause_timebox, start_timebox, stop_timebox};
+use crate::core::{TimeboxService, TimeboxWithSessions};
+use crate::models::Timebox;
+use crate::state::AppState;
+use crate::tray::refresh_tray;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Emitted to the frontend whenever timeboxes change outside of the main window,
+/// so the UI can refresh its data.
+pub const TIMEBOXES_CHANGED_EVENT: &str = "timeboxes-changed";
+
+/// Lifecycle actions that can be triggered from outside the main window
+/// (tray menu, global shortcuts).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeboxAction {
+    Start,
+    Pause,
+    Stop,
+    Finish,
+}
+
+impl TimeboxAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeboxAction::Start => "start",
+            TimeboxAction::Pause => "pause",
+            TimeboxAction::Stop => "stop",
+            TimeboxAction::Finish => "finish",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "start" => Some(TimeboxAction::Start),
+            "pause" => Some(TimeboxAction::Pause),
+            "stop" => Some(TimeboxAction::Stop),
+            "finish" => Some(TimeboxAction::Finish),
+            _ => None,
+        }
+    }
+}
+
+/// Runs an action through the same functions as the Tauri commands, then notifies
+/// the frontend and refreshes the tray so everything stays in sync.
+pub fn run_timebox_action(app: &AppHandle, action: TimeboxAction, id: i64) -> Result<Timebox, String> {
+    let timebox = match action {
+        TimeboxAction::Start => start_timebox(app.state(), id),
+        TimeboxAction::Pause => pause_timebox(app.state(), id),
+        TimeboxAction::Stop => stop_timebox(app.state(), id),
+        TimeboxAction::Finish => finish_timebox(app.state(), id),
+    }?;
+
+    notify_timeboxes_changed(app);
+    Ok(timebox)
+}
+
+/// Same as `run_timebox_action` but on a background thread, for callers on the main
+/// thread (menu and shortcut handlers), which must not block waiting for the database lock.
+pub fn spawn_timebox_action(app: &AppHandle, action: TimeboxAction, id: i64) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = run_timebox_action(&app, action, id) {
+            eprintln!("Failed to {} timebox {}: {}", action.as_str(), id, e);
+        }
+    });
+}
+
+pub fn notify_timeboxes_changed(app: &AppHandle) {
+    let _ = app.emit(TIMEBOXES_CHANGED_EVENT, ());
+    refresh_tray(app);
+}
+
+/// The timebox the user is currently working on, see `TimeboxService::current`.
+pub fn current_timebox(app: &AppHandle) -> Option<TimeboxWithSessions> {
+    let state = app.state::<AppState>();
+    let conn = state.db.lock().ok()?;
+    TimeboxService::new(&conn).current().ok().flatten()
+}
+
+/// The timebox a start without an id resumes, see `TimeboxService::resumable`.
+pub fn resumable_timebox(app: &AppHandle) -> Option<TimeboxWithSessions> {
+    let state = app.state::<AppState>();
+    let conn = state.db.lock().ok()?;
+    TimeboxService::new(&conn).resumable().ok().flatten()
+}
@@ -0,0 +1,213 @@
+use chrono::Local;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use timeboxd_lib::core::{TimeboxService, TimeboxWithSessions};
+use timeboxd_lib::database::{default_db_path, open_database};
+use timeboxd_lib::models::{CreateTimeboxRequest, Timebox, TimeboxStatus};
+use timeboxd_lib::parser::parse_duration_details;
+
+/// timeboxd from the terminal, using the same database as the desktop app
+#[derive(Parser)]
+#[command(name = "timeboxd-cli", version)]
+struct Cli {
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Database to use instead of the app's
+    #[arg(long, global = true, env = "TIMEBOXD_DB")]
+    db: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Start or resume a timebox (defaults to the paused one, then the last stopped one)
+    Start { id: Option<i64> },
+    /// Stop a timebox (defaults to the current one)
+    Stop { id: Option<i64> },
+    /// Pause a timebox (defaults to the current one)
+    Pause { id: Option<i64> },
+    /// Finish a timebox (defaults to the current one)
+    Finish { id: Option<i64> },
+    /// List timeboxes
+    List {
+        #[arg(value_enum, default_value = "today")]
+        range: ListRange,
+    },
+    /// Show the timebox you're working on
+    Status,
+    /// Add a timebox, e.g. `add "Write RFC draft" 25m`
+    Add {
+        intention: String,
+        /// Duration like 25m, 1h30, "until 3pm" or "2 pomodoros"
+        #[arg(required = true, num_args = 1..)]
+        duration: Vec<String>,
+        /// Start it right away
+        #[arg(long)]
+        start: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ListRange {
+    Today,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<(), String> {
+    let db_path = match &cli.db {
+        Some(path) => path.clone(),
+        None => default_db_path().map_err(|e| e.to_string())?,
+    };
+    let conn = open_database(&db_path).map_err(|e| e.to_string())?;
+    let service = TimeboxService::new(&conn);
+
+    match &cli.command {
+        Command::Start { id } => {
+            let id = resolve_start_id(&service, *id)?;
+            print_timebox(cli, "Started", &service.start(id)?)
+        }
+        Command::Stop { id } => {
+            let id = resolve_id(&service, *id)?;
+            print_timebox(cli, "Stopped", &service.stop(id)?)
+        }
+        Command::Pause { id } => {
+            let id = resolve_id(&service, *id)?;
+            print_timebox(cli, "Paused", &service.pause(id)?)
+        }
+        Command::Finish { id } => {
+            let id = resolve_id(&service, *id)?;
+            print_timebox(cli, "Finished", &service.finish(id)?)
+        }
+        Command::List { range: ListRange::Today } => {
+            let timeboxes = service.today()?;
+            if cli.json {
+                return print_json(&timeboxes);
+            }
+            if timeboxes.is_empty() {
+                println!("No timeboxes today");
+            }
+            for t in &timeboxes {
+                println!(
+                    "#{:<4} {:<12} {:>13}  {}",
+                    t.timebox.id,
+                    t.timebox.status.as_str(),
+                    progress(t),
+                    t.timebox.intention
+                );
+            }
+            Ok(())
+        }
+        Command::Status => {
+            let current = service.current()?;
+            if cli.json {
+                return print_json(&current);
+            }
+            match current {
+                Some(t) => {
+                    let label = if t.timebox.status == TimeboxStatus::Paused { "Paused" } else { "Working on" };
+                    println!("{} #{} {} ({})", label, t.timebox.id, t.timebox.intention, progress(&t));
+                }
+                None => println!("No timebox running"),
+            }
+            Ok(())
+        }
+        Command::Add { intention, duration, start } => {
+            let parsed = parse_duration_details(&duration.join(" "), Local::now().naive_local())
+                .map_err(|e| e.to_string())?;
+
+            let request = CreateTimeboxRequest {
+                intention: intention.trim().to_string(),
+                intended_duration: parsed.intended_duration,
+                notes: None,
+                linear_project_id: None,
+                linear_issue_id: None,
+                linear_issue_identifier: None,
+                linear_issue_url: None,
+                tags: Vec::new(),
+            };
+            if request.intention.is_empty() {
+                return Err("Intention can't be empty".to_string());
+            }
+
+            let timebox = service.create(&request)?;
+            if *start {
+                print_timebox(cli, "Started", &service.start(timebox.id)?)
+            } else {
+                print_timebox(cli, "Added", &timebox)
+            }
+        }
+    }
+}
+
+/// Uses the given id, or the timebox the user is currently working on
+fn resolve_id(service: &TimeboxService, id: Option<i64>) -> Result<i64, String> {
+    match id {
+        Some(id) => Ok(id),
+        None => service
+            .current()?
+            .map(|t| t.timebox.id)
+            .ok_or_else(|| "No timebox running, pass an id (see `timeboxd-cli list`)".to_string()),
+    }
+}
+
+/// Uses the given id, or the timebox to resume, never the one that is already running
+fn resolve_start_id(service: &TimeboxService, id: Option<i64>) -> Result<i64, String> {
+    match id {
+        Some(id) => Ok(id),
+        None => service
+            .resumable()?
+            .map(|t| t.timebox.id)
+            .ok_or_else(|| "No paused or stopped timebox to resume, pass an id (see `timeboxd-cli list`)".to_string()),
+    }
+}
+
+fn print_timebox(cli: &Cli, verb: &str, timebox: &Timebox) -> Result<(), String> {
+    if cli.json {
+        return print_json(timebox);
+    }
+    println!("{} #{} {} ({} min)", verb, timebox.id, timebox.intention, timebox.intended_duration);
+    Ok(())
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Elapsed and planned time, e.g. "12:03 / 25:00"
+fn progress(t: &TimeboxWithSessions) -> String {
+    format!(
+        "{} / {}",
+        format_clock(t.actual_duration as i64),
+        format_clock(t.timebox.intended_duration * 60)
+    )
+}
+
+fn format_clock(seconds: i64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    let seconds = seconds % 60;
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::parser::{parse_duration_details, ParsedDuration};
+use crate::state::AppState;
+use chrono::Local;
+use tauri::State;
+
+const DEFAULT_DURATION_PRESETS: &str = "5m,15m,45m";
+
+/// Parses a typed duration ("1h30", "until 3pm", "2 pomodoros") relative to now.
+/// Errors are returned in the given locale, e.g. the browser's `navigator.language`.
+#[tauri::command]
+pub fn parse_duration(input: String, locale: Option<String>) -> Result<ParsedDuration, String> {
+    parse_duration_details(&input, Local::now().naive_local())
+        .map_err(|e| e.message(locale.as_deref().unwrap_or("en")))
+}
+
+/// Preset durations for the timebox form, as text to be parsed with `parse_duration`
+#[tauri::command]
+pub fn get_duration_presets(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+
+    let presets: String = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = 'duration_presets'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or_else(|_| DEFAULT_DURATION_PRESETS.to_string());
+
+    Ok(presets
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect())
+}
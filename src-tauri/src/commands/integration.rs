@@ -1,259 +1,50 @@
+use crate::integrations::http::ApiError;
+use crate::integrations::linear::{test_linear_api_key, LinearTestResult};
+use crate::integrations::todoist::{test_todoist_token, TodoistTestResult};
+use crate::integrations::{find_integration, list_integrations, provider, provider_descriptors, validate_integration_config, ConnectionTestResult, ProviderDescriptor, ProviderTask, INTEGRATION_SELECT_COLUMNS};
 use crate::models::{CreateIntegrationRequest, Integration};
 use crate::state::AppState;
 use chrono::Local;
 use rusqlite::{params, OptionalExtension};
-use serde::Deserialize;
 use tauri::State;
 
-const INTEGRATION_SELECT_COLUMNS: &str = "id, connection_name, integration_type, connection_config, created_at, updated_at";
-
-// Linear types
-#[derive(Debug, Deserialize)]
-struct LinearViewerResponse {
-    data: Option<LinearViewerData>,
-    errors: Option<Vec<LinearError>>,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearViewerData {
-    viewer: LinearViewer,
-}
-
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-struct LinearViewer {
-    id: String,
-    name: String,
-    email: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearError {
-    message: String,
-}
-
-#[derive(Debug, serde::Serialize)]
-pub struct LinearTestResult {
-    pub success: bool,
-    pub user_name: Option<String>,
-    pub user_email: Option<String>,
-    pub error: Option<String>,
-}
-
-// Todoist types
-#[derive(Debug, serde::Serialize)]
-pub struct TodoistTestResult {
-    pub success: bool,
-    pub user_name: Option<String>,
-    pub error: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct TodoistUser {
-    full_name: String,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct TodoistTask {
-    pub id: String,
-}
-
-#[derive(Debug, serde::Serialize)]
-pub struct TodoistTaskResult {
-    pub success: bool,
-    pub task_id: Option<String>,
-    pub error: Option<String>,
-}
-
 #[tauri::command]
-pub fn test_linear_connection(api_key: String) -> Result<LinearTestResult, String> {
-    let client = reqwest::blocking::Client::new();
-
-    let query = r#"{ "query": "{ viewer { id name email } }" }"#;
-
-    let response = client
-        .post("https://api.linear.app/graphql")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .body(query)
-        .send()
-        .map_err(|e| format!("Failed to connect to Linear: {}", e))?;
-
-    if !response.status().is_success() {
-        return Ok(LinearTestResult {
-            success: false,
-            user_name: None,
-            user_email: None,
-            error: Some(format!("Linear API returned status: {}", response.status())),
-        });
-    }
-
-    let result: LinearViewerResponse = response
-        .json()
-        .map_err(|e| format!("Failed to parse Linear response: {}", e))?;
-
-    if let Some(errors) = result.errors {
-        let error_msg = errors
-            .into_iter()
-            .map(|e| e.message)
-            .collect::<Vec<_>>()
-            .join(", ");
-        return Ok(LinearTestResult {
-            success: false,
-            user_name: None,
-            user_email: None,
-            error: Some(error_msg),
-        });
-    }
-
-    if let Some(data) = result.data {
-        Ok(LinearTestResult {
-            success: true,
-            user_name: Some(data.viewer.name),
-            user_email: Some(data.viewer.email),
-            error: None,
-        })
-    } else {
-        Ok(LinearTestResult {
-            success: false,
-            user_name: None,
-            user_email: None,
-            error: Some("No data returned from Linear".to_string()),
-        })
-    }
+pub async fn test_linear_connection(api_key: String, api_base_url: Option<String>) -> Result<LinearTestResult, ApiError> {
+    test_linear_api_key(&api_key, api_base_url.as_deref()).await
 }
 
 #[tauri::command]
-pub fn test_todoist_connection(api_token: String) -> Result<TodoistTestResult, String> {
-    let client = reqwest::blocking::Client::new();
-
-    let response = client
-        .get("https://api.todoist.com/sync/v9/user")
-        .header("Authorization", format!("Bearer {}", api_token))
-        .send()
-        .map_err(|e| format!("Failed to connect to Todoist: {}", e))?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        return Ok(TodoistTestResult {
-            success: false,
-            user_name: None,
-            error: Some(format!("Todoist API returned status: {}", status)),
-        });
-    }
-
-    let user: TodoistUser = response
-        .json()
-        .map_err(|e| format!("Failed to parse Todoist response: {}", e))?;
-
-    Ok(TodoistTestResult {
-        success: true,
-        user_name: Some(user.full_name),
-        error: None,
-    })
-}
-
-/// Creates a task in Todoist with today's due date
-pub fn create_todoist_task(api_token: &str, content: &str, description: Option<&str>) -> Result<TodoistTaskResult, String> {
-    let client = reqwest::blocking::Client::new();
-
-    let today = Local::now().format("%Y-%m-%d").to_string();
-
-    let mut body = serde_json::json!({
-        "content": content,
-        "due_date": today
-    });
-
-    if let Some(desc) = description {
-        body["description"] = serde_json::Value::String(desc.to_string());
-    }
-
-    let response = client
-        .post("https://api.todoist.com/rest/v2/tasks")
-        .header("Authorization", format!("Bearer {}", api_token))
-        .header("Content-Type", "application/json")
-        .json(&body)
-        .send()
-        .map_err(|e| format!("Failed to create Todoist task: {}", e))?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().unwrap_or_default();
-        return Ok(TodoistTaskResult {
-            success: false,
-            task_id: None,
-            error: Some(format!("Todoist API returned status {}: {}", status, error_text)),
-        });
-    }
-
-    let task: TodoistTask = response
-        .json()
-        .map_err(|e| format!("Failed to parse Todoist response: {}", e))?;
-
-    Ok(TodoistTaskResult {
-        success: true,
-        task_id: Some(task.id),
-        error: None,
-    })
+pub async fn test_todoist_connection(api_token: String, api_base_url: Option<String>) -> Result<TodoistTestResult, ApiError> {
+    test_todoist_token(&api_token, api_base_url.as_deref()).await
 }
 
-/// Marks a Todoist task as completed
-pub fn complete_todoist_task(api_token: &str, task_id: &str) -> Result<bool, String> {
-    let client = reqwest::blocking::Client::new();
-
-    let response = client
-        .post(format!("https://api.todoist.com/rest/v2/tasks/{}/close", task_id))
-        .header("Authorization", format!("Bearer {}", api_token))
-        .send()
-        .map_err(|e| format!("Failed to complete Todoist task: {}", e))?;
-
-    Ok(response.status().is_success())
+/// Providers that can be connected, with the config fields each one needs
+#[tauri::command]
+pub fn get_integration_providers() -> Vec<ProviderDescriptor> {
+    provider_descriptors()
 }
 
-/// Creates a task in Todoist and immediately marks it as completed.
-/// This is used when finishing a timebox to record it as a completed task.
-pub fn create_completed_todoist_task(api_token: &str, content: &str, description: Option<&str>) -> Result<TodoistTaskResult, String> {
-    // First create the task
-    let create_result = create_todoist_task(api_token, content, description)?;
-
-    if !create_result.success {
-        return Ok(create_result);
-    }
-
-    // Then mark it as completed
-    if let Some(ref task_id) = create_result.task_id {
-        let completed = complete_todoist_task(api_token, task_id)?;
-        if !completed {
-            return Ok(TodoistTaskResult {
-                success: false,
-                task_id: create_result.task_id,
-                error: Some("Task created but failed to mark as completed".to_string()),
-            });
-        }
-    }
-
-    Ok(create_result)
+/// Checks a config against its service before it is saved
+#[tauri::command]
+pub async fn test_integration_connection(
+    integration_type: String,
+    connection_config: serde_json::Value,
+) -> Result<ConnectionTestResult, String> {
+    let provider = provider(&integration_type).ok_or_else(|| format!("Unknown integration type '{}'", integration_type))?;
+    provider.test_connection(&connection_config).await
 }
 
-/// Gets the Todoist API token from the database if a Todoist integration exists
-pub fn get_todoist_api_token(conn: &rusqlite::Connection) -> Option<String> {
-    let mut stmt = conn
-        .prepare(&format!(
-            "SELECT {} FROM integrations WHERE integration_type = 'todoist' LIMIT 1",
-            INTEGRATION_SELECT_COLUMNS
-        ))
-        .ok()?;
-
-    let integration: Option<Integration> = stmt
-        .query_row([], Integration::from_row)
-        .optional()
-        .ok()?;
+/// Open tasks from a connected integration that timeboxes can be created from
+#[tauri::command]
+pub async fn fetch_integration_tasks(state: State<'_, AppState>, id: i64) -> Result<Vec<ProviderTask>, String> {
+    let integration = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        find_integration(&conn, id)?.ok_or_else(|| "Integration not found".to_string())?
+    };
 
-    integration.and_then(|i| {
-        i.connection_config.get("api_token")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-    })
+    let provider = provider(&integration.integration_type)
+        .ok_or_else(|| format!("Unknown integration type '{}'", integration.integration_type))?;
+    provider.fetch_tasks(&integration).await
 }
 
 #[tauri::command]
@@ -261,10 +52,12 @@ pub fn create_integration(
     state: State<'_, AppState>,
     request: CreateIntegrationRequest,
 ) -> Result<Integration, String> {
+    let connection_config = validate_integration_config(&request.integration_type, request.connection_config)?;
+
     let conn = state.db.lock().map_err(|e| e.to_string())?;
     let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
-    let config_json = serde_json::to_string(&request.connection_config)
+    let config_json = serde_json::to_string(&connection_config)
         .map_err(|e| e.to_string())?;
 
     conn.execute(
@@ -289,21 +82,7 @@ pub fn create_integration(
 #[tauri::command]
 pub fn get_integrations(state: State<'_, AppState>) -> Result<Vec<Integration>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare(&format!(
-            "SELECT {} FROM integrations ORDER BY created_at DESC",
-            INTEGRATION_SELECT_COLUMNS
-        ))
-        .map_err(|e| e.to_string())?;
-
-    let integrations: Vec<Integration> = stmt
-        .query_map([], Integration::from_row)
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    Ok(integrations)
+    list_integrations(&conn)
 }
 
 #[tauri::command]
@@ -347,6 +126,11 @@ pub fn update_integration_config(
     let conn = state.db.lock().map_err(|e| e.to_string())?;
     let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
+    let integration_type: String = conn
+        .query_row("SELECT integration_type FROM integrations WHERE id = ?1", params![id], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    let connection_config = validate_integration_config(&integration_type, connection_config)?;
+
     let config_json = serde_json::to_string(&connection_config)
         .map_err(|e| e.to_string())?;
 
@@ -1,217 +1,83 @@
-use crate::models::{LinearProject, SaveLinearProjectRequest};
+use crate::core::TimeboxService;
+use crate::integrations::get_linear_config;
+use crate::integrations::http::ApiError;
+use crate::integrations::linear::client::IssueCreateInput;
+use crate::integrations::linear::issue_cache::{self, CachedProjectIssues, IssueSyncResult};
+use crate::integrations::linear::link_validation::{self, LinkValidationResult};
+use crate::integrations::linear::planning::{self, EstimateMinutes, PlannedIssue, PlannedTimeboxes};
+use crate::integrations::linear::project_sync::{self, ProjectRefreshResult};
+use crate::integrations::linear::state_rules::{self, LinearStateRule, StateTransition};
+use crate::integrations::linear::LinearClient;
+use crate::integrations::ProviderHook;
+use crate::models::{CreateTimeboxRequest, LinearProject, SaveLinearProjectRequest};
 use crate::state::AppState;
 use chrono::Local;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
-const LINEAR_PROJECT_SELECT_COLUMNS: &str = "id, linear_project_id, linear_team_id, name, description, state, is_active_timebox_project, created_at, updated_at, archived_at, deleted_at";
-
-// GraphQL Response types for Linear API
-#[derive(Debug, Deserialize)]
-struct LinearTeamsResponse {
-    data: Option<LinearTeamsData>,
-    errors: Option<Vec<LinearError>>,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearTeamsData {
-    teams: LinearTeamsNodes,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearTeamsNodes {
-    nodes: Vec<LinearTeam>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearTeam {
-    pub id: String,
-    pub name: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearProjectsResponse {
-    data: Option<LinearProjectsData>,
-    errors: Option<Vec<LinearError>>,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearProjectsData {
-    team: LinearTeamWithProjects,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearTeamWithProjects {
-    projects: LinearProjectsNodes,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearProjectsNodes {
-    nodes: Vec<LinearApiProject>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearApiProject {
-    pub id: String,
-    pub name: String,
-    pub description: Option<String>,
-    pub state: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearError {
-    message: String,
-}
+pub use crate::integrations::linear::client::{
+    LinearApiIssue, LinearApiProject, LinearCycle, LinearIssue, LinearPlanningIssue, LinearSearchProject, LinearTeam,
+    LinearTeamWorkflowState,
+};
 
-// GraphQL Response types for Linear Issues API
-#[derive(Debug, Deserialize)]
-struct LinearIssuesResponse {
-    data: Option<LinearIssuesData>,
-    errors: Option<Vec<LinearError>>,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearIssuesData {
-    project: LinearProjectWithIssues,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearProjectWithIssues {
-    issues: LinearIssuesNodes,
-}
-
-#[derive(Debug, Deserialize)]
-struct LinearIssuesNodes {
-    nodes: Vec<LinearApiIssue>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearApiIssue {
-    pub id: String,
-    pub identifier: String,
-    pub title: String,
-    pub description: Option<String>,
-    pub url: String,
-    pub priority: Option<i32>,
-    #[serde(rename = "priorityLabel")]
-    pub priority_label: Option<String>,
-    #[serde(rename = "dueDate")]
-    pub due_date: Option<String>,
-    pub estimate: Option<f64>,
-    pub state: Option<LinearWorkflowState>,
-    pub assignee: Option<LinearUser>,
-    pub labels: Option<LinearLabelsNodes>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearWorkflowState {
-    pub id: String,
-    pub name: String,
-    pub color: String,
-    #[serde(rename = "type")]
-    pub state_type: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearUser {
-    pub id: String,
-    pub name: String,
-    pub email: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearLabelsNodes {
-    pub nodes: Vec<LinearLabel>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearLabel {
-    pub id: String,
-    pub name: String,
-    pub color: String,
-}
+const LINEAR_PROJECT_SELECT_COLUMNS: &str = "id, linear_project_id, linear_team_id, name, description, state, is_active_timebox_project, created_at, updated_at, archived_at, deleted_at";
 
 // Command: Fetch teams from Linear
 #[tauri::command]
-pub fn get_linear_teams(api_key: String) -> Result<Vec<LinearTeam>, String> {
-    let client = reqwest::blocking::Client::new();
-    // Fetch up to 100 teams (Linear max is 250)
-    let query = r#"{ "query": "{ teams(first: 100) { nodes { id name } } }" }"#;
-
-    let response = client
-        .post("https://api.linear.app/graphql")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .body(query)
-        .send()
-        .map_err(|e| format!("Failed to connect to Linear: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Linear API returned status: {}", response.status()));
-    }
-
-    let result: LinearTeamsResponse = response
-        .json()
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-    if let Some(errors) = result.errors {
-        return Err(errors
-            .into_iter()
-            .map(|e| e.message)
-            .collect::<Vec<_>>()
-            .join(", "));
-    }
-
-    Ok(result.data.map(|d| d.teams.nodes).unwrap_or_default())
+pub async fn get_linear_teams(api_key: String, api_base_url: Option<String>) -> Result<Vec<LinearTeam>, ApiError> {
+    LinearClient::new(&api_key, api_base_url.as_deref()).teams().await
 }
 
 // Command: Fetch projects for a team from Linear
 #[tauri::command]
-pub fn get_linear_team_projects(api_key: String, team_id: String) -> Result<Vec<LinearApiProject>, String> {
-    let client = reqwest::blocking::Client::new();
-    // Fetch up to 250 projects (Linear's max per request)
-    let query = format!(
-        r#"{{ "query": "{{ team(id: \"{}\") {{ projects(first: 250) {{ nodes {{ id name description state }} }} }} }}" }}"#,
-        team_id
-    );
-
-    let response = client
-        .post("https://api.linear.app/graphql")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .body(query)
-        .send()
-        .map_err(|e| format!("Failed to connect to Linear: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Linear API returned status: {}", response.status()));
-    }
-
-    let result: LinearProjectsResponse = response
-        .json()
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-    if let Some(errors) = result.errors {
-        return Err(errors
-            .into_iter()
-            .map(|e| e.message)
-            .collect::<Vec<_>>()
-            .join(", "));
+pub async fn get_linear_team_projects(
+    api_key: String,
+    team_id: String,
+    api_base_url: Option<String>,
+) -> Result<Vec<LinearApiProject>, ApiError> {
+    LinearClient::new(&api_key, api_base_url.as_deref()).team_projects(&team_id).await
+}
+
+/// Saves a Linear project locally (upsert). A new project gets the default start rule,
+/// and its team's workflow states are fetched so the rule resolves, offline too. Failing
+/// to fetch them doesn't fail the save; the background refresh tries again.
+pub async fn save_project(state: &AppState, request: &SaveLinearProjectRequest) -> Result<LinearProject, String> {
+    let (project, is_new) = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        store_project(&conn, request)?
+    };
+
+    if is_new {
+        if let Err(e) = team_workflow_states(state, &project.linear_team_id, false).await {
+            eprintln!("Failed to fetch workflow states of Linear team {}: {}", project.linear_team_id, e);
+        }
     }
 
-    Ok(result.data.map(|d| d.team.projects.nodes).unwrap_or_default())
+    Ok(project)
 }
 
 // Command: Save a Linear project to local DB (upsert)
 #[tauri::command]
-pub fn save_linear_project(
+pub async fn save_linear_project(
     state: State<'_, AppState>,
     request: SaveLinearProjectRequest,
 ) -> Result<LinearProject, String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    save_project(&state, &request).await
+}
+
+// Returns the project and whether it is new
+fn store_project(conn: &rusqlite::Connection, request: &SaveLinearProjectRequest) -> Result<(LinearProject, bool), String> {
     let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
+    let is_new: bool = conn
+        .query_row(
+            "SELECT NOT EXISTS (SELECT 1 FROM linear_projects WHERE linear_project_id = ?1)",
+            params![request.linear_project_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
     // Upsert: insert or update if exists
     conn.execute(
         r#"INSERT INTO linear_projects (linear_project_id, linear_team_id, name, description, state, created_at, updated_at)
@@ -240,8 +106,15 @@ pub fn save_linear_project(
         ))
         .map_err(|e| e.to_string())?;
 
-    stmt.query_row(params![request.linear_project_id], LinearProject::from_row)
-        .map_err(|e| e.to_string())
+    let project = stmt
+        .query_row(params![request.linear_project_id], LinearProject::from_row)
+        .map_err(|e| e.to_string())?;
+
+    if is_new {
+        state_rules::set_default_state_rule(conn, project.id, &project.linear_team_id)?;
+    }
+
+    Ok((project, is_new))
 }
 
 // Command: Toggle is_active_timebox_project
@@ -364,33 +237,6 @@ pub fn delete_linear_project(
 // Linear Issue API Commands
 // ============================================
 
-// GraphQL Response types for Linear Issue API
-#[derive(Debug, Deserialize)]
-struct IssueCreateResponse {
-    data: Option<IssueCreateData>,
-    errors: Option<Vec<LinearError>>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct IssueCreateData {
-    issue_create: IssueCreateResult,
-}
-
-#[derive(Debug, Deserialize)]
-struct IssueCreateResult {
-    success: bool,
-    issue: Option<LinearIssue>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearIssue {
-    pub id: String,
-    pub identifier: String,
-    pub url: String,
-    pub title: String,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateLinearIssueRequest {
     pub title: String,
@@ -408,383 +254,388 @@ pub struct CreateLinearIssueResult {
 
 // Command: Create a Linear issue
 #[tauri::command]
-pub fn create_linear_issue(
+pub async fn create_linear_issue(
     api_key: String,
     request: CreateLinearIssueRequest,
-) -> Result<CreateLinearIssueResult, String> {
-    let client = reqwest::blocking::Client::new();
-
-    // Escape special characters for GraphQL
-    let title_escaped = request.title.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
-    let description_escaped = request
-        .description
-        .clone()
-        .unwrap_or_default()
-        .replace('\\', "\\\\")
-        .replace('"', "\\\"")
-        .replace('\n', "\\n");
-
-    let query = format!(
-        r#"{{ "query": "mutation {{ issueCreate(input: {{ title: \"{}\", description: \"{}\", projectId: \"{}\", teamId: \"{}\" }}) {{ success issue {{ id identifier url title }} }} }}" }}"#,
-        title_escaped,
-        description_escaped,
-        request.project_id,
-        request.team_id
-    );
-
-    let response = client
-        .post("https://api.linear.app/graphql")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .body(query)
-        .send()
-        .map_err(|e| format!("Failed to connect to Linear: {}", e))?;
-
-    if !response.status().is_success() {
-        return Ok(CreateLinearIssueResult {
-            success: false,
-            issue: None,
-            error: Some(format!("Linear API returned status: {}", response.status())),
-        });
-    }
-
-    let result: IssueCreateResponse = response
-        .json()
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-    if let Some(errors) = result.errors {
-        let error_msg = errors
-            .into_iter()
-            .map(|e| e.message)
-            .collect::<Vec<_>>()
-            .join(", ");
-        return Ok(CreateLinearIssueResult {
-            success: false,
-            issue: None,
-            error: Some(error_msg),
-        });
-    }
-
-    match result.data {
-        Some(data) => Ok(CreateLinearIssueResult {
-            success: data.issue_create.success,
-            issue: data.issue_create.issue,
+    api_base_url: Option<String>,
+) -> Result<CreateLinearIssueResult, ApiError> {
+    let input = IssueCreateInput {
+        title: request.title,
+        description: request.description,
+        project_id: request.project_id,
+        team_id: request.team_id,
+    };
+
+    match LinearClient::new(&api_key, api_base_url.as_deref()).create_issue(&input).await {
+        Ok(created) => Ok(CreateLinearIssueResult {
+            success: created.success,
+            issue: created.issue,
             error: None,
         }),
-        None => Ok(CreateLinearIssueResult {
+        Err(e) if e.is_rejection() => Ok(CreateLinearIssueResult {
             success: false,
             issue: None,
-            error: Some("No data returned from Linear".to_string()),
+            error: Some(e.to_string()),
         }),
+        Err(e) => Err(e),
     }
 }
 
-// GraphQL Response types for workflow states
-#[derive(Debug, Deserialize)]
-struct TeamStatesResponse {
-    data: Option<TeamStatesData>,
-    errors: Option<Vec<LinearError>>,
+// Command: Get workflow states for a team
+#[tauri::command]
+pub async fn get_linear_team_states(
+    api_key: String,
+    team_id: String,
+    api_base_url: Option<String>,
+) -> Result<Vec<LinearTeamWorkflowState>, ApiError> {
+    LinearClient::new(&api_key, api_base_url.as_deref()).team_states(&team_id).await
 }
 
-#[derive(Debug, Deserialize)]
-struct TeamStatesData {
-    team: TeamWithStates,
+// Command: Update a Linear issue's state
+#[tauri::command]
+pub async fn update_linear_issue_state(
+    api_key: String,
+    issue_id: String,
+    state_id: String,
+    api_base_url: Option<String>,
+) -> Result<bool, ApiError> {
+    LinearClient::new(&api_key, api_base_url.as_deref()).update_issue_state(&issue_id, &state_id).await
 }
 
-#[derive(Debug, Deserialize)]
-struct TeamWithStates {
-    states: StatesNodes,
-}
+// Command: Fetch issues for a project from Linear
+#[tauri::command]
+pub async fn get_linear_project_issues(
+    api_key: String,
+    project_id: String,
+    api_base_url: Option<String>,
+) -> Result<Vec<LinearApiIssue>, ApiError> {
+    LinearClient::new(&api_key, api_base_url.as_deref())
+        .project_issues(&project_id)
+        .await
+        .map(|paged| paged.nodes)
+}
+
+/// Brings the local copy of a project's issues up to date. Only issues changed since
+/// the last sync are fetched, unless `full` is set or the last full sync is a day old.
+/// The DB lock is not held during the fetch.
+pub async fn sync_project_issues(state: &AppState, project_id: &str, full: bool) -> Result<IssueSyncResult, String> {
+    let (sync, config) = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        (issue_cache::sync_state(&conn, project_id)?, get_linear_config(&conn))
+    };
+    let client = LinearClient::from_config(&config.ok_or("Linear is not connected")?)?;
+
+    let now = Local::now().naive_local();
+    let since = sync
+        .filter(|sync| !full && !sync.needs_full_sync(now))
+        .and_then(|sync| sync.last_updated_at);
+    let issues = match &since {
+        Some(since) => client.project_issues_updated_since(project_id, since).await?,
+        None => client.project_issues(project_id).await?,
+    };
 
-#[derive(Debug, Deserialize)]
-struct StatesNodes {
-    nodes: Vec<LinearTeamWorkflowState>,
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    issue_cache::store_synced_issues(&conn, project_id, &issues.nodes, since.is_none(), issues.truncated)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearTeamWorkflowState {
-    pub id: String,
-    pub name: String,
-    #[serde(rename = "type")]
-    pub state_type: String,
+// Command: Get a project's issues from the local cache, without touching the network
+#[tauri::command]
+pub fn get_cached_linear_project_issues(
+    state: State<'_, AppState>,
+    project_id: String,
+) -> Result<CachedProjectIssues, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    issue_cache::cached_project_issues(&conn, &project_id)
 }
 
-// Command: Get workflow states for a team
+// Command: Sync a project's issues from Linear into the local cache
 #[tauri::command]
-pub fn get_linear_team_states(api_key: String, team_id: String) -> Result<Vec<LinearTeamWorkflowState>, String> {
-    let client = reqwest::blocking::Client::new();
-
-    let query = format!(
-        r#"{{ "query": "{{ team(id: \"{}\") {{ states {{ nodes {{ id name type }} }} }} }}" }}"#,
-        team_id
-    );
-
-    let response = client
-        .post("https://api.linear.app/graphql")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .body(query)
-        .send()
-        .map_err(|e| format!("Failed to connect to Linear: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Linear API returned status: {}", response.status()));
+pub async fn sync_linear_project_issues(
+    state: State<'_, AppState>,
+    project_id: String,
+    full: bool,
+) -> Result<IssueSyncResult, String> {
+    sync_project_issues(&state, &project_id, full).await
+}
+
+/// Fills in the Linear issue id and url for a request that only has an identifier,
+/// when Linear is connected. The DB lock is not held during the lookup, and the
+/// identifier is kept even if the lookup fails so the link isn't lost.
+pub async fn resolve_linear_issue(state: &AppState, request: &mut CreateTimeboxRequest) -> Result<(), String> {
+    let Some(identifier) = request.linear_issue_identifier.clone() else {
+        return Ok(());
+    };
+
+    let config = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        get_linear_config(&conn)
+    };
+    let Some(config) = config else {
+        return Ok(());
+    };
+    let client = LinearClient::from_config(&config)?;
+
+    match client.find_issue(&identifier).await {
+        Ok(Some(issue)) => {
+            request.linear_issue_id = Some(issue.id);
+            request.linear_issue_identifier = Some(issue.identifier);
+            request.linear_issue_url = Some(issue.url);
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to look up Linear issue {}: {}", identifier, e),
     }
 
-    let result: TeamStatesResponse = response
-        .json()
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    Ok(())
+}
+
+// ============================================
+// Linear State Transition Commands
+// ============================================
 
-    if let Some(errors) = result.errors {
-        return Err(errors
-            .into_iter()
-            .map(|e| e.message)
-            .collect::<Vec<_>>()
-            .join(", "));
+/// A team's workflow states, fetched from Linear and cached when `refresh` is set or
+/// nothing is cached yet. The DB lock is not held during the fetch.
+pub async fn team_workflow_states(
+    state: &AppState,
+    team_id: &str,
+    refresh: bool,
+) -> Result<Vec<LinearTeamWorkflowState>, String> {
+    let (cached, config) = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        (state_rules::cached_team_states(&conn, team_id)?, get_linear_config(&conn))
+    };
+    if !refresh && !cached.is_empty() {
+        return Ok(cached);
     }
 
-    Ok(result.data.map(|d| d.team.states.nodes).unwrap_or_default())
+    let config = config.ok_or("Linear is not connected")?;
+    let states = LinearClient::from_config(&config)?.team_states(team_id).await?;
+
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    state_rules::cache_team_states(&conn, team_id, &states)?;
+    Ok(states)
+}
+
+/// Fetches the workflow states of teams whose default rules can't resolve yet, such as
+/// projects saved before states were fetched on save
+pub async fn cache_missing_team_states(state: &AppState) -> Result<(), String> {
+    let teams = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        state_rules::teams_missing_states(&conn)?
+    };
+    for team_id in teams {
+        team_workflow_states(state, &team_id, false).await?;
+    }
+    Ok(())
 }
 
-// GraphQL Response types for issue update
-#[derive(Debug, Deserialize)]
-struct IssueUpdateResponse {
-    data: Option<IssueUpdateData>,
-    errors: Option<Vec<LinearError>>,
+/// Sets the state an event moves a saved project's issues to, refreshing the team's
+/// cached states when the name isn't among them. `None` removes the rule.
+pub async fn save_state_rule(
+    state: &AppState,
+    linear_project_id: i64,
+    event: ProviderHook,
+    state_name: Option<String>,
+) -> Result<Vec<LinearStateRule>, String> {
+    if let Some(name) = state_name.as_deref() {
+        let team_id: String = {
+            let conn = state.db.lock().map_err(|e| e.to_string())?;
+            conn.query_row(
+                "SELECT linear_team_id FROM linear_projects WHERE id = ?1 AND deleted_at IS NULL",
+                params![linear_project_id],
+                |row| row.get(0),
+            )
+            .map_err(|_| format!("Linear project {} not found", linear_project_id))?
+        };
+
+        let has_state = |states: &[LinearTeamWorkflowState]| states.iter().any(|s| s.name.eq_ignore_ascii_case(name.trim()));
+        if !has_state(&team_workflow_states(state, &team_id, false).await?)
+            && !has_state(&team_workflow_states(state, &team_id, true).await?)
+        {
+            return Err(format!("The team has no '{}' workflow state", name.trim()));
+        }
+    }
+
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    state_rules::set_state_rule(&conn, linear_project_id, event, state_name.as_deref())?;
+    state_rules::state_rules(&conn, linear_project_id)
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct IssueUpdateData {
-    issue_update: IssueUpdateResult,
+// Command: Get a team's workflow states, from the local cache unless refresh is set
+#[tauri::command]
+pub async fn get_linear_workflow_states(
+    state: State<'_, AppState>,
+    team_id: String,
+    refresh: bool,
+) -> Result<Vec<LinearTeamWorkflowState>, String> {
+    team_workflow_states(&state, &team_id, refresh).await
 }
 
-#[derive(Debug, Deserialize)]
-struct IssueUpdateResult {
-    success: bool,
+// Command: Get the state transition rules of a saved Linear project
+#[tauri::command]
+pub fn get_linear_state_rules(state: State<'_, AppState>, linear_project_id: i64) -> Result<Vec<LinearStateRule>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    state_rules::state_rules(&conn, linear_project_id)
 }
 
-// Command: Update a Linear issue's state
+// Command: Set or clear the state a timebox event moves a project's issues to
 #[tauri::command]
-pub fn update_linear_issue_state(
-    api_key: String,
-    issue_id: String,
-    state_id: String,
-) -> Result<bool, String> {
-    let client = reqwest::blocking::Client::new();
-
-    let query = format!(
-        r#"{{ "query": "mutation {{ issueUpdate(id: \"{}\", input: {{ stateId: \"{}\" }}) {{ success }} }}" }}"#,
-        issue_id,
-        state_id
-    );
-
-    let response = client
-        .post("https://api.linear.app/graphql")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .body(query)
-        .send()
-        .map_err(|e| format!("Failed to connect to Linear: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Linear API returned status: {}", response.status()));
-    }
+pub async fn set_linear_state_rule(
+    state: State<'_, AppState>,
+    linear_project_id: i64,
+    event: ProviderHook,
+    state_name: Option<String>,
+) -> Result<Vec<LinearStateRule>, String> {
+    save_state_rule(&state, linear_project_id, event, state_name).await
+}
 
-    let result: IssueUpdateResponse = response
-        .json()
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+// Command: Dry run of what starting, stopping and finishing a timebox would do to its issue
+#[tauri::command]
+pub fn preview_linear_state_transitions(state: State<'_, AppState>, timebox_id: i64) -> Result<Vec<StateTransition>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let timebox = TimeboxService::new(&conn).get(timebox_id)?;
+    state_rules::preview_transitions(&conn, &timebox)
+}
 
-    if let Some(errors) = result.errors {
-        return Err(errors
-            .into_iter()
-            .map(|e| e.message)
-            .collect::<Vec<_>>()
-            .join(", "));
-    }
+// ============================================
+// Linear Planning Commands
+// ============================================
 
-    Ok(result.data.map(|d| d.issue_update.success).unwrap_or(false))
+/// A client for the connected Linear integration. The DB lock is released before
+/// returning, so the caller can await requests.
+fn connected_linear_client(state: &AppState) -> Result<LinearClient, String> {
+    let config = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        get_linear_config(&conn)
+    };
+    LinearClient::from_config(&config.ok_or("Linear is not connected")?)
 }
 
-// Command: Fetch issues for a project from Linear
+// Command: Fetch the open issues assigned to the connected Linear user
 #[tauri::command]
-pub fn get_linear_project_issues(
-    api_key: String,
-    project_id: String,
-) -> Result<Vec<LinearApiIssue>, String> {
-    let client = reqwest::blocking::Client::new();
-
-    let query = format!(
-        r#"{{ "query": "{{ project(id: \"{}\") {{ issues(first: 250) {{ nodes {{ id identifier title description url priority priorityLabel dueDate estimate state {{ id name color type }} assignee {{ id name email }} labels {{ nodes {{ id name color }} }} }} }} }} }}" }}"#,
-        project_id
-    );
-
-    let response = client
-        .post("https://api.linear.app/graphql")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .body(query)
-        .send()
-        .map_err(|e| format!("Failed to connect to Linear: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Linear API returned status: {}", response.status()));
-    }
+pub async fn get_linear_my_issues(state: State<'_, AppState>) -> Result<Vec<LinearPlanningIssue>, String> {
+    Ok(connected_linear_client(&state)?.assigned_issues().await?)
+}
 
-    let result: LinearIssuesResponse = response
-        .json()
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+// Command: Fetch a team's active cycle and its issues, None when no cycle is running
+#[tauri::command]
+pub async fn get_linear_current_cycle(state: State<'_, AppState>, team_id: String) -> Result<Option<LinearCycle>, String> {
+    Ok(connected_linear_client(&state)?.active_cycle(&team_id).await?)
+}
 
-    if let Some(errors) = result.errors {
-        return Err(errors
-            .into_iter()
-            .map(|e| e.message)
-            .collect::<Vec<_>>()
-            .join(", "));
-    }
+// Command: Get the table that turns issue estimates into timebox minutes
+#[tauri::command]
+pub fn get_linear_estimate_minutes(state: State<'_, AppState>) -> Result<Vec<EstimateMinutes>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(planning::estimate_table(&get_linear_config(&conn).unwrap_or_default()))
+}
 
-    Ok(result.data.map(|d| d.project.issues.nodes).unwrap_or_default())
+// Command: Create a timebox for each selected issue, sized by its estimate
+#[tauri::command]
+pub fn create_timeboxes_from_linear_issues(
+    state: State<'_, AppState>,
+    issues: Vec<PlannedIssue>,
+    unestimated_minutes: Option<i64>,
+) -> Result<PlannedTimeboxes, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let table = planning::estimate_table(&get_linear_config(&conn).unwrap_or_default());
+    planning::create_timeboxes_from_issues(
+        &conn,
+        &issues,
+        &table,
+        unestimated_minutes.unwrap_or(planning::DEFAULT_UNESTIMATED_MINUTES),
+    )
 }
 
 // ============================================
-// Linear Team Search API Commands
+// Linear Issue Link Commands
 // ============================================
 
-// GraphQL Response types for team search
-#[derive(Debug, Deserialize)]
-struct LinearSearchTeamsResponse {
-    data: Option<LinearSearchTeamsData>,
-    errors: Option<Vec<LinearError>>,
-}
+/// Checks every linked timebox's issue against Linear and records which links broke.
+/// Does nothing without linked timeboxes, even when Linear isn't connected.
+pub async fn validate_issue_links(state: &AppState) -> Result<LinkValidationResult, String> {
+    let linked = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        link_validation::linked_timeboxes(&conn)?
+    };
+    let issues = if linked.is_empty() {
+        Vec::new()
+    } else {
+        let mut ids: Vec<String> = linked.iter().map(|l| l.linear_issue_id.clone()).collect();
+        ids.sort();
+        ids.dedup();
+        connected_linear_client(state)?.issues_by_id(&ids).await?
+    };
 
-#[derive(Debug, Deserialize)]
-struct LinearSearchTeamsData {
-    teams: LinearTeamsNodes,
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    link_validation::store_link_checks(&conn, &linked, &issues)
 }
 
-// Command: Search teams by name
+// Command: Check that linked Linear issues still exist and weren't archived or moved
 #[tauri::command]
-pub fn search_linear_teams(
-    api_key: String,
-    search_term: String,
-) -> Result<Vec<LinearTeam>, String> {
-    let client = reqwest::blocking::Client::new();
-
-    // Escape special characters for GraphQL
-    let search_escaped = search_term
-        .replace('\\', "\\\\")
-        .replace('"', "\\\"");
-
-    // Search teams with name filter (case insensitive contains)
-    let query = format!(
-        r#"{{ "query": "{{ teams(first: 50, filter: {{ name: {{ containsIgnoreCase: \"{}\" }} }}) {{ nodes {{ id name }} }} }}" }}"#,
-        search_escaped
-    );
-
-    let response = client
-        .post("https://api.linear.app/graphql")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .body(query)
-        .send()
-        .map_err(|e| format!("Failed to connect to Linear: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Linear API returned status: {}", response.status()));
-    }
-
-    let result: LinearSearchTeamsResponse = response
-        .json()
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-    if let Some(errors) = result.errors {
-        return Err(errors
-            .into_iter()
-            .map(|e| e.message)
-            .collect::<Vec<_>>()
-            .join(", "));
-    }
-
-    Ok(result.data.map(|d| d.teams.nodes).unwrap_or_default())
+pub async fn validate_linear_issue_links(state: State<'_, AppState>) -> Result<LinkValidationResult, String> {
+    validate_issue_links(&state).await
 }
 
 // ============================================
-// Linear Project Search API Commands
+// Linear Project Refresh Commands
 // ============================================
 
-// GraphQL Response types for project search
-#[derive(Debug, Deserialize)]
-struct LinearSearchProjectsResponse {
-    data: Option<LinearSearchProjectsData>,
-    errors: Option<Vec<LinearError>>,
+/// Refreshes every saved project from Linear and archives the ones archived or completed
+/// there, logging what changed. The DB lock is not held during the fetch.
+pub async fn refresh_saved_projects(state: &AppState) -> Result<ProjectRefreshResult, String> {
+    let ids = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        project_sync::saved_project_ids(&conn)?
+    };
+    let projects = if ids.is_empty() {
+        Vec::new()
+    } else {
+        connected_linear_client(state)?.projects_by_id(&ids).await?
+    };
+
+    let result = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        project_sync::store_refreshed_projects(&conn, &ids, &projects)?
+    };
+    for change in &result.changed {
+        eprintln!("{}", change.describe());
+    }
+    for id in &result.missing {
+        eprintln!("Linear project {} no longer exists in Linear, leaving it as is", id);
+    }
+    Ok(result)
 }
 
-#[derive(Debug, Deserialize)]
-struct LinearSearchProjectsData {
-    projects: LinearSearchProjectsNodes,
+// Command: Refresh saved projects' name, description and state from Linear
+#[tauri::command]
+pub async fn refresh_linear_projects(state: State<'_, AppState>) -> Result<ProjectRefreshResult, String> {
+    refresh_saved_projects(&state).await
 }
 
-#[derive(Debug, Deserialize)]
-struct LinearSearchProjectsNodes {
-    nodes: Vec<LinearSearchProject>,
-}
+// ============================================
+// Linear Team Search API Commands
+// ============================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearSearchProject {
-    pub id: String,
-    pub name: String,
-    pub description: Option<String>,
-    pub state: Option<String>,
-    pub teams: LinearSearchProjectTeams,
+// Command: Search teams by name
+#[tauri::command]
+pub async fn search_linear_teams(
+    api_key: String,
+    search_term: String,
+    api_base_url: Option<String>,
+) -> Result<Vec<LinearTeam>, ApiError> {
+    LinearClient::new(&api_key, api_base_url.as_deref()).search_teams(&search_term).await
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinearSearchProjectTeams {
-    pub nodes: Vec<LinearTeam>,
-}
+// ============================================
+// Linear Project Search API Commands
+// ============================================
 
 // Command: Search projects across all teams by name
 #[tauri::command]
-pub fn search_linear_projects(
+pub async fn search_linear_projects(
     api_key: String,
     search_term: String,
-) -> Result<Vec<LinearSearchProject>, String> {
-    let client = reqwest::blocking::Client::new();
-
-    // Escape special characters for GraphQL
-    let search_escaped = search_term
-        .replace('\\', "\\\\")
-        .replace('"', "\\\"");
-
-    // Search projects with name filter (case insensitive contains)
-    let query = format!(
-        r#"{{ "query": "{{ projects(first: 50, filter: {{ name: {{ containsIgnoreCase: \"{}\" }} }}) {{ nodes {{ id name description state teams {{ nodes {{ id name }} }} }} }} }}" }}"#,
-        search_escaped
-    );
-
-    let response = client
-        .post("https://api.linear.app/graphql")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .body(query)
-        .send()
-        .map_err(|e| format!("Failed to connect to Linear: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Linear API returned status: {}", response.status()));
-    }
-
-    let result: LinearSearchProjectsResponse = response
-        .json()
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-    if let Some(errors) = result.errors {
-        return Err(errors
-            .into_iter()
-            .map(|e| e.message)
-            .collect::<Vec<_>>()
-            .join(", "));
-    }
-
-    Ok(result.data.map(|d| d.projects.nodes).unwrap_or_default())
+    api_base_url: Option<String>,
+) -> Result<Vec<LinearSearchProject>, ApiError> {
+    LinearClient::new(&api_key, api_base_url.as_deref()).search_projects(&search_term).await
 }
@@ -0,0 +1,404 @@
+use super::{
+    create_linear_issue, get_linear_project_issues, get_linear_team_projects, get_linear_teams, search_linear_projects,
+    search_linear_teams, update_linear_issue_state, CreateLinearIssueRequest,
+};
+use crate::commands::test_linear_connection;
+use crate::integrations::http::ApiErrorKind;
+use crate::integrations::linear::LinearClient;
+use crate::mock_server::{unreachable_url, MockServer};
+use serde_json::json;
+use tauri::async_runtime::block_on;
+
+const API_KEY: &str = "lin_api_test";
+
+fn issue_request(title: &str, description: Option<&str>) -> CreateLinearIssueRequest {
+    CreateLinearIssueRequest {
+        title: title.to_string(),
+        description: description.map(str::to_string),
+        project_id: "project-1".to_string(),
+        team_id: "team-1".to_string(),
+    }
+}
+
+#[test]
+fn test_connection_success() {
+    let server = MockServer::start();
+    server.respond_json(
+        200,
+        json!({ "data": { "viewer": { "id": "user-1", "name": "Ada Lovelace", "email": "ada@example.com" } } }),
+    );
+
+    let result = block_on(test_linear_connection(API_KEY.to_string(), Some(server.url()))).unwrap();
+    assert!(result.success);
+    assert_eq!(result.user_name.as_deref(), Some("Ada Lovelace"));
+    assert_eq!(result.user_email.as_deref(), Some("ada@example.com"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/graphql");
+    assert_eq!(requests[0].authorization.as_deref(), Some(API_KEY));
+    assert!(requests[0].graphql_query().contains("viewer"));
+}
+
+#[test]
+fn test_connection_failures() {
+    let server = MockServer::start();
+    server
+        .respond(401, r#"{"errors":[{"message":"Authentication required"}]}"#)
+        .respond_json(
+            200,
+            json!({ "errors": [{ "message": "Invalid API key" }, { "message": "Try again" }] }),
+        )
+        .respond_json(200, json!({ "data": null }))
+        .respond(200, "<html>not json</html>");
+
+    let result = block_on(test_linear_connection(API_KEY.to_string(), Some(server.url()))).unwrap();
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("401"));
+
+    let result = block_on(test_linear_connection(API_KEY.to_string(), Some(server.url()))).unwrap();
+    assert!(!result.success);
+    assert_eq!(result.error.as_deref(), Some("Invalid API key, Try again"));
+
+    let result = block_on(test_linear_connection(API_KEY.to_string(), Some(server.url()))).unwrap();
+    assert_eq!(result.error.as_deref(), Some("No data returned from Linear"));
+
+    let error = block_on(test_linear_connection(API_KEY.to_string(), Some(server.url()))).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::InvalidResponse);
+    assert!(error.message.starts_with("Failed to parse Linear response"), "{}", error);
+
+    let error = block_on(test_linear_connection(API_KEY.to_string(), Some(unreachable_url()))).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Offline);
+    assert!(error.message.starts_with("Failed to connect to Linear"), "{}", error);
+}
+
+#[test]
+fn test_project_issues() {
+    let server = MockServer::start();
+    server.respond_json(
+        200,
+        json!({ "data": { "project": { "issues": { "nodes": [
+            {
+                "id": "issue-1",
+                "identifier": "ENG-1",
+                "title": "Fix login",
+                "description": "Users get logged out",
+                "url": "https://linear.app/acme/issue/ENG-1",
+                "priority": 2,
+                "priorityLabel": "High",
+                "dueDate": "2025-03-14",
+                "estimate": 3.0,
+                "state": { "id": "state-1", "name": "Todo", "color": "#e2e2e2", "type": "unstarted" },
+                "assignee": { "id": "user-1", "name": "Ada", "email": null },
+                "labels": { "nodes": [{ "id": "label-1", "name": "Bug", "color": "#eb5757" }] }
+            },
+            {
+                "id": "issue-2",
+                "identifier": "ENG-2",
+                "title": "Bare issue",
+                "description": null,
+                "url": "https://linear.app/acme/issue/ENG-2",
+                "priority": null,
+                "priorityLabel": null,
+                "dueDate": null,
+                "estimate": null,
+                "state": null,
+                "assignee": null,
+                "labels": null
+            }
+        ], "pageInfo": { "hasNextPage": false, "endCursor": null } } } } }),
+    );
+
+    let issues = block_on(get_linear_project_issues(API_KEY.to_string(), "project-1".to_string(), Some(server.url()))).unwrap();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].identifier, "ENG-1");
+    assert_eq!(issues[0].priority_label.as_deref(), Some("High"));
+    assert_eq!(issues[0].estimate, Some(3.0));
+    assert_eq!(issues[0].state.as_ref().unwrap().state_type, "unstarted");
+    assert_eq!(issues[0].labels.as_ref().unwrap().nodes[0].name, "Bug");
+    assert!(issues[1].state.is_none());
+
+    assert_eq!(server.requests()[0].json()["variables"]["projectId"], "project-1");
+}
+
+#[test]
+fn test_project_issues_failures() {
+    let server = MockServer::start();
+    server
+        // Reads are retried, so the 500 has to come back every time
+        .respond(500, "Internal Server Error")
+        .respond(500, "Internal Server Error")
+        .respond(500, "Internal Server Error")
+        .respond_json(200, json!({ "errors": [{ "message": "Entity not found: Project" }] }))
+        .respond(200, r#"{"data": {"project": {"issues": "#);
+
+    let fetch = || block_on(get_linear_project_issues(API_KEY.to_string(), "missing".to_string(), Some(server.url())));
+
+    let error = fetch().unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Server);
+    assert_eq!(error.status, Some(500));
+    assert_eq!(error.message, "Linear API returned status: 500 Internal Server Error: Internal Server Error");
+    assert_eq!(server.requests().len(), 3);
+
+    let error = fetch().unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Rejected);
+    assert_eq!(error.message, "Entity not found: Project");
+
+    assert!(fetch().unwrap_err().message.starts_with("Failed to parse Linear response"));
+}
+
+#[test]
+fn test_create_issue() {
+    let server = MockServer::start();
+    server.respond_json(
+        200,
+        json!({ "data": { "issueCreate": { "success": true, "issue": {
+            "id": "issue-9",
+            "identifier": "ENG-9",
+            "url": "https://linear.app/acme/issue/ENG-9",
+            "title": "Write RFC"
+        } } } }),
+    );
+
+    let result = block_on(create_linear_issue(
+        API_KEY.to_string(),
+        issue_request("Write RFC", Some("First draft")),
+        Some(server.url()),
+    ))
+    .unwrap();
+    assert!(result.success);
+    assert_eq!(result.issue.unwrap().identifier, "ENG-9");
+
+    let body = server.requests()[0].json();
+    assert_eq!(
+        body["variables"]["input"],
+        json!({ "title": "Write RFC", "description": "First draft", "projectId": "project-1", "teamId": "team-1" })
+    );
+}
+
+#[test]
+fn test_create_issue_failures() {
+    let server = MockServer::start();
+    server
+        .respond(403, "Forbidden")
+        .respond_json(200, json!({ "errors": [{ "message": "teamId must be a UUID" }] }))
+        .respond_json(200, json!({ "data": { "issueCreate": { "success": false, "issue": null } } }))
+        .respond(200, "{}{}");
+
+    let create = || block_on(create_linear_issue(API_KEY.to_string(), issue_request("Task", None), Some(server.url())));
+
+    let result = create().unwrap();
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("403"));
+
+    let result = create().unwrap();
+    assert_eq!(result.error.as_deref(), Some("teamId must be a UUID"));
+
+    let result = create().unwrap();
+    assert!(!result.success);
+    assert!(result.issue.is_none());
+
+    assert!(create().unwrap_err().message.starts_with("Failed to parse Linear response"));
+}
+
+#[test]
+fn test_update_issue_state() {
+    let server = MockServer::start();
+    server
+        .respond_json(200, json!({ "data": { "issueUpdate": { "success": true } } }))
+        .respond_json(200, json!({ "errors": [{ "message": "Invalid stateId" }] }))
+        .respond(502, "Bad Gateway")
+        .respond(502, "Bad Gateway")
+        .respond(502, "Bad Gateway")
+        .respond(200, "not json");
+
+    let update = || {
+        block_on(update_linear_issue_state(
+            API_KEY.to_string(),
+            "issue-1".to_string(),
+            "state-2".to_string(),
+            Some(server.url()),
+        ))
+    };
+
+    assert!(update().unwrap());
+    assert_eq!(
+        server.requests()[0].json()["variables"],
+        json!({ "id": "issue-1", "stateId": "state-2" })
+    );
+
+    assert_eq!(update().unwrap_err().message, "Invalid stateId");
+    assert_eq!(update().unwrap_err().status, Some(502));
+    assert!(update().unwrap_err().message.starts_with("Failed to parse Linear response"));
+}
+
+// Text that broke the old string-built queries
+const TRICKY_TEXTS: &[&str] = &[
+    r#"Fix "login" bug"#,
+    r"C:\Users\ada\notes.md",
+    "Trailing backslash \\",
+    "Ünïcödé – 日本語 – emoji 🚀",
+    "# Plan\n\n- [ ] step one\n- [x] `code` and **bold**\n\n```rust\nlet s = \"}\";\n```",
+    "} mutation { issueDelete(id: \"x\") { success } } #",
+];
+
+#[test]
+fn test_create_issue_sends_text_verbatim() {
+    let server = MockServer::start();
+    for _ in TRICKY_TEXTS {
+        server.respond_json(200, json!({ "data": { "issueCreate": { "success": true, "issue": null } } }));
+    }
+
+    for text in TRICKY_TEXTS {
+        block_on(create_linear_issue(API_KEY.to_string(), issue_request(text, Some(text)), Some(server.url()))).unwrap();
+    }
+
+    for (request, text) in server.requests().iter().zip(TRICKY_TEXTS) {
+        let body = request.json();
+        assert_eq!(body["variables"]["input"]["title"], *text);
+        assert_eq!(body["variables"]["input"]["description"], *text);
+        // The query text itself never changes
+        assert_eq!(request.graphql_query(), server.requests()[0].graphql_query());
+        assert!(!request.graphql_query().contains("issueDelete"));
+    }
+}
+
+#[test]
+fn test_create_issue_without_description() {
+    let server = MockServer::start();
+    server.respond_json(200, json!({ "data": { "issueCreate": { "success": true, "issue": null } } }));
+
+    block_on(create_linear_issue(API_KEY.to_string(), issue_request("Task", None), Some(server.url()))).unwrap();
+
+    let input = &server.requests()[0].json()["variables"]["input"];
+    assert!(input.get("description").is_none());
+}
+
+#[test]
+fn test_search_and_lookup_send_variables() {
+    let server = MockServer::start();
+    server
+        .respond_json(200, json!({ "data": { "projects": { "nodes": [], "pageInfo": { "hasNextPage": false, "endCursor": null } } } }))
+        .respond_json(200, json!({ "data": { "teams": { "nodes": [], "pageInfo": { "hasNextPage": false, "endCursor": null } } } }))
+        .respond_json(200, json!({ "data": { "issue": null } }));
+
+    let term = r#"Q1 "launch" \ plan"#;
+    block_on(search_linear_projects(API_KEY.to_string(), term.to_string(), Some(server.url()))).unwrap();
+    block_on(search_linear_teams(API_KEY.to_string(), term.to_string(), Some(server.url()))).unwrap();
+    let client = LinearClient::new(API_KEY, Some(&server.url()));
+    assert!(block_on(client.find_issue("ENG-\"1")).unwrap().is_none());
+
+    let requests = server.requests();
+    assert_eq!(requests[0].json()["variables"]["term"], term);
+    assert_eq!(requests[1].json()["variables"]["term"], term);
+    assert_eq!(requests[2].json()["variables"]["id"], "ENG-\"1");
+    for request in &requests {
+        assert!(!request.graphql_query().contains("launch"));
+    }
+}
+
+fn team_page(ids: &[&str], end_cursor: Option<&str>) -> serde_json::Value {
+    let nodes: Vec<_> = ids.iter().map(|id| json!({ "id": id, "name": format!("Team {}", id) })).collect();
+    json!({ "data": { "teams": {
+        "nodes": nodes,
+        "pageInfo": { "hasNextPage": end_cursor.is_some(), "endCursor": end_cursor }
+    } } })
+}
+
+#[test]
+fn test_teams_follow_cursor_across_pages() {
+    let server = MockServer::start();
+    server
+        .respond_json(200, team_page(&["t1", "t2"], Some("cursor-a")))
+        .respond_json(200, team_page(&["t3"], Some("cursor-b")))
+        .respond_json(200, team_page(&["t4"], None));
+
+    let teams = block_on(get_linear_teams(API_KEY.to_string(), Some(server.url()))).unwrap();
+    let ids: Vec<_> = teams.iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(ids, ["t1", "t2", "t3", "t4"]);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].json()["variables"]["after"].is_null());
+    assert_eq!(requests[1].json()["variables"]["after"], "cursor-a");
+    assert_eq!(requests[2].json()["variables"]["after"], "cursor-b");
+    assert!(requests[0].graphql_query().contains("pageInfo"));
+}
+
+#[test]
+fn test_nested_connection_pagination_keeps_variables() {
+    let server = MockServer::start();
+    let page = |id: &str, end_cursor: Option<&str>| {
+        json!({ "data": { "team": { "projects": {
+            "nodes": [{ "id": id, "name": id, "description": null, "state": "started" }],
+            "pageInfo": { "hasNextPage": end_cursor.is_some(), "endCursor": end_cursor }
+        } } } })
+    };
+    server
+        .respond_json(200, page("p1", Some("next")))
+        .respond_json(200, page("p2", None));
+
+    let projects = block_on(get_linear_team_projects(API_KEY.to_string(), "team-1".to_string(), Some(server.url()))).unwrap();
+    assert_eq!(projects.len(), 2);
+
+    let second = server.requests()[1].json();
+    assert_eq!(second["variables"], json!({ "teamId": "team-1", "after": "next" }));
+}
+
+#[test]
+fn test_pagination_stops_on_error_page() {
+    let server = MockServer::start();
+    server
+        .respond_json(200, team_page(&["t1"], Some("cursor-a")))
+        .respond(404, "Not Found");
+
+    let error = block_on(get_linear_teams(API_KEY.to_string(), Some(server.url()))).unwrap_err();
+    assert_eq!(error.message, "Linear API returned status: 404 Not Found: Not Found");
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_create_issue_is_not_retried_on_server_error() {
+    let server = MockServer::start();
+    server
+        .respond(503, "Service Unavailable")
+        .respond_json(200, json!({ "data": { "issueCreate": { "success": true, "issue": null } } }));
+
+    let result = block_on(create_linear_issue(API_KEY.to_string(), issue_request("Task", None), Some(server.url()))).unwrap();
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("503"));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_rate_limit_errors() {
+    let server = MockServer::start();
+    // Linear answers with a 400 and an empty budget, the reset is far enough off to give up
+    let reset = (chrono::Utc::now().timestamp_millis() + 3_600_000).to_string();
+    server
+        .respond_with_headers(
+            400,
+            &[("X-RateLimit-Requests-Remaining", "0"), ("X-RateLimit-Requests-Reset", &reset)],
+            r#"{"errors":[{"message":"Rate limit exceeded","extensions":{"code":"RATELIMITED"}}]}"#,
+        )
+        .respond_json(
+            200,
+            json!({ "errors": [{ "message": "Rate limit exceeded", "extensions": { "code": "RATELIMITED" } }] }),
+        )
+        .respond_json(
+            200,
+            json!({ "errors": [{ "message": "Authentication required", "extensions": { "code": "AUTHENTICATION_ERROR" } }] }),
+        );
+
+    let error = block_on(get_linear_teams(API_KEY.to_string(), Some(server.url()))).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::RateLimited);
+    assert!(error.retry_after_secs.unwrap() > 3500);
+    assert_eq!(server.requests().len(), 1);
+
+    let error = block_on(get_linear_teams(API_KEY.to_string(), Some(server.url()))).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::RateLimited);
+
+    let error = block_on(get_linear_teams(API_KEY.to_string(), Some(server.url()))).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Auth);
+}
@@ -5,6 +5,14 @@ pub mod linear;
 pub mod idle;
 pub mod settings;
 pub mod sound;
+pub mod quick_capture;
+pub mod duration;
+pub mod webhook;
+pub mod shell_hook;
+pub mod outbox;
+
+#[cfg(test)]
+mod linear_tests;
 
 pub use timebox::*;
 pub use session::*;
@@ -13,3 +21,8 @@ pub use linear::*;
 pub use idle::*;
 pub use settings::*;
 pub use sound::*;
+pub use quick_capture::*;
+pub use duration::*;
+pub use webhook::*;
+pub use shell_hook::*;
+pub use outbox::*;
@@ -0,0 +1,34 @@
+use crate::core::OutboxService;
+use crate::models::{OutboxItem, OutboxStatus};
+use crate::state::AppState;
+use tauri::State;
+
+const DEFAULT_ITEM_LIMIT: i64 = 50;
+
+/// Queued integration side effects, newest first, optionally only those with a status
+#[tauri::command]
+pub fn get_outbox_items(
+    state: State<'_, AppState>,
+    status: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<OutboxItem>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    OutboxService::new(&conn).items(
+        status.as_deref().map(OutboxStatus::from_str),
+        limit.unwrap_or(DEFAULT_ITEM_LIMIT),
+    )
+}
+
+/// Queues an item (usually a failed one) to be delivered again
+#[tauri::command]
+pub fn retry_outbox_item(state: State<'_, AppState>, id: i64) -> Result<OutboxItem, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    OutboxService::new(&conn).retry(id)
+}
+
+/// Gives up on an item without delivering it
+#[tauri::command]
+pub fn discard_outbox_item(state: State<'_, AppState>, id: i64) -> Result<OutboxItem, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    OutboxService::new(&conn).discard(id)
+}
@@ -0,0 +1,36 @@
+use crate::actions::{notify_timeboxes_changed, run_timebox_action, TimeboxAction};
+use crate::commands::{create_timebox, resolve_linear_issue};
+use crate::models::{CreateTimeboxRequest, Timebox};
+use crate::parser::parse_quick_capture as parse_line;
+use crate::state::AppState;
+use chrono::Local;
+use tauri::{AppHandle, Manager, State};
+
+/// Previews how a quick-capture line will be interpreted, without saving anything
+#[tauri::command]
+pub fn parse_quick_capture(input: String) -> Result<CreateTimeboxRequest, String> {
+    parse_line(&input, Local::now().naive_local())
+}
+
+/// Creates a timebox from a quick-capture line, optionally starting it right away.
+/// Linear identifiers are resolved to the issue's id and url when Linear is connected.
+#[tauri::command]
+pub async fn quick_capture_timebox(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: String,
+    start: bool,
+) -> Result<Timebox, String> {
+    let mut request = parse_line(&input, Local::now().naive_local())?;
+
+    resolve_linear_issue(&state, &mut request).await?;
+
+    let timebox = create_timebox(app.state(), request)?;
+
+    if start {
+        return run_timebox_action(&app, TimeboxAction::Start, timebox.id);
+    }
+
+    notify_timeboxes_changed(&app);
+    Ok(timebox)
+}
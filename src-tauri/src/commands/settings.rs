@@ -1,7 +1,9 @@
+use crate::http_api::start_http_api;
+use crate::shortcuts::register_shortcuts;
 use crate::state::AppState;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, State};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IdleSettings {
@@ -53,3 +55,201 @@ pub fn set_idle_settings(state: State<'_, AppState>, settings: IdleSettings) ->
 
     Ok(())
 }
+
+pub const DEFAULT_TOGGLE_PAUSE_SHORTCUT: &str = "CommandOrControl+Alt+Shift+Space";
+pub const DEFAULT_FINISH_CURRENT_SHORTCUT: &str = "CommandOrControl+Alt+Shift+F";
+pub const DEFAULT_QUICK_CREATE_SHORTCUT: &str = "CommandOrControl+Alt+Shift+N";
+
+/// Global keyboard shortcuts. `None` disables the shortcut.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutSettings {
+    pub toggle_pause: Option<String>,
+    pub finish_current: Option<String>,
+    pub quick_create: Option<String>,
+}
+
+fn get_shortcut_setting(conn: &rusqlite::Connection, key: &str, default: &str) -> Option<String> {
+    let value: String = conn
+        .query_row("SELECT value FROM settings WHERE key = ?1", params![key], |row| row.get(0))
+        .unwrap_or_else(|_| default.to_string());
+
+    // An empty value means the shortcut was disabled
+    if value.trim().is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Reads the shortcut settings from the database, falling back to the defaults
+pub fn load_shortcut_settings(conn: &rusqlite::Connection) -> ShortcutSettings {
+    ShortcutSettings {
+        toggle_pause: get_shortcut_setting(conn, "shortcut_toggle_pause", DEFAULT_TOGGLE_PAUSE_SHORTCUT),
+        finish_current: get_shortcut_setting(conn, "shortcut_finish_current", DEFAULT_FINISH_CURRENT_SHORTCUT),
+        quick_create: get_shortcut_setting(conn, "shortcut_quick_create", DEFAULT_QUICK_CREATE_SHORTCUT),
+    }
+}
+
+#[tauri::command]
+pub fn get_shortcut_settings(state: State<'_, AppState>) -> Result<ShortcutSettings, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(load_shortcut_settings(&conn))
+}
+
+/// Validates and registers the new shortcuts before saving them, so a conflicting
+/// shortcut is reported to the user instead of silently not working.
+#[tauri::command]
+pub fn set_shortcut_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    settings: ShortcutSettings,
+) -> Result<(), String> {
+    let previous = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        load_shortcut_settings(&conn)
+    };
+
+    if let Err(e) = register_shortcuts(&app, &settings) {
+        // Restore the shortcuts that were working before
+        let _ = register_shortcuts(&app, &previous);
+        return Err(e);
+    }
+
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+
+    for (key, value) in [
+        ("shortcut_toggle_pause", &settings.toggle_pause),
+        ("shortcut_finish_current", &settings.finish_current),
+        ("shortcut_quick_create", &settings.quick_create),
+    ] {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value, updated_at) VALUES (?1, ?2, datetime('now', 'localtime'))",
+            params![key, value.as_deref().map(str::trim).unwrap_or("")],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+pub const DEFAULT_HTTP_API_PORT: u16 = 7523;
+
+/// Local HTTP API for scripts and editor plugins. Only ever bound to 127.0.0.1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+}
+
+fn generate_http_api_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Reads the HTTP API settings, creating a token the first time
+pub fn load_http_api_settings(conn: &rusqlite::Connection) -> HttpApiSettings {
+    let get = |key: &str| -> Option<String> {
+        conn.query_row("SELECT value FROM settings WHERE key = ?1", params![key], |row| row.get(0))
+            .ok()
+    };
+
+    let token = match get("http_api_token").filter(|t| !t.is_empty()) {
+        Some(token) => token,
+        None => {
+            let token = generate_http_api_token();
+            let _ = save_setting(conn, "http_api_token", &token);
+            token
+        }
+    };
+
+    HttpApiSettings {
+        enabled: get("http_api_enabled").as_deref() == Some("true"),
+        port: get("http_api_port").and_then(|p| p.parse().ok()).unwrap_or(DEFAULT_HTTP_API_PORT),
+        token,
+    }
+}
+
+pub(crate) fn save_setting(conn: &rusqlite::Connection, key: &str, value: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value, updated_at) VALUES (?1, ?2, datetime('now', 'localtime'))",
+        params![key, value],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_http_api_settings(state: State<'_, AppState>) -> Result<HttpApiSettings, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(load_http_api_settings(&conn))
+}
+
+/// Restarts the server with the new settings, falling back to the previous ones when that
+/// fails so clients keep being served.
+///
+/// Stopping waits for the serving thread, and a request in flight may be waiting on the
+/// main thread to refresh the tray, so this runs on a blocking thread and never on the
+/// main one.
+async fn restart_http_api(app: &AppHandle, settings: &HttpApiSettings, previous: HttpApiSettings) -> Result<(), String> {
+    let app = app.clone();
+    let settings = settings.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        start_http_api(&app, &settings).inspect_err(|_| {
+            let _ = start_http_api(&app, &previous);
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Restarts the server with the new settings before saving them, so a port that is
+/// already taken is reported instead of silently not listening.
+#[tauri::command]
+pub async fn set_http_api_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+    port: u16,
+) -> Result<HttpApiSettings, String> {
+    let previous = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        load_http_api_settings(&conn)
+    };
+
+    let settings = HttpApiSettings {
+        enabled,
+        port,
+        token: previous.token.clone(),
+    };
+    restart_http_api(&app, &settings, previous).await?;
+
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    save_setting(&conn, "http_api_enabled", if enabled { "true" } else { "false" })?;
+    save_setting(&conn, "http_api_port", &port.to_string())?;
+
+    Ok(settings)
+}
+
+/// Replaces the bearer token, invalidating the old one immediately. Like the other
+/// settings, the token is only saved once the server runs with it.
+#[tauri::command]
+pub async fn regenerate_http_api_token(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<HttpApiSettings, String> {
+    let previous = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        load_http_api_settings(&conn)
+    };
+
+    let settings = HttpApiSettings {
+        token: generate_http_api_token(),
+        ..previous.clone()
+    };
+    restart_http_api(&app, &settings, previous).await?;
+
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    save_setting(&conn, "http_api_token", &settings.token)?;
+
+    Ok(settings)
+}
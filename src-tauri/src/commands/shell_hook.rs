@@ -0,0 +1,55 @@
+use crate::commands::save_setting;
+use crate::core::{shell_hooks_enabled, ShellHookService, SHELL_HOOKS_ENABLED_SETTING};
+use crate::models::{SaveShellHookRequest, ShellHook, ShellHookRun};
+use crate::state::AppState;
+use tauri::State;
+
+const DEFAULT_RUN_LIMIT: i64 = 50;
+
+#[tauri::command]
+pub fn get_shell_hooks_enabled(state: State<'_, AppState>) -> Result<bool, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(shell_hooks_enabled(&conn))
+}
+
+/// Hooks run arbitrary commands, so nothing runs until the user turns this on
+#[tauri::command]
+pub fn set_shell_hooks_enabled(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    save_setting(&conn, SHELL_HOOKS_ENABLED_SETTING, if enabled { "true" } else { "false" })
+}
+
+#[tauri::command]
+pub fn get_shell_hooks(state: State<'_, AppState>) -> Result<Vec<ShellHook>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    ShellHookService::new(&conn).list()
+}
+
+#[tauri::command]
+pub fn create_shell_hook(state: State<'_, AppState>, request: SaveShellHookRequest) -> Result<ShellHook, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    ShellHookService::new(&conn).create(&request)
+}
+
+#[tauri::command]
+pub fn update_shell_hook(
+    state: State<'_, AppState>,
+    id: i64,
+    request: SaveShellHookRequest,
+) -> Result<ShellHook, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    ShellHookService::new(&conn).update(id, &request)
+}
+
+#[tauri::command]
+pub fn delete_shell_hook(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    ShellHookService::new(&conn).delete(id)
+}
+
+/// Run log, newest first
+#[tauri::command]
+pub fn get_shell_hook_runs(state: State<'_, AppState>, limit: Option<i64>) -> Result<Vec<ShellHookRun>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    ShellHookService::new(&conn).runs(limit.unwrap_or(DEFAULT_RUN_LIMIT))
+}
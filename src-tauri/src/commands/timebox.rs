@@ -1,44 +1,15 @@
-use crate::commands::integration::{create_completed_todoist_task, get_todoist_api_token};
-use crate::models::{CreateTimeboxRequest, Session, Timebox, TimeboxChangeLog, TimeboxStatus, UpdateTimeboxRequest};
+use crate::core::{ReorderTimeboxRequest, TimeboxService, TimeboxWithSessions};
+use crate::models::{CreateTimeboxRequest, Timebox, TimeboxChangeLog, UpdateTimeboxRequest};
 use crate::state::AppState;
-use chrono::Local;
-use rusqlite::params;
 use tauri::State;
 
-const TIMEBOX_SELECT_COLUMNS: &str = "id, intention, notes, intended_duration, status, created_at, updated_at, started_at, completed_at, after_time_stopped_at, deleted_at, canceled_at, display_order, archived_at, finished_at, linear_project_id, linear_issue_id, linear_issue_identifier, linear_issue_url";
-
 #[tauri::command]
 pub fn create_timebox(
     state: State<'_, AppState>,
     request: CreateTimeboxRequest,
 ) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-
-    conn.execute(
-        "INSERT INTO timeboxes (intention, intended_duration, notes, linear_project_id, linear_issue_id, linear_issue_identifier, linear_issue_url) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-        params![
-            request.intention,
-            request.intended_duration,
-            request.notes,
-            request.linear_project_id,
-            request.linear_issue_id,
-            request.linear_issue_identifier,
-            request.linear_issue_url
-        ],
-    )
-    .map_err(|e| e.to_string())?;
-
-    let id = conn.last_insert_rowid();
-
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).create(&request)
 }
 
 #[tauri::command]
@@ -48,459 +19,68 @@ pub fn update_timebox(
     request: UpdateTimeboxRequest,
 ) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Get current timebox state for change log
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1 AND deleted_at IS NULL", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let current: Timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    // Determine new values (use request value if provided, otherwise keep current)
-    let new_intention = request.intention.clone().unwrap_or(current.intention.clone());
-    let new_notes = if request.notes.is_some() { request.notes.clone() } else { current.notes.clone() };
-    let new_duration = request.intended_duration.unwrap_or(current.intended_duration);
-
-    // Log changes if any field is being updated
-    let has_intention_change = new_intention != current.intention;
-    let has_notes_change = new_notes != current.notes;
-    let has_duration_change = new_duration != current.intended_duration;
-
-    if has_intention_change || has_notes_change || has_duration_change {
-        conn.execute(
-            "INSERT INTO timebox_change_log (timebox_id, previous_intention_title, updated_intention_title, previous_note_content, updated_note_content, previous_intended_duration, new_intended_duration, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-            params![
-                id,
-                if has_intention_change { Some(&current.intention) } else { None::<&String> },
-                if has_intention_change { Some(&new_intention) } else { None::<&String> },
-                if has_notes_change { current.notes.as_ref() } else { None::<&String> },
-                if has_notes_change { new_notes.as_ref() } else { None::<&String> },
-                if has_duration_change { Some(current.intended_duration) } else { None::<i64> },
-                if has_duration_change { Some(new_duration) } else { None::<i64> },
-                now
-            ],
-        )
-        .map_err(|e| e.to_string())?;
-    }
-
-    conn.execute(
-        "UPDATE timeboxes SET intention = ?1, notes = ?2, intended_duration = ?3, updated_at = ?4 WHERE id = ?5",
-        params![new_intention, new_notes, new_duration, now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Return the updated timebox
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).update(id, &request)
 }
 
 #[tauri::command]
 pub fn start_timebox(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Check if this is the first start (started_at is null)
-    let started_at: Option<String> = conn
-        .query_row(
-            "SELECT started_at FROM timeboxes WHERE id = ?1 AND deleted_at IS NULL",
-            params![id],
-            |row| row.get(0),
-        )
-        .map_err(|e| e.to_string())?;
-
-    // Update timebox - set started_at only if first time, always set status to in_progress
-    // Also clear completed_at so a stopped timebox can be restarted and appear in active list
-    if started_at.is_none() {
-        conn.execute(
-            "UPDATE timeboxes SET started_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
-            params![now, TimeboxStatus::InProgress.as_str(), id],
-        )
-        .map_err(|e| e.to_string())?;
-    } else {
-        conn.execute(
-            "UPDATE timeboxes SET status = ?1, completed_at = NULL, updated_at = ?2 WHERE id = ?3",
-            params![TimeboxStatus::InProgress.as_str(), now, id],
-        )
-        .map_err(|e| e.to_string())?;
-    }
-
-    // Create a new session
-    conn.execute(
-        "INSERT INTO sessions (timebox_id, started_at) VALUES (?1, ?2)",
-        params![id, now],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Return the updated timebox
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).start(id)
 }
 
 #[tauri::command]
 pub fn stop_timebox(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Close any open sessions for this timebox
-    conn.execute(
-        "UPDATE sessions SET stopped_at = ?1 WHERE timebox_id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Update timebox - set completed_at and status to stopped (user manually stopped)
-    conn.execute(
-        "UPDATE timeboxes SET completed_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
-        params![now, TimeboxStatus::Stopped.as_str(), id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Return the updated timebox
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).stop(id)
 }
 
 /// Auto-stop a timebox due to system idle. Same as stop_timebox but also sets auto_stopped_at.
 #[tauri::command]
 pub fn auto_stop_timebox(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Close any open sessions for this timebox with auto_stopped_at marker
-    conn.execute(
-        "UPDATE sessions SET stopped_at = ?1, auto_stopped_at = ?1 WHERE timebox_id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Update timebox - set completed_at and status to stopped (auto-stopped due to idle)
-    conn.execute(
-        "UPDATE timeboxes SET completed_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
-        params![now, TimeboxStatus::Stopped.as_str(), id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Return the updated timebox
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).auto_stop(id)
 }
 
 #[tauri::command]
 pub fn finish_timebox(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Close any open sessions for this timebox
-    conn.execute(
-        "UPDATE sessions SET stopped_at = ?1 WHERE timebox_id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Update timebox - set finished_at and status to completed (user explicitly finished)
-    conn.execute(
-        "UPDATE timeboxes SET finished_at = ?1, completed_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
-        params![now, TimeboxStatus::Completed.as_str(), id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Return the updated timebox
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    // If Todoist integration exists, create a completed task
-    if let Some(api_token) = get_todoist_api_token(&conn) {
-        let description = timebox.notes.as_deref();
-        // Silently log any errors but don't fail the timebox completion
-        if let Err(e) = create_completed_todoist_task(&api_token, &timebox.intention, description) {
-            eprintln!("Failed to create Todoist task: {}", e);
-        }
-    }
-
-    Ok(timebox)
+    TimeboxService::new(&conn).finish(id)
 }
 
 #[tauri::command]
 pub fn stop_timebox_after_time(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Close any open sessions for this timebox
-    conn.execute(
-        "UPDATE sessions SET stopped_at = ?1 WHERE timebox_id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Update timebox - set after_time_stopped_at (timer expired naturally) and status to completed
-    conn.execute(
-        "UPDATE timeboxes SET after_time_stopped_at = ?1, completed_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
-        params![now, TimeboxStatus::Completed.as_str(), id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Return the updated timebox
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).stop_after_time(id)
 }
 
 #[tauri::command]
 pub fn cancel_timebox(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Close any open sessions for this timebox with cancelled_at
-    conn.execute(
-        "UPDATE sessions SET cancelled_at = ?1 WHERE timebox_id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Update timebox - set canceled_at and status to cancelled
-    conn.execute(
-        "UPDATE timeboxes SET canceled_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
-        params![now, TimeboxStatus::Cancelled.as_str(), id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Return the updated timebox
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).cancel(id)
 }
 
 #[tauri::command]
 pub fn pause_timebox(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Close any open sessions for this timebox
-    conn.execute(
-        "UPDATE sessions SET stopped_at = ?1 WHERE timebox_id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Update timebox - set status to paused
-    conn.execute(
-        "UPDATE timeboxes SET status = ?1, updated_at = ?2 WHERE id = ?3",
-        params![TimeboxStatus::Paused.as_str(), now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Return the updated timebox
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).pause(id)
 }
 
 #[tauri::command]
 pub fn delete_timebox(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Soft delete - set deleted_at
-    conn.execute(
-        "UPDATE timeboxes SET deleted_at = ?1, updated_at = ?1 WHERE id = ?2",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    // Return the updated timebox
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
-}
-
-#[derive(serde::Serialize)]
-pub struct TimeboxWithSessions {
-    #[serde(flatten)]
-    pub timebox: Timebox,
-    pub sessions: Vec<Session>,
-    pub actual_duration: f64,
+    TimeboxService::new(&conn).delete(id)
 }
 
 #[tauri::command]
 pub fn get_today_timeboxes(state: State<'_, AppState>) -> Result<Vec<TimeboxWithSessions>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-
-    let mut timebox_stmt = conn
-        .prepare(&format!(
-            "SELECT {}
-             FROM timeboxes
-             WHERE created_at >= datetime('now', 'localtime', '-24 hours')
-               AND deleted_at IS NULL
-               AND archived_at IS NULL
-             ORDER BY COALESCE(display_order, 999999), created_at DESC",
-            TIMEBOX_SELECT_COLUMNS
-        ))
-        .map_err(|e| e.to_string())?;
-
-    let timeboxes: Vec<Timebox> = timebox_stmt
-        .query_map([], Timebox::from_row)
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    let mut result = Vec::new();
-
-    for timebox in timeboxes {
-        let mut session_stmt = conn
-            .prepare(
-                "SELECT id, timebox_id, started_at, stopped_at, cancelled_at, auto_stopped_at
-                 FROM sessions
-                 WHERE timebox_id = ?1
-                 ORDER BY started_at DESC",
-            )
-            .map_err(|e| e.to_string())?;
-
-        let sessions: Vec<Session> = session_stmt
-            .query_map(params![timebox.id], Session::from_row)
-            .map_err(|e| e.to_string())?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        // Calculate actual duration in seconds (using stopped_at or current time for active sessions)
-        let actual_duration: f64 = conn
-            .query_row(
-                "SELECT COALESCE(SUM((julianday(COALESCE(stopped_at, datetime('now', 'localtime'))) - julianday(started_at)) * 86400), 0)
-                 FROM sessions WHERE timebox_id = ?1 AND cancelled_at IS NULL",
-                params![timebox.id],
-                |row| row.get(0),
-            )
-            .map_err(|e| e.to_string())?;
-
-        result.push(TimeboxWithSessions {
-            timebox,
-            sessions,
-            actual_duration,
-        });
-    }
-
-    Ok(result)
+    TimeboxService::new(&conn).today()
 }
 
 #[tauri::command]
 pub fn get_active_timeboxes(state: State<'_, AppState>) -> Result<Vec<TimeboxWithSessions>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-
-    // Active = started but not completed, not stopped after time, not canceled, not deleted
-    let mut timebox_stmt = conn
-        .prepare(&format!(
-            "SELECT {}
-             FROM timeboxes
-             WHERE started_at IS NOT NULL
-               AND completed_at IS NULL
-               AND after_time_stopped_at IS NULL
-               AND canceled_at IS NULL
-               AND deleted_at IS NULL
-             ORDER BY created_at DESC",
-            TIMEBOX_SELECT_COLUMNS
-        ))
-        .map_err(|e| e.to_string())?;
-
-    let timeboxes: Vec<Timebox> = timebox_stmt
-        .query_map([], Timebox::from_row)
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    let mut result = Vec::new();
-
-    for timebox in timeboxes {
-        let mut session_stmt = conn
-            .prepare(
-                "SELECT id, timebox_id, started_at, stopped_at, cancelled_at, auto_stopped_at
-                 FROM sessions
-                 WHERE timebox_id = ?1
-                 ORDER BY started_at DESC",
-            )
-            .map_err(|e| e.to_string())?;
-
-        let sessions: Vec<Session> = session_stmt
-            .query_map(params![timebox.id], Session::from_row)
-            .map_err(|e| e.to_string())?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        let actual_duration: f64 = conn
-            .query_row(
-                "SELECT COALESCE(SUM((julianday(COALESCE(stopped_at, datetime('now', 'localtime'))) - julianday(started_at)) * 86400), 0)
-                 FROM sessions WHERE timebox_id = ?1 AND cancelled_at IS NULL",
-                params![timebox.id],
-                |row| row.get(0),
-            )
-            .map_err(|e| e.to_string())?;
-
-        result.push(TimeboxWithSessions {
-            timebox,
-            sessions,
-            actual_duration,
-        });
-    }
-
-    Ok(result)
+    TimeboxService::new(&conn).active()
 }
 
 #[tauri::command]
@@ -509,29 +89,7 @@ pub fn get_timebox_change_log(
     timebox_id: i64,
 ) -> Result<Vec<TimeboxChangeLog>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, timebox_id, previous_intention_title, updated_intention_title, previous_note_content, updated_note_content, previous_intended_duration, new_intended_duration, updated_at
-             FROM timebox_change_log
-             WHERE timebox_id = ?1
-             ORDER BY updated_at DESC",
-        )
-        .map_err(|e| e.to_string())?;
-
-    let logs: Vec<TimeboxChangeLog> = stmt
-        .query_map(params![timebox_id], TimeboxChangeLog::from_row)
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    Ok(logs)
-}
-
-#[derive(Debug, serde::Deserialize)]
-pub struct ReorderTimeboxRequest {
-    pub id: i64,
-    pub display_order: i64,
+    TimeboxService::new(&conn).change_log(timebox_id)
 }
 
 #[tauri::command]
@@ -540,120 +98,25 @@ pub fn reorder_timeboxes(
     orders: Vec<ReorderTimeboxRequest>,
 ) -> Result<(), String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    for order in orders {
-        conn.execute(
-            "UPDATE timeboxes SET display_order = ?1, updated_at = ?2 WHERE id = ?3",
-            params![order.display_order, now, order.id],
-        )
-        .map_err(|e| e.to_string())?;
-    }
-
-    Ok(())
+    TimeboxService::new(&conn).reorder(&orders)
 }
 
 #[tauri::command]
 pub fn archive_timebox(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    conn.execute(
-        "UPDATE timeboxes SET archived_at = ?1, updated_at = ?1 WHERE id = ?2",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).archive(id)
 }
 
 #[tauri::command]
 pub fn unarchive_timebox(state: State<'_, AppState>, id: i64) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    conn.execute(
-        "UPDATE timeboxes SET archived_at = NULL, updated_at = ?1 WHERE id = ?2",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).unarchive(id)
 }
 
 #[tauri::command]
 pub fn get_archived_timeboxes(state: State<'_, AppState>) -> Result<Vec<TimeboxWithSessions>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-
-    let mut timebox_stmt = conn
-        .prepare(&format!(
-            "SELECT {}
-             FROM timeboxes
-             WHERE created_at >= datetime('now', 'localtime', '-24 hours')
-               AND deleted_at IS NULL
-               AND archived_at IS NOT NULL
-             ORDER BY archived_at DESC",
-            TIMEBOX_SELECT_COLUMNS
-        ))
-        .map_err(|e| e.to_string())?;
-
-    let timeboxes: Vec<Timebox> = timebox_stmt
-        .query_map([], Timebox::from_row)
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    let mut result = Vec::new();
-
-    for timebox in timeboxes {
-        let mut session_stmt = conn
-            .prepare(
-                "SELECT id, timebox_id, started_at, stopped_at, cancelled_at, auto_stopped_at
-                 FROM sessions
-                 WHERE timebox_id = ?1
-                 ORDER BY started_at DESC",
-            )
-            .map_err(|e| e.to_string())?;
-
-        let sessions: Vec<Session> = session_stmt
-            .query_map(params![timebox.id], Session::from_row)
-            .map_err(|e| e.to_string())?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        let actual_duration: f64 = conn
-            .query_row(
-                "SELECT COALESCE(SUM((julianday(COALESCE(stopped_at, datetime('now', 'localtime'))) - julianday(started_at)) * 86400), 0)
-                 FROM sessions WHERE timebox_id = ?1 AND cancelled_at IS NULL",
-                params![timebox.id],
-                |row| row.get(0),
-            )
-            .map_err(|e| e.to_string())?;
-
-        result.push(TimeboxWithSessions {
-            timebox,
-            sessions,
-            actual_duration,
-        });
-    }
-
-    Ok(result)
+    TimeboxService::new(&conn).archived()
 }
 
 // Command: Set Linear issue on a timebox
@@ -662,26 +125,11 @@ pub fn set_timebox_linear_issue(
     state: State<'_, AppState>,
     timebox_id: i64,
     linear_issue_id: String,
+    linear_issue_identifier: String,
     linear_issue_url: String,
 ) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    conn.execute(
-        "UPDATE timeboxes SET linear_issue_id = ?1, linear_issue_url = ?2, updated_at = ?3 WHERE id = ?4",
-        params![linear_issue_id, linear_issue_url, now, timebox_id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![timebox_id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).set_linear_issue(timebox_id, &linear_issue_id, &linear_issue_identifier, &linear_issue_url)
 }
 
 // Command: Set Linear project on a timebox
@@ -692,21 +140,5 @@ pub fn set_timebox_linear_project(
     linear_project_id: Option<i64>,
 ) -> Result<Timebox, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    conn.execute(
-        "UPDATE timeboxes SET linear_project_id = ?1, updated_at = ?2 WHERE id = ?3",
-        params![linear_project_id, now, timebox_id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    let mut stmt = conn
-        .prepare(&format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS))
-        .map_err(|e| e.to_string())?;
-
-    let timebox = stmt
-        .query_row(params![timebox_id], Timebox::from_row)
-        .map_err(|e| e.to_string())?;
-
-    Ok(timebox)
+    TimeboxService::new(&conn).set_linear_project(timebox_id, linear_project_id)
 }
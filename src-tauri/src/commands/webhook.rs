@@ -0,0 +1,53 @@
+use crate::core::{WebhookConfig, WebhookService};
+use crate::models::WebhookDelivery;
+use crate::state::AppState;
+use crate::webhooks::ping_webhook;
+use serde::Serialize;
+use serde_json::json;
+use tauri::State;
+
+const DEFAULT_DELIVERY_LIMIT: i64 = 50;
+
+#[derive(Debug, Serialize)]
+pub struct WebhookTestResult {
+    pub success: bool,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Delivery log, newest first, for one webhook or all of them
+#[tauri::command]
+pub fn get_webhook_deliveries(
+    state: State<'_, AppState>,
+    integration_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<WebhookDelivery>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    WebhookService::new(&conn).deliveries(integration_id, limit.unwrap_or(DEFAULT_DELIVERY_LIMIT))
+}
+
+/// Queues a delivery (usually a failed one) to be sent again
+#[tauri::command]
+pub fn retry_webhook_delivery(state: State<'_, AppState>, id: i64) -> Result<WebhookDelivery, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    WebhookService::new(&conn).retry(id)
+}
+
+/// Sends a `ping` event to check a URL and secret before saving the webhook
+#[tauri::command]
+pub async fn test_webhook(url: String, secret: Option<String>) -> Result<WebhookTestResult, String> {
+    let config = WebhookConfig::from_value(&json!({ "url": url, "secret": secret, "events": ["created"] }))?;
+
+    match ping_webhook(&config).await {
+        Ok(status) => Ok(WebhookTestResult {
+            success: true,
+            response_status: Some(status),
+            error: None,
+        }),
+        Err((status, error)) => Ok(WebhookTestResult {
+            success: false,
+            response_status: status,
+            error: Some(error),
+        }),
+    }
+}
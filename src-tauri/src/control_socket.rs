@@ -0,0 +1,232 @@
+//! Unix socket in the app data dir for shell scripts and status bars.
+//!
+//! Speaks line-delimited JSON. Each request line gets exactly one response line:
+//!
+//! ```text
+//! {"cmd":"state"}                 -> {"ok":true,"state":{...}}   (state is null when idle)
+//! {"cmd":"start","id":12}         -> {"ok":true,"timebox":{...}}
+//! {"cmd":"start"}                 -> start defaults to the paused timebox, then the last stopped one
+//! {"cmd":"pause"}                 -> pause/stop/finish default to the current timebox
+//! {"cmd":"subscribe"}             -> {"ok":true}, then event lines:
+//!                                    {"event":"state_changed","state":{...}}
+//!                                    {"event":"tick","state":{...}}   (every second while running)
+//! ```
+
+use crate::actions::{current_timebox, resumable_timebox, run_timebox_action, TimeboxAction};
+use crate::core::TimeboxWithSessions;
+use crate::models::TimeboxStatus;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs::{self, DirBuilder};
+use std::io::{BufRead, BufReader, Write};
+use std::net::Shutdown;
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const SOCKET_DIR_NAME: &str = "control";
+const SOCKET_FILE_NAME: &str = "timeboxd.sock";
+// A subscriber that stops reading is dropped rather than stalling every other one
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+type Subscribers = Arc<Mutex<Vec<Arc<Mutex<UnixStream>>>>>;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum SocketRequest {
+    State,
+    Subscribe,
+    Start { id: Option<i64> },
+    Pause { id: Option<i64> },
+    Stop { id: Option<i64> },
+    Finish { id: Option<i64> },
+}
+
+/// What status bars need to render the timer
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct TimerState {
+    timebox_id: i64,
+    intention: String,
+    status: TimeboxStatus,
+    intended_seconds: i64,
+    elapsed_seconds: i64,
+    remaining_seconds: i64,
+}
+
+impl TimerState {
+    fn from_timebox(t: &TimeboxWithSessions) -> Self {
+        TimerState {
+            timebox_id: t.timebox.id,
+            intention: t.timebox.intention.clone(),
+            status: t.timebox.status.clone(),
+            intended_seconds: t.timebox.intended_duration * 60,
+            elapsed_seconds: t.actual_duration.floor() as i64,
+            remaining_seconds: t.remaining_seconds(),
+        }
+    }
+}
+
+/// The socket lives in a directory only the current user can enter, so nobody else can
+/// connect to it, not even in the moment between creating it and changing its mode.
+pub fn socket_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?.join(SOCKET_DIR_NAME);
+    DirBuilder::new().recursive(true).mode(0o700).create(&dir).map_err(|e| e.to_string())?;
+    // The mode only applies to a newly created directory
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o700)).map_err(|e| e.to_string())?;
+    Ok(dir.join(SOCKET_FILE_NAME))
+}
+
+/// Listens on the control socket and pushes timer events to subscribers.
+pub fn start_control_socket(app: &AppHandle) -> Result<PathBuf, String> {
+    let path = socket_path(app)?;
+
+    // A socket left behind by a previous run would make bind fail
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+
+    let listener = UnixListener::bind(&path).map_err(|e| e.to_string())?;
+
+    let subscribers: Subscribers = Arc::default();
+
+    let accept_app = app.clone();
+    let accept_subscribers = subscribers.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let app = accept_app.clone();
+            let subscribers = accept_subscribers.clone();
+            thread::spawn(move || handle_connection(&app, stream, &subscribers));
+        }
+    });
+
+    let tick_app = app.clone();
+    thread::spawn(move || broadcast_loop(&tick_app, &subscribers));
+
+    Ok(path)
+}
+
+fn handle_connection(app: &AppHandle, stream: UnixStream, subscribers: &Subscribers) {
+    let Ok(reader) = stream.try_clone() else {
+        return;
+    };
+    if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_err() {
+        return;
+    }
+    let writer = Arc::new(Mutex::new(stream));
+
+    for line in BufReader::new(reader).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<SocketRequest>(&line) {
+            Ok(SocketRequest::Subscribe) => {
+                if let Ok(mut subscribers) = subscribers.lock() {
+                    subscribers.push(writer.clone());
+                }
+                json!({ "ok": true })
+            }
+            Ok(request) => handle_request(app, request),
+            Err(e) => json!({ "ok": false, "error": format!("Invalid request: {}", e) }),
+        };
+
+        if !write_line(&writer, &response) {
+            break;
+        }
+    }
+}
+
+fn handle_request(app: &AppHandle, request: SocketRequest) -> Value {
+    let (action, id) = match request {
+        SocketRequest::State => return json!({ "ok": true, "state": current_state(app) }),
+        SocketRequest::Subscribe => return json!({ "ok": true }),
+        SocketRequest::Start { id } => (TimeboxAction::Start, id),
+        SocketRequest::Pause { id } => (TimeboxAction::Pause, id),
+        SocketRequest::Stop { id } => (TimeboxAction::Stop, id),
+        SocketRequest::Finish { id } => (TimeboxAction::Finish, id),
+    };
+
+    let id = match (action, id) {
+        (_, Some(id)) => id,
+        // Starting the current timebox would re-start a running one
+        (TimeboxAction::Start, None) => match resumable_timebox(app) {
+            Some(t) => t.timebox.id,
+            None => return json!({ "ok": false, "error": "No paused or stopped timebox to resume, pass an id" }),
+        },
+        (_, None) => match current_timebox(app) {
+            Some(t) => t.timebox.id,
+            None => return json!({ "ok": false, "error": "No timebox running, pass an id" }),
+        },
+    };
+
+    match run_timebox_action(app, action, id) {
+        Ok(timebox) => json!({ "ok": true, "timebox": timebox }),
+        Err(e) => json!({ "ok": false, "error": e }),
+    }
+}
+
+fn current_state(app: &AppHandle) -> Option<TimerState> {
+    current_timebox(app).as_ref().map(TimerState::from_timebox)
+}
+
+/// Sends a tick every second while a timer runs, and a state_changed event whenever
+/// the current timebox or its status changes (from the UI, tray, or anywhere else).
+fn broadcast_loop(app: &AppHandle, subscribers: &Subscribers) {
+    let mut last_key: Option<(i64, TimeboxStatus)> = None;
+
+    loop {
+        thread::sleep(Duration::from_secs(1));
+
+        let has_subscribers = subscribers.lock().map(|s| !s.is_empty()).unwrap_or(false);
+        if !has_subscribers {
+            last_key = None;
+            continue;
+        }
+
+        let state = current_state(app);
+        let key = state.as_ref().map(|s| (s.timebox_id, s.status.clone()));
+
+        if key != last_key {
+            broadcast(subscribers, &json!({ "event": "state_changed", "state": state }));
+            last_key = key;
+        } else if state.as_ref().is_some_and(|s| s.status == TimeboxStatus::InProgress) {
+            broadcast(subscribers, &json!({ "event": "tick", "state": state }));
+        }
+    }
+}
+
+fn broadcast(subscribers: &Subscribers, event: &Value) {
+    // Write without holding the list, so a slow subscriber doesn't block new ones
+    let Ok(targets) = subscribers.lock().map(|s| s.clone()) else {
+        return;
+    };
+    let failed: Vec<_> = targets.into_iter().filter(|writer| !write_line(writer, event)).collect();
+    if failed.is_empty() {
+        return;
+    }
+
+    // Drop subscribers that have disconnected or timed out. A timed out write may have
+    // left half a line behind, so the connection is closed too.
+    for writer in &failed {
+        if let Ok(stream) = writer.lock() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+    if let Ok(mut subscribers) = subscribers.lock() {
+        subscribers.retain(|writer| !failed.iter().any(|f| Arc::ptr_eq(f, writer)));
+    }
+}
+
+fn write_line(writer: &Mutex<UnixStream>, value: &Value) -> bool {
+    let Ok(mut stream) = writer.lock() else {
+        return false;
+    };
+    writeln!(stream, "{}", value).and_then(|_| stream.flush()).is_ok()
+}
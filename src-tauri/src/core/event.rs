@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// Timebox lifecycle events that webhooks and shell hooks can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeboxEvent {
+    Created,
+    Started,
+    Paused,
+    Stopped,
+    Finished,
+    Overtime,
+    AutoStopped,
+}
+
+impl TimeboxEvent {
+    pub const ALL: [TimeboxEvent; 7] = [
+        TimeboxEvent::Created,
+        TimeboxEvent::Started,
+        TimeboxEvent::Paused,
+        TimeboxEvent::Stopped,
+        TimeboxEvent::Finished,
+        TimeboxEvent::Overtime,
+        TimeboxEvent::AutoStopped,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeboxEvent::Created => "created",
+            TimeboxEvent::Started => "started",
+            TimeboxEvent::Paused => "paused",
+            TimeboxEvent::Stopped => "stopped",
+            TimeboxEvent::Finished => "finished",
+            TimeboxEvent::Overtime => "overtime",
+            TimeboxEvent::AutoStopped => "auto_stopped",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        TimeboxEvent::ALL.into_iter().find(|e| e.as_str() == s)
+    }
+}
@@ -0,0 +1,24 @@
+pub mod event;
+pub mod outbox;
+pub mod session;
+pub mod shell_hook;
+pub mod timebox;
+pub mod webhook;
+
+#[cfg(test)]
+mod outbox_tests;
+#[cfg(test)]
+mod session_tests;
+#[cfg(test)]
+mod shell_hook_tests;
+#[cfg(test)]
+mod timebox_tests;
+#[cfg(test)]
+mod webhook_tests;
+
+pub use event::*;
+pub use outbox::*;
+pub use session::*;
+pub use shell_hook::*;
+pub use timebox::*;
+pub use webhook::*;
@@ -0,0 +1,266 @@
+use super::timebox::now;
+use super::webhook::webhook_backoff_seconds;
+use crate::integrations::http::{ApiError, ApiErrorKind};
+use crate::models::{OutboxItem, OutboxStatus};
+use chrono::{Duration, Local};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+/// Items are given up on after this many attempts, a few hours with the webhook backoff
+pub const OUTBOX_MAX_ATTEMPTS: i64 = 10;
+
+const OUTBOX_SELECT_COLUMNS: &str = "id, integration_id, timebox_id, action, payload, status, attempts, last_error, next_attempt_at, delivered_at, created_at, updated_at, dedupe_key";
+
+// Longest error kept on an item
+const MAX_ERROR_LENGTH: usize = 500;
+
+/// A side effect an integration asked for. Stored as JSON in the outbox, so variants
+/// must stay readable by later versions.
+///
+/// Actions with a `dedupe_key` are queued once per key, however often they're asked for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutboxAction {
+    /// `request_id` is sent as Todoist's X-Request-Id so a retry can't create the task twice
+    TodoistCreateCompletedTask {
+        content: String,
+        description: Option<String>,
+        request_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dedupe_key: Option<String>,
+    },
+    /// `comment_id` is sent as the new comment's id so a retry can't post it twice
+    LinearPostComment {
+        issue_id: String,
+        body: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dedupe_key: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        comment_id: Option<String>,
+    },
+    LinearUpdateIssueState {
+        issue_id: String,
+        state_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dedupe_key: Option<String>,
+    },
+}
+
+impl OutboxAction {
+    pub fn todoist_completed_task(content: &str, description: Option<&str>, dedupe_key: Option<String>) -> Self {
+        OutboxAction::TodoistCreateCompletedTask {
+            content: content.to_string(),
+            description: description.map(str::to_string),
+            request_id: uuid::Uuid::new_v4().to_string(),
+            dedupe_key,
+        }
+    }
+
+    pub fn linear_comment(issue_id: &str, body: &str, dedupe_key: Option<String>) -> Self {
+        OutboxAction::LinearPostComment {
+            issue_id: issue_id.to_string(),
+            body: body.to_string(),
+            dedupe_key,
+            comment_id: Some(uuid::Uuid::new_v4().to_string()),
+        }
+    }
+
+    /// Value of the `action` column, for filtering without parsing payloads
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutboxAction::TodoistCreateCompletedTask { .. } => "todoist_create_completed_task",
+            OutboxAction::LinearPostComment { .. } => "linear_post_comment",
+            OutboxAction::LinearUpdateIssueState { .. } => "linear_update_issue_state",
+        }
+    }
+
+    /// Actions with the same key are only queued once per integration
+    pub fn dedupe_key(&self) -> Option<&str> {
+        match self {
+            OutboxAction::TodoistCreateCompletedTask { dedupe_key, .. }
+            | OutboxAction::LinearPostComment { dedupe_key, .. }
+            | OutboxAction::LinearUpdateIssueState { dedupe_key, .. } => dedupe_key.as_deref(),
+        }
+    }
+
+    pub fn from_item(item: &OutboxItem) -> Result<Self, String> {
+        serde_json::from_str(&item.payload).map_err(|e| format!("Invalid outbox payload: {}", e))
+    }
+}
+
+pub struct OutboxService<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> OutboxService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        OutboxService { conn }
+    }
+
+    /// Queues an action. When one with the same dedupe key was queued before, that item
+    /// is returned instead and nothing new is queued.
+    pub fn enqueue(&self, integration_id: i64, timebox_id: Option<i64>, action: &OutboxAction) -> Result<OutboxItem, String> {
+        let payload = serde_json::to_string(action).map_err(|e| e.to_string())?;
+        let dedupe_key = action.dedupe_key();
+        let now = now();
+        let inserted = self
+            .conn
+            .execute(
+                "INSERT INTO integration_outbox (integration_id, timebox_id, action, payload, status, next_attempt_at, created_at, updated_at, dedupe_key) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6, ?6, ?7) ON CONFLICT(integration_id, dedupe_key) DO NOTHING",
+                params![integration_id, timebox_id, action.name(), payload, OutboxStatus::Pending.as_str(), now, dedupe_key],
+            )
+            .map_err(|e| e.to_string())?;
+
+        if inserted == 0 {
+            return self
+                .conn
+                .query_row(
+                    &format!(
+                        "SELECT {} FROM integration_outbox WHERE integration_id = ?1 AND dedupe_key = ?2",
+                        OUTBOX_SELECT_COLUMNS
+                    ),
+                    params![integration_id, dedupe_key],
+                    OutboxItem::from_row,
+                )
+                .map_err(|e| e.to_string());
+        }
+
+        self.get(self.conn.last_insert_rowid())
+    }
+
+    pub fn get(&self, id: i64) -> Result<OutboxItem, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM integration_outbox WHERE id = ?1", OUTBOX_SELECT_COLUMNS),
+                params![id],
+                OutboxItem::from_row,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    /// Pending items whose next attempt is due, oldest first
+    pub fn due(&self, limit: i64) -> Result<Vec<OutboxItem>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM integration_outbox WHERE status = ?1 AND next_attempt_at <= ?2 ORDER BY next_attempt_at, id LIMIT ?3",
+                OUTBOX_SELECT_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let items = stmt
+            .query_map(params![OutboxStatus::Pending.as_str(), now(), limit], OutboxItem::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(items)
+    }
+
+    /// Most recent items first, optionally only those with a status
+    pub fn items(&self, status: Option<OutboxStatus>, limit: i64) -> Result<Vec<OutboxItem>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM integration_outbox WHERE ?1 IS NULL OR status = ?1 ORDER BY created_at DESC, id DESC LIMIT ?2",
+                OUTBOX_SELECT_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let items = stmt
+            .query_map(params![status.map(|s| s.as_str()), limit], OutboxItem::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(items)
+    }
+
+    /// Like `record_failure`, this leaves items that were discarded while being delivered
+    /// alone, as only pending items are updated.
+    pub fn record_success(&self, id: i64) -> Result<OutboxItem, String> {
+        let now = now();
+        self.conn
+            .execute(
+                "UPDATE integration_outbox SET status = ?1, attempts = attempts + 1, last_error = NULL, next_attempt_at = NULL, delivered_at = ?2, updated_at = ?2 WHERE id = ?3 AND status = ?4",
+                params![OutboxStatus::Delivered.as_str(), now, id, OutboxStatus::Pending.as_str()],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    /// Schedules the next attempt with backoff. Items fail for good once attempts run out,
+    /// or straight away when retrying can't help (bad credentials, a rejected request).
+    pub fn record_failure(&self, id: i64, error: &ApiError) -> Result<OutboxItem, String> {
+        let item = self.get(id)?;
+        if item.status != OutboxStatus::Pending {
+            return Ok(item);
+        }
+        let attempts = item.attempts + 1;
+        let now = Local::now().naive_local();
+        let message: String = error.message.chars().take(MAX_ERROR_LENGTH).collect();
+
+        let permanent = matches!(error.kind, ApiErrorKind::Auth | ApiErrorKind::Rejected);
+        let (status, next_attempt_at) = if permanent || attempts >= OUTBOX_MAX_ATTEMPTS {
+            (OutboxStatus::Failed, None)
+        } else {
+            // Wait at least as long as a rate limit asks
+            let backoff = webhook_backoff_seconds(attempts).max(error.retry_after_secs.unwrap_or(0) as i64);
+            let next = now + Duration::seconds(backoff);
+            (OutboxStatus::Pending, Some(next.format("%Y-%m-%d %H:%M:%S").to_string()))
+        };
+
+        self.conn
+            .execute(
+                "UPDATE integration_outbox SET status = ?1, attempts = ?2, last_error = ?3, next_attempt_at = ?4, updated_at = ?5 WHERE id = ?6 AND status = ?7",
+                params![
+                    status.as_str(),
+                    attempts,
+                    message,
+                    next_attempt_at,
+                    now.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    id,
+                    OutboxStatus::Pending.as_str()
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    /// Delivers an item again on the next worker run, with a fresh set of attempts
+    pub fn retry(&self, id: i64) -> Result<OutboxItem, String> {
+        let item = self.get(id)?;
+        if item.status == OutboxStatus::Delivered {
+            return Err("Item was already delivered".to_string());
+        }
+
+        let now = now();
+        self.conn
+            .execute(
+                "UPDATE integration_outbox SET status = ?1, attempts = 0, next_attempt_at = ?2, updated_at = ?2 WHERE id = ?3",
+                params![OutboxStatus::Pending.as_str(), now, id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    /// Gives up on an item; it stays in the log but is never sent
+    pub fn discard(&self, id: i64) -> Result<OutboxItem, String> {
+        let item = self.get(id)?;
+        if item.status == OutboxStatus::Delivered {
+            return Err("Item was already delivered".to_string());
+        }
+
+        self.conn
+            .execute(
+                "UPDATE integration_outbox SET status = ?1, next_attempt_at = NULL, updated_at = ?2 WHERE id = ?3",
+                params![OutboxStatus::Discarded.as_str(), now(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+}
@@ -0,0 +1,173 @@
+use super::{OutboxAction, OutboxService, TimeboxService, OUTBOX_MAX_ATTEMPTS};
+use crate::integrations::http::{ApiError, ApiErrorKind};
+use crate::models::{CreateTimeboxRequest, OutboxStatus};
+use crate::test_support::{add_integration, test_db, timebox_request};
+use rusqlite::Connection;
+use serde_json::json;
+
+fn add_todoist(conn: &Connection) -> i64 {
+    add_integration(conn, "todoist", json!({ "api_token": "token" }))
+}
+
+fn comment() -> OutboxAction {
+    OutboxAction::LinearPostComment {
+        issue_id: "issue-1".to_string(),
+        body: "Spent 25 minutes".to_string(),
+        dedupe_key: None,
+        comment_id: None,
+    }
+}
+
+#[test]
+fn test_enqueue_stores_action_as_json() {
+    let conn = test_db();
+    let integration = add_todoist(&conn);
+    let outbox = OutboxService::new(&conn);
+
+    let action = OutboxAction::todoist_completed_task("Write RFC", None, None);
+    let item = outbox.enqueue(integration, None, &action).unwrap();
+
+    assert_eq!(item.status, OutboxStatus::Pending);
+    assert_eq!(item.action, "todoist_create_completed_task");
+    assert_eq!(item.attempts, 0);
+    assert_eq!(OutboxAction::from_item(&item).unwrap(), action);
+
+    let payload: serde_json::Value = serde_json::from_str(&item.payload).unwrap();
+    assert_eq!(payload["type"], "todoist_create_completed_task");
+    assert_eq!(payload["content"], "Write RFC");
+
+    let due = outbox.due(20).unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, item.id);
+}
+
+#[test]
+fn test_finish_queues_todoist_task() {
+    let conn = test_db();
+    let integration = add_todoist(&conn);
+    let service = TimeboxService::new(&conn);
+
+    let request = CreateTimeboxRequest {
+        notes: Some("First draft".to_string()),
+        ..timebox_request()
+    };
+    let id = service.create(&request).unwrap().id;
+    service.start(id).unwrap();
+    service.finish(id).unwrap();
+
+    let items = OutboxService::new(&conn).items(None, 50).unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].integration_id, integration);
+    assert_eq!(items[0].timebox_id, Some(id));
+
+    match OutboxAction::from_item(&items[0]).unwrap() {
+        OutboxAction::TodoistCreateCompletedTask { content, description, request_id, dedupe_key } => {
+            assert_eq!(content, "Write RFC");
+            assert_eq!(description.as_deref(), Some("First draft"));
+            assert!(!request_id.is_empty());
+            assert_eq!(dedupe_key, Some(format!("todoist_completed:{}", id)));
+        }
+        other => panic!("Unexpected action {:?}", other),
+    }
+
+    // A second finish, e.g. from the tray right after the UI, is turned down
+    assert!(service.finish(id).is_err());
+    assert_eq!(OutboxService::new(&conn).items(None, 50).unwrap().len(), 1);
+}
+
+#[test]
+fn test_transient_failure_is_retried_later() {
+    let conn = test_db();
+    let integration = add_todoist(&conn);
+    let outbox = OutboxService::new(&conn);
+    let item = outbox.enqueue(integration, None, &comment()).unwrap();
+
+    let error = ApiError::new(ApiErrorKind::Server, "Linear API returned status: 503");
+    let item = outbox.record_failure(item.id, &error).unwrap();
+    assert_eq!(item.status, OutboxStatus::Pending);
+    assert_eq!(item.attempts, 1);
+    assert_eq!(item.last_error.as_deref(), Some("Linear API returned status: 503"));
+    assert!(item.next_attempt_at.is_some());
+    assert!(outbox.due(20).unwrap().is_empty());
+
+    // A rate limit longer than the backoff pushes the next attempt out further
+    let mut limited = ApiError::new(ApiErrorKind::RateLimited, "Linear API returned status: 429");
+    limited.retry_after_secs = Some(7200);
+    let rescheduled = outbox.record_failure(item.id, &limited).unwrap();
+    assert_eq!(rescheduled.status, OutboxStatus::Pending);
+    assert!(rescheduled.next_attempt_at > item.next_attempt_at);
+}
+
+#[test]
+fn test_auth_and_rejected_errors_fail_immediately() {
+    let conn = test_db();
+    let integration = add_todoist(&conn);
+    let outbox = OutboxService::new(&conn);
+
+    for kind in [ApiErrorKind::Auth, ApiErrorKind::Rejected] {
+        let item = outbox.enqueue(integration, None, &comment()).unwrap();
+        let item = outbox.record_failure(item.id, &ApiError::new(kind, "Nope")).unwrap();
+        assert_eq!(item.status, OutboxStatus::Failed);
+        assert_eq!(item.next_attempt_at, None);
+    }
+}
+
+#[test]
+fn test_gives_up_after_max_attempts() {
+    let conn = test_db();
+    let integration = add_todoist(&conn);
+    let outbox = OutboxService::new(&conn);
+    let item = outbox.enqueue(integration, None, &comment()).unwrap();
+
+    let error = ApiError::new(ApiErrorKind::Offline, "Failed to connect to Linear");
+    for _ in 0..OUTBOX_MAX_ATTEMPTS - 1 {
+        assert_eq!(outbox.record_failure(item.id, &error).unwrap().status, OutboxStatus::Pending);
+    }
+    let item = outbox.record_failure(item.id, &error).unwrap();
+    assert_eq!(item.status, OutboxStatus::Failed);
+    assert_eq!(item.attempts, OUTBOX_MAX_ATTEMPTS);
+}
+
+#[test]
+fn test_retry_and_discard() {
+    let conn = test_db();
+    let integration = add_todoist(&conn);
+    let outbox = OutboxService::new(&conn);
+
+    let failed = outbox.enqueue(integration, None, &comment()).unwrap();
+    outbox.record_failure(failed.id, &ApiError::new(ApiErrorKind::Auth, "Bad key")).unwrap();
+
+    let retried = outbox.retry(failed.id).unwrap();
+    assert_eq!(retried.status, OutboxStatus::Pending);
+    assert_eq!(retried.attempts, 0);
+    assert_eq!(outbox.due(20).unwrap().len(), 1);
+
+    let discarded = outbox.discard(failed.id).unwrap();
+    assert_eq!(discarded.status, OutboxStatus::Discarded);
+    assert!(outbox.due(20).unwrap().is_empty());
+    assert_eq!(outbox.items(Some(OutboxStatus::Discarded), 50).unwrap().len(), 1);
+
+    let delivered = outbox.enqueue(integration, None, &comment()).unwrap();
+    outbox.record_success(delivered.id).unwrap();
+    assert_eq!(outbox.retry(delivered.id).unwrap_err(), "Item was already delivered");
+    assert_eq!(outbox.discard(delivered.id).unwrap_err(), "Item was already delivered");
+}
+
+#[test]
+fn test_discard_during_delivery_sticks() {
+    let conn = test_db();
+    let integration = add_todoist(&conn);
+    let outbox = OutboxService::new(&conn);
+
+    // Discarded while the network call was in flight
+    let succeeded = outbox.enqueue(integration, None, &comment()).unwrap();
+    outbox.discard(succeeded.id).unwrap();
+    let item = outbox.record_success(succeeded.id).unwrap();
+    assert_eq!((item.status, item.attempts), (OutboxStatus::Discarded, 0));
+
+    let failed = outbox.enqueue(integration, None, &comment()).unwrap();
+    outbox.discard(failed.id).unwrap();
+    let error = ApiError::new(ApiErrorKind::Server, "Linear API returned status: 503");
+    let item = outbox.record_failure(failed.id, &error).unwrap();
+    assert_eq!((item.status, item.attempts, item.next_attempt_at), (OutboxStatus::Discarded, 0, None));
+}
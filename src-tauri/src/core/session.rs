@@ -0,0 +1,138 @@
+use crate::core::timebox::now;
+use crate::models::Session;
+use rusqlite::{params, Connection, OptionalExtension};
+
+const SESSION_SELECT_COLUMNS: &str = "id, timebox_id, started_at, stopped_at, cancelled_at, auto_stopped_at";
+
+/// Work sessions of a timebox. A timebox has at most one open session, which is
+/// neither stopped nor cancelled.
+pub struct SessionService<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SessionService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        SessionService { conn }
+    }
+
+    pub fn get(&self, id: i64) -> Result<Session, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM sessions WHERE id = ?1", SESSION_SELECT_COLUMNS),
+                params![id],
+                Session::from_row,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    /// All sessions of a timebox, most recent first
+    pub fn for_timebox(&self, timebox_id: i64) -> Result<Vec<Session>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM sessions WHERE timebox_id = ?1 ORDER BY started_at DESC",
+                SESSION_SELECT_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let sessions: Vec<Session> = stmt
+            .query_map(params![timebox_id], Session::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(sessions)
+    }
+
+    pub fn active_for_timebox(&self, timebox_id: i64) -> Result<Option<Session>, String> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM sessions WHERE timebox_id = ?1 AND stopped_at IS NULL AND cancelled_at IS NULL LIMIT 1",
+                    SESSION_SELECT_COLUMNS
+                ),
+                params![timebox_id],
+                Session::from_row,
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+    }
+
+    /// Worked time in seconds, counting open sessions up to now and skipping cancelled ones
+    pub fn actual_duration(&self, timebox_id: i64) -> Result<f64, String> {
+        self.conn
+            .query_row(
+                "SELECT COALESCE(SUM((julianday(COALESCE(stopped_at, datetime('now', 'localtime'))) - julianday(started_at)) * 86400), 0)
+                 FROM sessions WHERE timebox_id = ?1 AND cancelled_at IS NULL",
+                params![timebox_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn stop(&self, id: i64) -> Result<Session, String> {
+        self.conn
+            .execute(
+                "UPDATE sessions SET stopped_at = ?1 WHERE id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
+                params![now(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    pub fn cancel(&self, id: i64) -> Result<Session, String> {
+        self.conn
+            .execute(
+                "UPDATE sessions SET cancelled_at = ?1 WHERE id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
+                params![now(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    /// Opens a session, unless the timebox already has one
+    pub(crate) fn open(&self, timebox_id: i64, now: &str) -> Result<(), String> {
+        if self.active_for_timebox(timebox_id)?.is_some() {
+            return Err(format!("Timebox {} already has an open session", timebox_id));
+        }
+        self.conn
+            .execute(
+                "INSERT INTO sessions (timebox_id, started_at) VALUES (?1, ?2)",
+                params![timebox_id, now],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub(crate) fn close_open(&self, timebox_id: i64, now: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "UPDATE sessions SET stopped_at = ?1 WHERE timebox_id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
+                params![now, timebox_id],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub(crate) fn auto_stop_open(&self, timebox_id: i64, now: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "UPDATE sessions SET stopped_at = ?1, auto_stopped_at = ?1 WHERE timebox_id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
+                params![now, timebox_id],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub(crate) fn cancel_open(&self, timebox_id: i64, now: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "UPDATE sessions SET cancelled_at = ?1 WHERE timebox_id = ?2 AND stopped_at IS NULL AND cancelled_at IS NULL",
+                params![now, timebox_id],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use super::{SessionService, TimeboxService};
+use crate::test_support::{create_timebox, test_db};
+
+#[test]
+fn test_sessions_are_most_recent_first() {
+    let conn = test_db();
+    let id = create_timebox(&conn);
+
+    conn.execute_batch(&format!(
+        "INSERT INTO sessions (timebox_id, started_at, stopped_at) VALUES ({id}, '2025-01-01 09:00:00', '2025-01-01 09:10:00');
+         INSERT INTO sessions (timebox_id, started_at, stopped_at) VALUES ({id}, '2025-01-01 11:00:00', '2025-01-01 11:10:00');"
+    ))
+    .unwrap();
+
+    let sessions = SessionService::new(&conn).for_timebox(id).unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0].started_at, "2025-01-01 11:00:00");
+}
+
+#[test]
+fn test_active_for_timebox() {
+    let conn = test_db();
+    let id = create_timebox(&conn);
+    let sessions = SessionService::new(&conn);
+    let timeboxes = TimeboxService::new(&conn);
+
+    assert!(sessions.active_for_timebox(id).unwrap().is_none());
+
+    timeboxes.start(id).unwrap();
+    let active = sessions.active_for_timebox(id).unwrap().unwrap();
+    assert_eq!(active.timebox_id, id);
+
+    timeboxes.pause(id).unwrap();
+    assert!(sessions.active_for_timebox(id).unwrap().is_none());
+}
+
+#[test]
+fn test_stop_session() {
+    let conn = test_db();
+    let id = create_timebox(&conn);
+    let sessions = SessionService::new(&conn);
+
+    TimeboxService::new(&conn).start(id).unwrap();
+    let session_id = sessions.active_for_timebox(id).unwrap().unwrap().id;
+
+    let stopped = sessions.stop(session_id).unwrap();
+    assert!(stopped.stopped_at.is_some());
+    assert!(stopped.cancelled_at.is_none());
+
+    // Cancelling an already stopped session changes nothing
+    let unchanged = sessions.cancel(session_id).unwrap();
+    assert!(unchanged.cancelled_at.is_none());
+}
+
+#[test]
+fn test_cancel_session() {
+    let conn = test_db();
+    let id = create_timebox(&conn);
+    let sessions = SessionService::new(&conn);
+
+    TimeboxService::new(&conn).start(id).unwrap();
+    let session_id = sessions.active_for_timebox(id).unwrap().unwrap().id;
+
+    let cancelled = sessions.cancel(session_id).unwrap();
+    assert!(cancelled.cancelled_at.is_some());
+    assert!(cancelled.stopped_at.is_none());
+    assert_eq!(sessions.actual_duration(id).unwrap(), 0.0);
+}
+
+#[test]
+fn test_get_missing_session_is_an_error() {
+    let conn = test_db();
+    assert!(SessionService::new(&conn).get(7).is_err());
+}
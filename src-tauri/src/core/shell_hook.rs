@@ -0,0 +1,303 @@
+use super::event::TimeboxEvent;
+use super::timebox::{now, TimeboxService, TimeboxWithSessions};
+use crate::models::{SaveShellHookRequest, ShellHook, ShellHookRun, ShellHookRunStatus};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+
+pub const SHELL_HOOKS_ENABLED_SETTING: &str = "shell_hooks_enabled";
+
+pub const DEFAULT_SHELL_HOOK_TIMEOUT_SECONDS: i64 = 10;
+pub const MAX_SHELL_HOOK_TIMEOUT_SECONDS: i64 = 300;
+
+const SHELL_HOOK_SELECT_COLUMNS: &str = "id, event, command, timeout_seconds, enabled, created_at, updated_at";
+const SHELL_HOOK_RUN_SELECT_COLUMNS: &str = "id, hook_id, timebox_id, event, command, env, status, exit_code, stdout, stderr, started_at, finished_at, created_at";
+
+/// Hooks only run when the user turned them on in settings
+pub fn shell_hooks_enabled(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        params![SHELL_HOOKS_ENABLED_SETTING],
+        |row| row.get::<_, String>(0),
+    )
+    .map(|value| value == "true")
+    .unwrap_or(false)
+}
+
+/// Environment passed to hook commands, all prefixed with TIMEBOXD_
+pub fn shell_hook_env(event: TimeboxEvent, timebox: &TimeboxWithSessions) -> BTreeMap<String, String> {
+    let t = &timebox.timebox;
+    let mut env = BTreeMap::new();
+
+    env.insert("TIMEBOXD_EVENT".to_string(), event.as_str().to_string());
+    env.insert("TIMEBOXD_TIMEBOX_ID".to_string(), t.id.to_string());
+    env.insert("TIMEBOXD_INTENTION".to_string(), t.intention.clone());
+    env.insert("TIMEBOXD_STATUS".to_string(), t.status.as_str().to_string());
+    env.insert("TIMEBOXD_INTENDED_MINUTES".to_string(), t.intended_duration.to_string());
+    env.insert("TIMEBOXD_ELAPSED_SECONDS".to_string(), (timebox.actual_duration as i64).to_string());
+    env.insert("TIMEBOXD_REMAINING_SECONDS".to_string(), timebox.remaining_seconds().to_string());
+    env.insert("TIMEBOXD_NOTES".to_string(), t.notes.clone().unwrap_or_default());
+    env.insert("TIMEBOXD_TAGS".to_string(), t.tags.join(","));
+    env.insert(
+        "TIMEBOXD_LINEAR_ISSUE".to_string(),
+        t.linear_issue_identifier.clone().unwrap_or_default(),
+    );
+    env.insert("TIMEBOXD_LINEAR_ISSUE_URL".to_string(), t.linear_issue_url.clone().unwrap_or_default());
+
+    env
+}
+
+pub struct ShellHookService<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> ShellHookService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        ShellHookService { conn }
+    }
+
+    pub fn list(&self) -> Result<Vec<ShellHook>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT {} FROM shell_hooks ORDER BY event, id", SHELL_HOOK_SELECT_COLUMNS))
+            .map_err(|e| e.to_string())?;
+
+        let hooks = stmt
+            .query_map([], ShellHook::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(hooks)
+    }
+
+    pub fn get(&self, id: i64) -> Result<ShellHook, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM shell_hooks WHERE id = ?1", SHELL_HOOK_SELECT_COLUMNS),
+                params![id],
+                ShellHook::from_row,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn create(&self, request: &SaveShellHookRequest) -> Result<ShellHook, String> {
+        let (event, command, timeout_seconds) = validate(request)?;
+        let now = now();
+
+        self.conn
+            .execute(
+                "INSERT INTO shell_hooks (event, command, timeout_seconds, enabled, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                params![event.as_str(), command, timeout_seconds, request.enabled.unwrap_or(true), now],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(self.conn.last_insert_rowid())
+    }
+
+    pub fn update(&self, id: i64, request: &SaveShellHookRequest) -> Result<ShellHook, String> {
+        let (event, command, timeout_seconds) = validate(request)?;
+        let enabled = match request.enabled {
+            Some(enabled) => enabled,
+            None => self.get(id)?.enabled,
+        };
+
+        self.conn
+            .execute(
+                "UPDATE shell_hooks SET event = ?1, command = ?2, timeout_seconds = ?3, enabled = ?4, updated_at = ?5 WHERE id = ?6",
+                params![event.as_str(), command, timeout_seconds, enabled, now(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    pub fn delete(&self, id: i64) -> Result<(), String> {
+        self.conn
+            .execute("DELETE FROM shell_hooks WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Queues a run of every enabled hook for the event, returning how many were queued.
+    /// Does nothing while hooks are turned off.
+    pub fn enqueue(&self, event: TimeboxEvent, timebox_id: i64) -> Result<usize, String> {
+        self.enqueue_where(event, timebox_id, |_| Ok(true))
+    }
+
+    /// Like `enqueue`, but skips hooks that already ran for this event and timebox
+    pub fn enqueue_once(&self, event: TimeboxEvent, timebox_id: i64) -> Result<usize, String> {
+        self.enqueue_where(event, timebox_id, |hook_id| {
+            let existing: Option<i64> = self
+                .conn
+                .query_row(
+                    "SELECT id FROM shell_hook_runs WHERE hook_id = ?1 AND timebox_id = ?2 AND event = ?3 LIMIT 1",
+                    params![hook_id, timebox_id, event.as_str()],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+            Ok(existing.is_none())
+        })
+    }
+
+    fn enqueue_where(
+        &self,
+        event: TimeboxEvent,
+        timebox_id: i64,
+        should_run: impl Fn(i64) -> Result<bool, String>,
+    ) -> Result<usize, String> {
+        if !shell_hooks_enabled(self.conn) {
+            return Ok(0);
+        }
+
+        let hooks: Vec<ShellHook> = self
+            .list()?
+            .into_iter()
+            .filter(|hook| hook.enabled && hook.event == event.as_str())
+            .collect();
+        if hooks.is_empty() {
+            return Ok(0);
+        }
+
+        let timebox = TimeboxService::new(self.conn).get_with_sessions(timebox_id)?;
+        let env = serde_json::to_string(&shell_hook_env(event, &timebox)).map_err(|e| e.to_string())?;
+
+        let mut queued = 0;
+        for hook in hooks {
+            if !should_run(hook.id)? {
+                continue;
+            }
+            self.conn
+                .execute(
+                    "INSERT INTO shell_hook_runs (hook_id, timebox_id, event, command, env, status, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        hook.id,
+                        timebox_id,
+                        event.as_str(),
+                        hook.command,
+                        env,
+                        ShellHookRunStatus::Pending.as_str(),
+                        now()
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+            queued += 1;
+        }
+
+        Ok(queued)
+    }
+
+    pub fn get_run(&self, id: i64) -> Result<ShellHookRun, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM shell_hook_runs WHERE id = ?1", SHELL_HOOK_RUN_SELECT_COLUMNS),
+                params![id],
+                ShellHookRun::from_row,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    /// Most recent runs first
+    pub fn runs(&self, limit: i64) -> Result<Vec<ShellHookRun>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM shell_hook_runs ORDER BY created_at DESC, id DESC LIMIT ?1",
+                SHELL_HOOK_RUN_SELECT_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let runs = stmt
+            .query_map(params![limit], ShellHookRun::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(runs)
+    }
+
+    /// Claims queued runs created at or after `not_before`, oldest first, marking them running.
+    /// Older ones are marked skipped.
+    pub fn take_pending(&self, not_before: &str) -> Result<Vec<ShellHookRun>, String> {
+        self.conn
+            .execute(
+                "UPDATE shell_hook_runs SET status = ?1 WHERE status = ?2 AND created_at < ?3",
+                params![ShellHookRunStatus::Skipped.as_str(), ShellHookRunStatus::Pending.as_str(), not_before],
+            )
+            .map_err(|e| e.to_string())?;
+
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM shell_hook_runs WHERE status = ?1 ORDER BY created_at, id",
+                SHELL_HOOK_RUN_SELECT_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let runs: Vec<ShellHookRun> = stmt
+            .query_map(params![ShellHookRunStatus::Pending.as_str()], ShellHookRun::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        let now = now();
+        for run in &runs {
+            self.conn
+                .execute(
+                    "UPDATE shell_hook_runs SET status = ?1, started_at = ?2 WHERE id = ?3",
+                    params![ShellHookRunStatus::Running.as_str(), now, run.id],
+                )
+                .map_err(|e| e.to_string())?;
+        }
+
+        runs.iter().map(|run| self.get_run(run.id)).collect()
+    }
+
+    pub fn record_result(
+        &self,
+        id: i64,
+        status: ShellHookRunStatus,
+        exit_code: Option<i32>,
+        stdout: &str,
+        stderr: &str,
+    ) -> Result<ShellHookRun, String> {
+        self.conn
+            .execute(
+                "UPDATE shell_hook_runs SET status = ?1, exit_code = ?2, stdout = ?3, stderr = ?4, finished_at = ?5 WHERE id = ?6",
+                params![status.as_str(), exit_code, stdout, stderr, now(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get_run(id)
+    }
+
+    /// Runs left running when the app quit never finished
+    pub fn fail_interrupted(&self) -> Result<(), String> {
+        self.conn
+            .execute(
+                "UPDATE shell_hook_runs SET status = ?1, stderr = 'Interrupted: timeboxd quit while the command was running', finished_at = ?2 WHERE status = ?3",
+                params![ShellHookRunStatus::Failed.as_str(), now(), ShellHookRunStatus::Running.as_str()],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+fn validate(request: &SaveShellHookRequest) -> Result<(TimeboxEvent, String, i64), String> {
+    let event = TimeboxEvent::parse(&request.event).ok_or_else(|| format!("Unknown event '{}'", request.event))?;
+
+    let command = request.command.trim().to_string();
+    if command.is_empty() {
+        return Err("Command can't be empty".to_string());
+    }
+
+    let timeout_seconds = request.timeout_seconds.unwrap_or(DEFAULT_SHELL_HOOK_TIMEOUT_SECONDS);
+    if !(1..=MAX_SHELL_HOOK_TIMEOUT_SECONDS).contains(&timeout_seconds) {
+        return Err(format!(
+            "Timeout must be between 1 and {} seconds",
+            MAX_SHELL_HOOK_TIMEOUT_SECONDS
+        ));
+    }
+
+    Ok((event, command, timeout_seconds))
+}
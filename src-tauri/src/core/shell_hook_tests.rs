@@ -0,0 +1,156 @@
+use super::{ShellHookService, TimeboxEvent, TimeboxService};
+use crate::models::{CreateTimeboxRequest, SaveShellHookRequest, ShellHookRunStatus};
+use crate::test_support::{test_db, timebox_request};
+use rusqlite::{params, Connection};
+use std::collections::BTreeMap;
+
+fn enable_hooks(conn: &Connection) {
+    conn.execute("UPDATE settings SET value = 'true' WHERE key = 'shell_hooks_enabled'", [])
+        .unwrap();
+}
+
+fn hook(event: &str, command: &str) -> SaveShellHookRequest {
+    SaveShellHookRequest {
+        event: event.to_string(),
+        command: command.to_string(),
+        timeout_seconds: None,
+        enabled: None,
+    }
+}
+
+fn create_tagged_timebox(conn: &Connection) -> i64 {
+    let request = CreateTimeboxRequest {
+        linear_issue_identifier: Some("ENG-42".to_string()),
+        tags: vec!["writing".to_string(), "deep".to_string()],
+        ..timebox_request()
+    };
+    TimeboxService::new(conn).create(&request).unwrap().id
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let conn = test_db();
+    let hooks = ShellHookService::new(&conn);
+    hooks.create(&hook("started", "notify-send started")).unwrap();
+
+    let id = create_tagged_timebox(&conn);
+    TimeboxService::new(&conn).start(id).unwrap();
+
+    assert!(hooks.runs(10).unwrap().is_empty());
+}
+
+#[test]
+fn test_lifecycle_queues_runs_with_env() {
+    let conn = test_db();
+    enable_hooks(&conn);
+    let hooks = ShellHookService::new(&conn);
+    hooks.create(&hook("started", "dunstctl set-paused true")).unwrap();
+    hooks.create(&hook("paused", "dunstctl set-paused false")).unwrap();
+
+    let id = create_tagged_timebox(&conn);
+    let service = TimeboxService::new(&conn);
+    service.start(id).unwrap();
+    service.pause(id).unwrap();
+
+    let runs = hooks.runs(10).unwrap();
+    let commands: Vec<&str> = runs.iter().rev().map(|r| r.command.as_str()).collect();
+    assert_eq!(commands, vec!["dunstctl set-paused true", "dunstctl set-paused false"]);
+
+    let env: BTreeMap<String, String> = serde_json::from_str(&runs[1].env).unwrap();
+    assert_eq!(env["TIMEBOXD_EVENT"], "started");
+    assert_eq!(env["TIMEBOXD_TIMEBOX_ID"], id.to_string());
+    assert_eq!(env["TIMEBOXD_INTENTION"], "Write RFC");
+    assert_eq!(env["TIMEBOXD_STATUS"], "in_progress");
+    assert_eq!(env["TIMEBOXD_INTENDED_MINUTES"], "25");
+    assert_eq!(env["TIMEBOXD_TAGS"], "writing,deep");
+    assert_eq!(env["TIMEBOXD_LINEAR_ISSUE"], "ENG-42");
+}
+
+#[test]
+fn test_disabled_hooks_do_not_run() {
+    let conn = test_db();
+    enable_hooks(&conn);
+    let hooks = ShellHookService::new(&conn);
+    let mut request = hook("created", "echo hi");
+    request.enabled = Some(false);
+    hooks.create(&request).unwrap();
+
+    create_tagged_timebox(&conn);
+
+    assert!(hooks.runs(10).unwrap().is_empty());
+}
+
+#[test]
+fn test_validation() {
+    let conn = test_db();
+    let hooks = ShellHookService::new(&conn);
+
+    assert!(hooks.create(&hook("exploded", "echo hi")).is_err());
+    assert!(hooks.create(&hook("started", "   ")).is_err());
+
+    let mut request = hook("started", "echo hi");
+    request.timeout_seconds = Some(0);
+    assert!(hooks.create(&request).is_err());
+    request.timeout_seconds = Some(301);
+    assert!(hooks.create(&request).is_err());
+
+    request.timeout_seconds = Some(30);
+    let created = hooks.create(&request).unwrap();
+    assert_eq!(created.timeout_seconds, 30);
+    assert!(created.enabled);
+
+    let updated = hooks.update(created.id, &hook("finished", "echo done")).unwrap();
+    assert_eq!(updated.event, "finished");
+    assert_eq!(updated.timeout_seconds, 10);
+    assert!(updated.enabled);
+}
+
+#[test]
+fn test_take_pending_claims_runs_and_skips_stale_ones() {
+    let conn = test_db();
+    enable_hooks(&conn);
+    let hooks = ShellHookService::new(&conn);
+    hooks.create(&hook("created", "echo hi")).unwrap();
+
+    create_tagged_timebox(&conn);
+    create_tagged_timebox(&conn);
+    conn.execute(
+        "UPDATE shell_hook_runs SET created_at = '2000-01-01 00:00:00' WHERE id = (SELECT MIN(id) FROM shell_hook_runs)",
+        params![],
+    )
+    .unwrap();
+
+    let taken = hooks.take_pending("2020-01-01 00:00:00").unwrap();
+    assert_eq!(taken.len(), 1);
+    assert_eq!(taken[0].status, ShellHookRunStatus::Running);
+    assert!(taken[0].started_at.is_some());
+
+    // Claimed runs aren't handed out twice
+    assert!(hooks.take_pending("2020-01-01 00:00:00").unwrap().is_empty());
+
+    let statuses: Vec<ShellHookRunStatus> = hooks.runs(10).unwrap().into_iter().map(|r| r.status).collect();
+    assert!(statuses.contains(&ShellHookRunStatus::Skipped));
+
+    let done = hooks
+        .record_result(taken[0].id, ShellHookRunStatus::Failed, Some(1), "", "boom")
+        .unwrap();
+    assert_eq!(done.exit_code, Some(1));
+    assert_eq!(done.stderr.as_deref(), Some("boom"));
+    assert!(done.finished_at.is_some());
+}
+
+#[test]
+fn test_enqueue_once_and_interrupted_runs() {
+    let conn = test_db();
+    enable_hooks(&conn);
+    let hooks = ShellHookService::new(&conn);
+    hooks.create(&hook("overtime", "echo over")).unwrap();
+    let id = create_tagged_timebox(&conn);
+
+    assert_eq!(hooks.enqueue_once(TimeboxEvent::Overtime, id).unwrap(), 1);
+    assert_eq!(hooks.enqueue_once(TimeboxEvent::Overtime, id).unwrap(), 0);
+
+    let taken = hooks.take_pending("2000-01-01 00:00:00").unwrap();
+    hooks.fail_interrupted().unwrap();
+    assert_eq!(hooks.get_run(taken[0].id).unwrap().status, ShellHookRunStatus::Failed);
+}
@@ -0,0 +1,565 @@
+use crate::core::session::SessionService;
+use crate::core::event::TimeboxEvent;
+use crate::core::shell_hook::ShellHookService;
+use crate::core::webhook::WebhookService;
+use crate::integrations::{run_provider_hooks, ProviderHook};
+use crate::models::{CreateTimeboxRequest, Session, Timebox, TimeboxChangeLog, TimeboxStatus, UpdateTimeboxRequest};
+use chrono::Local;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+pub const TIMEBOX_SELECT_COLUMNS: &str = "id, intention, notes, intended_duration, status, created_at, updated_at, started_at, completed_at, after_time_stopped_at, deleted_at, canceled_at, display_order, archived_at, finished_at, linear_project_id, linear_issue_id, linear_issue_identifier, linear_issue_url, tags, linear_issue_link_status, linear_issue_checked_at";
+
+#[derive(Debug, Serialize)]
+pub struct TimeboxWithSessions {
+    #[serde(flatten)]
+    pub timebox: Timebox,
+    pub sessions: Vec<Session>,
+    pub actual_duration: f64,
+}
+
+impl TimeboxWithSessions {
+    /// Seconds left in the timebox; negative once it is in overtime.
+    pub fn remaining_seconds(&self) -> i64 {
+        // intended_duration is stored in minutes by the UI (see useTimers.ts)
+        self.timebox.intended_duration * 60 - self.actual_duration.floor() as i64
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderTimeboxRequest {
+    pub id: i64,
+    pub display_order: i64,
+}
+
+/// Timebox queries and lifecycle transitions over a plain SQLite connection, shared by
+/// the Tauri commands and the CLI.
+pub struct TimeboxService<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> TimeboxService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        TimeboxService { conn }
+    }
+
+    pub fn get(&self, id: i64) -> Result<Timebox, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM timeboxes WHERE id = ?1", TIMEBOX_SELECT_COLUMNS),
+                params![id],
+                Timebox::from_row,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn get_with_sessions(&self, id: i64) -> Result<TimeboxWithSessions, String> {
+        self.with_sessions(self.get(id)?)
+    }
+
+    pub fn create(&self, request: &CreateTimeboxRequest) -> Result<Timebox, String> {
+        let tags_json = if request.tags.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&request.tags).map_err(|e| e.to_string())?)
+        };
+
+        self.conn
+            .execute(
+                "INSERT INTO timeboxes (intention, intended_duration, notes, linear_project_id, linear_issue_id, linear_issue_identifier, linear_issue_url, tags) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    request.intention,
+                    request.intended_duration,
+                    request.notes,
+                    request.linear_project_id,
+                    request.linear_issue_id,
+                    request.linear_issue_identifier,
+                    request.linear_issue_url,
+                    tags_json
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+
+        let id = self.conn.last_insert_rowid();
+        self.notify_event(TimeboxEvent::Created, id);
+        self.get(id)
+    }
+
+    /// Updates intention, notes and duration, recording what changed in the change log
+    pub fn update(&self, id: i64, request: &UpdateTimeboxRequest) -> Result<Timebox, String> {
+        let now = now();
+
+        // Get current timebox state for change log
+        let current: Timebox = self
+            .conn
+            .query_row(
+                &format!("SELECT {} FROM timeboxes WHERE id = ?1 AND deleted_at IS NULL", TIMEBOX_SELECT_COLUMNS),
+                params![id],
+                Timebox::from_row,
+            )
+            .map_err(|e| e.to_string())?;
+
+        // Determine new values (use request value if provided, otherwise keep current)
+        let new_intention = request.intention.clone().unwrap_or(current.intention.clone());
+        let new_notes = if request.notes.is_some() { request.notes.clone() } else { current.notes.clone() };
+        let new_duration = request.intended_duration.unwrap_or(current.intended_duration);
+
+        // Log changes if any field is being updated
+        let has_intention_change = new_intention != current.intention;
+        let has_notes_change = new_notes != current.notes;
+        let has_duration_change = new_duration != current.intended_duration;
+
+        if has_intention_change || has_notes_change || has_duration_change {
+            self.conn
+                .execute(
+                    "INSERT INTO timebox_change_log (timebox_id, previous_intention_title, updated_intention_title, previous_note_content, updated_note_content, previous_intended_duration, new_intended_duration, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        id,
+                        if has_intention_change { Some(&current.intention) } else { None::<&String> },
+                        if has_intention_change { Some(&new_intention) } else { None::<&String> },
+                        if has_notes_change { current.notes.as_ref() } else { None::<&String> },
+                        if has_notes_change { new_notes.as_ref() } else { None::<&String> },
+                        if has_duration_change { Some(current.intended_duration) } else { None::<i64> },
+                        if has_duration_change { Some(new_duration) } else { None::<i64> },
+                        now
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+        }
+
+        self.conn
+            .execute(
+                "UPDATE timeboxes SET intention = ?1, notes = ?2, intended_duration = ?3, updated_at = ?4 WHERE id = ?5",
+                params![new_intention, new_notes, new_duration, now, id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    pub fn start(&self, id: i64) -> Result<Timebox, String> {
+        let now = now();
+        // Integration side effects are queued in the same transaction as the change
+        let tx = self.conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+        // Check if this is the first start (started_at is null)
+        let (started_at, status): (Option<String>, String) = self
+            .conn
+            .query_row(
+                "SELECT started_at, status FROM timeboxes WHERE id = ?1 AND deleted_at IS NULL",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| e.to_string())?;
+
+        // Only stopped timeboxes can be picked up again once they ended
+        match TimeboxStatus::from_str(&status) {
+            TimeboxStatus::NotStarted | TimeboxStatus::Paused | TimeboxStatus::Stopped => {}
+            TimeboxStatus::InProgress => return Err(format!("Timebox {} is already running", id)),
+            TimeboxStatus::Completed | TimeboxStatus::Cancelled => {
+                return Err(format!("Timebox {} is {} and can't be started again", id, status))
+            }
+        }
+
+        // Update timebox - set started_at only if first time, always set status to in_progress
+        // Also clear completed_at so a stopped timebox can be restarted and appear in active list
+        if started_at.is_none() {
+            self.conn
+                .execute(
+                    "UPDATE timeboxes SET started_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
+                    params![now, TimeboxStatus::InProgress.as_str(), id],
+                )
+                .map_err(|e| e.to_string())?;
+        } else {
+            self.conn
+                .execute(
+                    "UPDATE timeboxes SET status = ?1, completed_at = NULL, updated_at = ?2 WHERE id = ?3",
+                    params![TimeboxStatus::InProgress.as_str(), now, id],
+                )
+                .map_err(|e| e.to_string())?;
+        }
+
+        SessionService::new(self.conn).open(id, &now)?;
+
+        let timebox = self.get(id)?;
+        self.queue_integration_actions(ProviderHook::Started, &timebox)?;
+        self.notify_event(TimeboxEvent::Started, id);
+        tx.commit().map_err(|e| e.to_string())?;
+        Ok(timebox)
+    }
+
+    /// User manually stopped: closes open sessions and marks the timebox stopped
+    pub fn stop(&self, id: i64) -> Result<Timebox, String> {
+        let now = now();
+        let tx = self.conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        self.ensure_not_ended(id, "stopped")?;
+        SessionService::new(self.conn).close_open(id, &now)?;
+
+        self.conn
+            .execute(
+                "UPDATE timeboxes SET completed_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
+                params![now, TimeboxStatus::Stopped.as_str(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        let timebox = self.get(id)?;
+        self.queue_integration_actions(ProviderHook::Stopped, &timebox)?;
+        self.notify_event(TimeboxEvent::Stopped, id);
+        tx.commit().map_err(|e| e.to_string())?;
+        Ok(timebox)
+    }
+
+    /// Stopped because the system was idle. Same as `stop` but the sessions are marked auto-stopped.
+    pub fn auto_stop(&self, id: i64) -> Result<Timebox, String> {
+        let now = now();
+        let tx = self.conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        SessionService::new(self.conn).auto_stop_open(id, &now)?;
+
+        self.conn
+            .execute(
+                "UPDATE timeboxes SET completed_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
+                params![now, TimeboxStatus::Stopped.as_str(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        let timebox = self.get(id)?;
+        self.queue_integration_actions(ProviderHook::Stopped, &timebox)?;
+        self.notify_event(TimeboxEvent::AutoStopped, id);
+        tx.commit().map_err(|e| e.to_string())?;
+        Ok(timebox)
+    }
+
+    /// The timer ran out: closes open sessions and marks the timebox completed
+    pub fn stop_after_time(&self, id: i64) -> Result<Timebox, String> {
+        let now = now();
+        let tx = self.conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        SessionService::new(self.conn).close_open(id, &now)?;
+
+        self.conn
+            .execute(
+                "UPDATE timeboxes SET after_time_stopped_at = ?1, completed_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
+                params![now, TimeboxStatus::Completed.as_str(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        let timebox = self.get(id)?;
+        self.queue_integration_actions(ProviderHook::Stopped, &timebox)?;
+        self.notify_event(TimeboxEvent::Stopped, id);
+        tx.commit().map_err(|e| e.to_string())?;
+        Ok(timebox)
+    }
+
+    pub fn cancel(&self, id: i64) -> Result<Timebox, String> {
+        let now = now();
+        SessionService::new(self.conn).cancel_open(id, &now)?;
+
+        self.conn
+            .execute(
+                "UPDATE timeboxes SET canceled_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
+                params![now, TimeboxStatus::Cancelled.as_str(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    pub fn pause(&self, id: i64) -> Result<Timebox, String> {
+        let now = now();
+        SessionService::new(self.conn).close_open(id, &now)?;
+
+        self.conn
+            .execute(
+                "UPDATE timeboxes SET status = ?1, updated_at = ?2 WHERE id = ?3",
+                params![TimeboxStatus::Paused.as_str(), now, id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.notify_event(TimeboxEvent::Paused, id);
+        self.get(id)
+    }
+
+    /// User explicitly finished: marks the timebox completed and queues what connected
+    /// integrations do in response (e.g. Todoist records a completed task).
+    pub fn finish(&self, id: i64) -> Result<Timebox, String> {
+        let now = now();
+        let tx = self.conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        // Finishing twice would queue the integration actions twice
+        self.ensure_not_ended(id, "finished")?;
+        SessionService::new(self.conn).close_open(id, &now)?;
+
+        self.conn
+            .execute(
+                "UPDATE timeboxes SET finished_at = ?1, completed_at = ?1, status = ?2, updated_at = ?1 WHERE id = ?3",
+                params![now, TimeboxStatus::Completed.as_str(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        let timebox = self.get(id)?;
+
+        self.queue_integration_actions(ProviderHook::Finished, &timebox)?;
+
+        self.notify_event(TimeboxEvent::Finished, id);
+        tx.commit().map_err(|e| e.to_string())?;
+        Ok(timebox)
+    }
+
+    /// Finished and cancelled timeboxes can't be stopped or finished again
+    fn ensure_not_ended(&self, id: i64, action: &str) -> Result<(), String> {
+        let status: String = self
+            .conn
+            .query_row(
+                "SELECT status FROM timeboxes WHERE id = ?1 AND deleted_at IS NULL",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+
+        match TimeboxStatus::from_str(&status) {
+            TimeboxStatus::Completed | TimeboxStatus::Cancelled => {
+                Err(format!("Timebox {} is {} and can't be {}", id, status, action))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Soft delete
+    pub fn delete(&self, id: i64) -> Result<Timebox, String> {
+        self.set_timestamp_column(id, "deleted_at", Some(&now()))
+    }
+
+    pub fn archive(&self, id: i64) -> Result<Timebox, String> {
+        self.set_timestamp_column(id, "archived_at", Some(&now()))
+    }
+
+    pub fn unarchive(&self, id: i64) -> Result<Timebox, String> {
+        self.set_timestamp_column(id, "archived_at", None)
+    }
+
+    pub fn reorder(&self, orders: &[ReorderTimeboxRequest]) -> Result<(), String> {
+        let now = now();
+
+        for order in orders {
+            self.conn
+                .execute(
+                    "UPDATE timeboxes SET display_order = ?1, updated_at = ?2 WHERE id = ?3",
+                    params![order.display_order, now, order.id],
+                )
+                .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Links the timebox to an issue, clearing the result of the previous link's check
+    pub fn set_linear_issue(
+        &self,
+        id: i64,
+        linear_issue_id: &str,
+        linear_issue_identifier: &str,
+        linear_issue_url: &str,
+    ) -> Result<Timebox, String> {
+        self.conn
+            .execute(
+                r#"UPDATE timeboxes SET linear_issue_id = ?1, linear_issue_identifier = ?2, linear_issue_url = ?3,
+                     linear_issue_link_status = NULL, linear_issue_checked_at = NULL, updated_at = ?4
+                   WHERE id = ?5"#,
+                params![linear_issue_id, linear_issue_identifier, linear_issue_url, now(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    pub fn set_linear_project(&self, id: i64, linear_project_id: Option<i64>) -> Result<Timebox, String> {
+        self.conn
+            .execute(
+                "UPDATE timeboxes SET linear_project_id = ?1, updated_at = ?2 WHERE id = ?3",
+                params![linear_project_id, now(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    pub fn change_log(&self, id: i64) -> Result<Vec<TimeboxChangeLog>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, timebox_id, previous_intention_title, updated_intention_title, previous_note_content, updated_note_content, previous_intended_duration, new_intended_duration, updated_at
+                 FROM timebox_change_log
+                 WHERE timebox_id = ?1
+                 ORDER BY updated_at DESC",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let logs: Vec<TimeboxChangeLog> = stmt
+            .query_map(params![id], TimeboxChangeLog::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(logs)
+    }
+
+    /// Timeboxes created in the last 24 hours that aren't archived or deleted
+    pub fn today(&self) -> Result<Vec<TimeboxWithSessions>, String> {
+        self.list_with_sessions(
+            "created_at >= datetime('now', 'localtime', '-24 hours')
+               AND deleted_at IS NULL
+               AND archived_at IS NULL
+             ORDER BY COALESCE(display_order, 999999), created_at DESC",
+        )
+    }
+
+    /// Started but not completed, not stopped after time, not canceled, not deleted
+    pub fn active(&self) -> Result<Vec<TimeboxWithSessions>, String> {
+        self.list_with_sessions(
+            "started_at IS NOT NULL
+               AND completed_at IS NULL
+               AND after_time_stopped_at IS NULL
+               AND canceled_at IS NULL
+               AND deleted_at IS NULL
+             ORDER BY created_at DESC",
+        )
+    }
+
+    /// Archived timeboxes from the last 24 hours, most recently archived first
+    pub fn archived(&self) -> Result<Vec<TimeboxWithSessions>, String> {
+        self.list_with_sessions(
+            "created_at >= datetime('now', 'localtime', '-24 hours')
+               AND deleted_at IS NULL
+               AND archived_at IS NOT NULL
+             ORDER BY archived_at DESC",
+        )
+    }
+
+    /// The timebox the user is currently working on: the most recently started
+    /// in_progress timebox, falling back to the most recently paused one.
+    pub fn current(&self) -> Result<Option<TimeboxWithSessions>, String> {
+        // Sessions are ordered by started_at DESC, so the first one is the latest
+        let last_started = |t: &TimeboxWithSessions| {
+            t.sessions.first().map(|s| s.started_at.clone()).unwrap_or_default()
+        };
+
+        let (in_progress, others): (Vec<_>, Vec<_>) = self
+            .active()?
+            .into_iter()
+            .partition(|t| t.timebox.status == TimeboxStatus::InProgress);
+
+        Ok(in_progress.into_iter().max_by_key(last_started).or_else(|| {
+            others
+                .into_iter()
+                .filter(|t| t.timebox.status == TimeboxStatus::Paused)
+                .max_by_key(last_started)
+        }))
+    }
+
+    /// The timebox a start without an id picks up: the most recently paused one, falling
+    /// back to the most recently stopped one from today. Running timeboxes are never picked.
+    pub fn resumable(&self) -> Result<Option<TimeboxWithSessions>, String> {
+        let last_started = |t: &TimeboxWithSessions| {
+            t.sessions.first().map(|s| s.started_at.clone()).unwrap_or_default()
+        };
+
+        let paused = self
+            .active()?
+            .into_iter()
+            .filter(|t| t.timebox.status == TimeboxStatus::Paused)
+            .max_by_key(last_started);
+        if paused.is_some() {
+            return Ok(paused);
+        }
+
+        Ok(self
+            .list_with_sessions(
+                "status = 'stopped'
+                   AND created_at >= datetime('now', 'localtime', '-24 hours')
+                   AND deleted_at IS NULL
+                   AND archived_at IS NULL
+                 ORDER BY completed_at DESC
+                 LIMIT 1",
+            )?
+            .into_iter()
+            .next())
+    }
+
+    fn set_timestamp_column(&self, id: i64, column: &str, value: Option<&str>) -> Result<Timebox, String> {
+        self.conn
+            .execute(
+                &format!("UPDATE timeboxes SET {} = ?1, updated_at = ?2 WHERE id = ?3", column),
+                params![value, now(), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    /// Queues overtime events for every running timebox that is past its time, not
+    /// just the current one. Repeats are skipped.
+    pub fn notify_overtime_all(&self) -> Result<(), String> {
+        for timebox in self.active()? {
+            if timebox.timebox.status == TimeboxStatus::InProgress && timebox.remaining_seconds() <= 0 {
+                self.notify_overtime(timebox.timebox.id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Queues the overtime event for webhooks and shell hooks, once per timebox.
+    /// Overtime has no user action behind it, so this is called by a poller.
+    fn notify_overtime(&self, id: i64) {
+        if let Err(e) = WebhookService::new(self.conn).enqueue_once(TimeboxEvent::Overtime, id) {
+            eprintln!("Failed to queue overtime webhooks for timebox {}: {}", id, e);
+        }
+        if let Err(e) = ShellHookService::new(self.conn).enqueue_once(TimeboxEvent::Overtime, id) {
+            eprintln!("Failed to queue overtime shell hooks for timebox {}: {}", id, e);
+        }
+    }
+
+    /// Hands the timebox, with its sessions, to the connected integrations and queues
+    /// the side effects they ask for in the outbox
+    fn queue_integration_actions(&self, hook: ProviderHook, timebox: &Timebox) -> Result<(), String> {
+        let timebox = self.with_sessions(timebox.clone())?;
+        run_provider_hooks(self.conn, hook, &timebox);
+        Ok(())
+    }
+
+    /// Queues webhook deliveries and shell hook runs. Failing to queue never fails the action itself.
+    fn notify_event(&self, event: TimeboxEvent, id: i64) {
+        if let Err(e) = WebhookService::new(self.conn).enqueue(event, id) {
+            eprintln!("Failed to queue {} webhooks for timebox {}: {}", event.as_str(), id, e);
+        }
+        if let Err(e) = ShellHookService::new(self.conn).enqueue(event, id) {
+            eprintln!("Failed to queue {} shell hooks for timebox {}: {}", event.as_str(), id, e);
+        }
+    }
+
+    fn list_with_sessions(&self, filter: &str) -> Result<Vec<TimeboxWithSessions>, String> {
+        let mut timebox_stmt = self
+            .conn
+            .prepare(&format!("SELECT {} FROM timeboxes WHERE {}", TIMEBOX_SELECT_COLUMNS, filter))
+            .map_err(|e| e.to_string())?;
+
+        let timeboxes: Vec<Timebox> = timebox_stmt
+            .query_map([], Timebox::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        timeboxes.into_iter().map(|timebox| self.with_sessions(timebox)).collect()
+    }
+
+    fn with_sessions(&self, timebox: Timebox) -> Result<TimeboxWithSessions, String> {
+        let sessions = SessionService::new(self.conn);
+
+        Ok(TimeboxWithSessions {
+            sessions: sessions.for_timebox(timebox.id)?,
+            actual_duration: sessions.actual_duration(timebox.id)?,
+            timebox,
+        })
+    }
+}
+
+pub(crate) fn now() -> String {
+    Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
@@ -0,0 +1,359 @@
+use super::{ReorderTimeboxRequest, TimeboxService};
+use crate::models::{CreateTimeboxRequest, TimeboxStatus, UpdateTimeboxRequest};
+use crate::test_support::{test_db, timebox_request};
+
+fn request(intention: &str, minutes: i64) -> CreateTimeboxRequest {
+    CreateTimeboxRequest {
+        intention: intention.to_string(),
+        intended_duration: minutes,
+        ..timebox_request()
+    }
+}
+
+#[test]
+fn test_create_timebox() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+
+    let mut req = request("Write RFC", 45);
+    req.notes = Some("Outline first".to_string());
+    req.tags = vec!["writing".to_string()];
+    let timebox = service.create(&req).unwrap();
+
+    assert_eq!(timebox.intention, "Write RFC");
+    assert_eq!(timebox.intended_duration, 45);
+    assert_eq!(timebox.notes.as_deref(), Some("Outline first"));
+    assert_eq!(timebox.tags, vec!["writing".to_string()]);
+    assert_eq!(timebox.status, TimeboxStatus::NotStarted);
+    assert!(timebox.started_at.is_none());
+}
+
+#[test]
+fn test_get_missing_timebox_is_an_error() {
+    let conn = test_db();
+    assert!(TimeboxService::new(&conn).get(42).is_err());
+}
+
+#[test]
+fn test_start_opens_a_session() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    let timebox = service.start(id).unwrap();
+
+    assert_eq!(timebox.status, TimeboxStatus::InProgress);
+    assert!(timebox.started_at.is_some());
+
+    let active = service.active().unwrap();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].sessions.len(), 1);
+    assert!(active[0].sessions[0].stopped_at.is_none());
+}
+
+#[test]
+fn test_pause_and_resume() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    let started = service.start(id).unwrap();
+    let paused = service.pause(id).unwrap();
+    assert_eq!(paused.status, TimeboxStatus::Paused);
+
+    // Paused timeboxes are still active
+    let active = service.active().unwrap();
+    assert_eq!(active.len(), 1);
+    assert!(active[0].sessions.iter().all(|s| s.stopped_at.is_some()));
+
+    let resumed = service.start(id).unwrap();
+    assert_eq!(resumed.status, TimeboxStatus::InProgress);
+    assert_eq!(resumed.started_at, started.started_at);
+    assert_eq!(service.active().unwrap()[0].sessions.len(), 2);
+}
+
+#[test]
+fn test_stop_and_restart() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    service.start(id).unwrap();
+    let stopped = service.stop(id).unwrap();
+
+    assert_eq!(stopped.status, TimeboxStatus::Stopped);
+    assert!(stopped.completed_at.is_some());
+    assert!(service.active().unwrap().is_empty());
+
+    // Restarting clears completed_at so it shows up as active again
+    let restarted = service.start(id).unwrap();
+    assert!(restarted.completed_at.is_none());
+    assert_eq!(service.active().unwrap().len(), 1);
+}
+
+#[test]
+fn test_double_start_is_rejected() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    service.start(id).unwrap();
+    assert!(service.start(id).is_err());
+    assert_eq!(service.get_with_sessions(id).unwrap().sessions.len(), 1);
+
+    // Finished and cancelled timeboxes stay ended
+    service.finish(id).unwrap();
+    assert!(service.start(id).is_err());
+    let cancelled = service.create(&request("Other", 25)).unwrap().id;
+    service.cancel(cancelled).unwrap();
+    assert!(service.start(cancelled).is_err());
+    assert_eq!(service.get(cancelled).unwrap().status, TimeboxStatus::Cancelled);
+}
+
+#[test]
+fn test_ended_timeboxes_cant_be_stopped_or_finished() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    service.start(id).unwrap();
+    // Finishing after a stop is fine
+    service.stop(id).unwrap();
+    let finished = service.finish(id).unwrap();
+    assert!(service.finish(id).is_err());
+    assert!(service.stop(id).is_err());
+    assert_eq!(service.get(id).unwrap().finished_at, finished.finished_at);
+
+    let cancelled = service.create(&request("Other", 25)).unwrap().id;
+    service.cancel(cancelled).unwrap();
+    assert!(service.finish(cancelled).is_err());
+    assert!(service.stop(cancelled).is_err());
+    assert_eq!(service.get(cancelled).unwrap().status, TimeboxStatus::Cancelled);
+}
+
+#[test]
+fn test_finish() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    service.start(id).unwrap();
+    let finished = service.finish(id).unwrap();
+
+    assert_eq!(finished.status, TimeboxStatus::Completed);
+    assert!(finished.finished_at.is_some());
+    assert_eq!(finished.finished_at, finished.completed_at);
+    assert!(service.active().unwrap().is_empty());
+}
+
+#[test]
+fn test_stop_after_time() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    service.start(id).unwrap();
+    let timebox = service.stop_after_time(id).unwrap();
+
+    assert_eq!(timebox.status, TimeboxStatus::Completed);
+    assert!(timebox.after_time_stopped_at.is_some());
+    assert!(timebox.finished_at.is_none());
+}
+
+#[test]
+fn test_auto_stop_marks_sessions() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    service.start(id).unwrap();
+    let timebox = service.auto_stop(id).unwrap();
+    assert_eq!(timebox.status, TimeboxStatus::Stopped);
+
+    let today = service.today().unwrap();
+    let session = &today[0].sessions[0];
+    assert!(session.stopped_at.is_some());
+    assert_eq!(session.auto_stopped_at, session.stopped_at);
+}
+
+#[test]
+fn test_cancel_excludes_session_from_duration() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    service.start(id).unwrap();
+    conn.execute(
+        "UPDATE sessions SET started_at = datetime('now', 'localtime', '-10 minutes') WHERE timebox_id = ?1",
+        [id],
+    )
+    .unwrap();
+
+    let timebox = service.cancel(id).unwrap();
+    assert_eq!(timebox.status, TimeboxStatus::Cancelled);
+
+    let today = service.today().unwrap();
+    assert!(today[0].sessions[0].cancelled_at.is_some());
+    assert_eq!(today[0].actual_duration, 0.0);
+}
+
+#[test]
+fn test_actual_duration_counts_stopped_sessions() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    conn.execute(
+        "INSERT INTO sessions (timebox_id, started_at, stopped_at) VALUES (?1, '2025-01-01 10:00:00', '2025-01-01 10:15:00')",
+        [id],
+    )
+    .unwrap();
+
+    let today = service.today().unwrap();
+    assert!((today[0].actual_duration - 900.0).abs() < 1.0);
+}
+
+#[test]
+fn test_update_logs_only_changed_fields() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Old title", 25)).unwrap().id;
+
+    let updated = service
+        .update(
+            id,
+            &UpdateTimeboxRequest {
+                intention: Some("New title".to_string()),
+                notes: None,
+                intended_duration: Some(25),
+            },
+        )
+        .unwrap();
+    assert_eq!(updated.intention, "New title");
+
+    let log = service.change_log(id).unwrap();
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].previous_intention_title.as_deref(), Some("Old title"));
+    assert_eq!(log[0].updated_intention_title.as_deref(), Some("New title"));
+    assert!(log[0].previous_intended_duration.is_none());
+
+    // Nothing changed, nothing logged
+    service
+        .update(id, &UpdateTimeboxRequest { intention: None, notes: None, intended_duration: None })
+        .unwrap();
+    assert_eq!(service.change_log(id).unwrap().len(), 1);
+}
+
+#[test]
+fn test_update_deleted_timebox_fails() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    service.delete(id).unwrap();
+    let result = service.update(id, &UpdateTimeboxRequest { intention: Some("x".to_string()), notes: None, intended_duration: None });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_delete_and_archive_hide_from_today() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let deleted = service.create(&request("Deleted", 25)).unwrap().id;
+    let archived = service.create(&request("Archived", 25)).unwrap().id;
+    service.create(&request("Kept", 25)).unwrap();
+
+    assert!(service.delete(deleted).unwrap().deleted_at.is_some());
+    assert!(service.archive(archived).unwrap().archived_at.is_some());
+
+    let today: Vec<String> = service.today().unwrap().into_iter().map(|t| t.timebox.intention).collect();
+    assert_eq!(today, vec!["Kept".to_string()]);
+
+    let archived_list = service.archived().unwrap();
+    assert_eq!(archived_list.len(), 1);
+    assert_eq!(archived_list[0].timebox.id, archived);
+
+    assert!(service.unarchive(archived).unwrap().archived_at.is_none());
+    assert_eq!(service.today().unwrap().len(), 2);
+}
+
+#[test]
+fn test_reorder() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let first = service.create(&request("First", 25)).unwrap().id;
+    let second = service.create(&request("Second", 25)).unwrap().id;
+
+    service
+        .reorder(&[
+            ReorderTimeboxRequest { id: first, display_order: 1 },
+            ReorderTimeboxRequest { id: second, display_order: 0 },
+        ])
+        .unwrap();
+
+    let ids: Vec<i64> = service.today().unwrap().into_iter().map(|t| t.timebox.id).collect();
+    assert_eq!(ids, vec![second, first]);
+}
+
+#[test]
+fn test_current_prefers_in_progress() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    assert!(service.current().unwrap().is_none());
+
+    let paused = service.create(&request("Paused", 25)).unwrap().id;
+    let running = service.create(&request("Running", 25)).unwrap().id;
+    service.start(paused).unwrap();
+    service.pause(paused).unwrap();
+    assert_eq!(service.current().unwrap().unwrap().timebox.id, paused);
+
+    service.start(running).unwrap();
+    assert_eq!(service.current().unwrap().unwrap().timebox.id, running);
+
+    service.finish(running).unwrap();
+    assert_eq!(service.current().unwrap().unwrap().timebox.id, paused);
+}
+
+#[test]
+fn test_resumable_skips_running() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let stopped = service.create(&request("Stopped", 25)).unwrap().id;
+    let paused = service.create(&request("Paused", 25)).unwrap().id;
+    let running = service.create(&request("Running", 25)).unwrap().id;
+
+    service.start(stopped).unwrap();
+    service.stop(stopped).unwrap();
+    service.start(running).unwrap();
+    assert_eq!(service.resumable().unwrap().unwrap().timebox.id, stopped);
+
+    service.start(paused).unwrap();
+    service.pause(paused).unwrap();
+    assert_eq!(service.resumable().unwrap().unwrap().timebox.id, paused);
+}
+
+#[test]
+fn test_set_linear_issue_and_project() {
+    let conn = test_db();
+    let service = TimeboxService::new(&conn);
+    let id = service.create(&request("Task", 25)).unwrap().id;
+
+    let timebox = service
+        .set_linear_issue(id, "issue-uuid", "ENG-1", "https://linear.app/acme/issue/ENG-1")
+        .unwrap();
+    assert_eq!(timebox.linear_issue_id.as_deref(), Some("issue-uuid"));
+    assert_eq!(timebox.linear_issue_identifier.as_deref(), Some("ENG-1"));
+    assert_eq!(timebox.linear_issue_url.as_deref(), Some("https://linear.app/acme/issue/ENG-1"));
+
+    // Relinking replaces the identifier and forgets the old link's check
+    conn.execute("UPDATE timeboxes SET linear_issue_link_status = 'deleted' WHERE id = ?1", [id])
+        .unwrap();
+    let timebox = service
+        .set_linear_issue(id, "issue-uuid-2", "ENG-2", "https://linear.app/acme/issue/ENG-2")
+        .unwrap();
+    assert_eq!(timebox.linear_issue_identifier.as_deref(), Some("ENG-2"));
+    assert!(timebox.linear_issue_link_status.is_none());
+
+    let timebox = service.set_linear_project(id, None).unwrap();
+    assert!(timebox.linear_project_id.is_none());
+}
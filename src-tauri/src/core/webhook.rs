@@ -0,0 +1,285 @@
+use super::event::TimeboxEvent;
+use super::timebox::{now, TimeboxService};
+use crate::models::{Integration, WebhookDelivery, WebhookDeliveryStatus};
+use chrono::{Duration, Local, NaiveDateTime};
+use hmac::{Hmac, Mac};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+
+pub const WEBHOOK_INTEGRATION_TYPE: &str = "webhook";
+
+/// Deliveries are given up after this many attempts
+pub const WEBHOOK_MAX_ATTEMPTS: i64 = 6;
+
+const WEBHOOK_DELIVERY_SELECT_COLUMNS: &str = "id, integration_id, timebox_id, event, url, payload, status, attempts, response_status, last_error, next_attempt_at, delivered_at, created_at, updated_at";
+
+// Longest response body kept in the delivery log
+const MAX_ERROR_LENGTH: usize = 500;
+
+/// `connection_config` of a webhook integration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Signs each body with HMAC-SHA256 when set
+    #[serde(default)]
+    pub secret: Option<String>,
+    pub events: Vec<TimeboxEvent>,
+}
+
+impl WebhookConfig {
+    /// Reads and validates a config, e.g. before saving an integration
+    pub fn from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let config: WebhookConfig =
+            serde_json::from_value(value.clone()).map_err(|e| format!("Invalid webhook config: {}", e))?;
+
+        let url = url::Url::parse(config.url.trim()).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err("Webhook URL must start with http:// or https://".to_string());
+        }
+        if config.events.is_empty() {
+            return Err("Subscribe the webhook to at least one event".to_string());
+        }
+
+        Ok(WebhookConfig {
+            url: url.to_string(),
+            secret: config.secret.filter(|s| !s.trim().is_empty()),
+            events: config.events,
+        })
+    }
+
+    pub fn subscribes_to(&self, event: TimeboxEvent) -> bool {
+        self.events.contains(&event)
+    }
+}
+
+/// `sha256=<hex>` signature of a body, sent in the X-Timeboxd-Signature header
+pub fn sign_webhook_payload(secret: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Seconds to wait before retrying after `attempts` failed attempts: 30s, 1m, 2m, ... capped at an hour
+pub fn webhook_backoff_seconds(attempts: i64) -> i64 {
+    let exponent = attempts.clamp(1, 8) - 1;
+    (30 * 2i64.pow(exponent as u32)).min(3600)
+}
+
+pub struct WebhookService<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> WebhookService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        WebhookService { conn }
+    }
+
+    /// Webhook integrations with a valid config, invalid ones are skipped
+    pub fn webhooks(&self) -> Result<Vec<(Integration, WebhookConfig)>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, connection_name, integration_type, connection_config, created_at, updated_at FROM integrations WHERE integration_type = ?1",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let integrations: Vec<Integration> = stmt
+            .query_map(params![WEBHOOK_INTEGRATION_TYPE], Integration::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(integrations
+            .into_iter()
+            .filter_map(|i| WebhookConfig::from_value(&i.connection_config).ok().map(|c| (i, c)))
+            .collect())
+    }
+
+    /// Queues a delivery to every webhook subscribed to the event, returning how many were queued
+    pub fn enqueue(&self, event: TimeboxEvent, timebox_id: i64) -> Result<usize, String> {
+        self.enqueue_where(event, timebox_id, |_| Ok(true))
+    }
+
+    /// Like `enqueue`, but skips webhooks that already got this event for the timebox.
+    /// Used for overtime, which is detected by polling.
+    pub fn enqueue_once(&self, event: TimeboxEvent, timebox_id: i64) -> Result<usize, String> {
+        self.enqueue_where(event, timebox_id, |integration_id| {
+            let count: i64 = self
+                .conn
+                .query_row(
+                    "SELECT COUNT(*) FROM webhook_deliveries WHERE integration_id = ?1 AND timebox_id = ?2 AND event = ?3",
+                    params![integration_id, timebox_id, event.as_str()],
+                    |row| row.get(0),
+                )
+                .map_err(|e| e.to_string())?;
+            Ok(count == 0)
+        })
+    }
+
+    fn enqueue_where(
+        &self,
+        event: TimeboxEvent,
+        timebox_id: i64,
+        should_send: impl Fn(i64) -> Result<bool, String>,
+    ) -> Result<usize, String> {
+        let webhooks = self.webhooks()?;
+        let mut queued = 0;
+        let mut payload = None;
+
+        for (integration, config) in webhooks {
+            if !config.subscribes_to(event) || !should_send(integration.id)? {
+                continue;
+            }
+
+            // Built lazily so timeboxes without subscribed webhooks cost a single query
+            if payload.is_none() {
+                payload = Some(self.payload(event, timebox_id)?);
+            }
+
+            let now = now();
+            self.conn
+                .execute(
+                    "INSERT INTO webhook_deliveries (integration_id, timebox_id, event, url, payload, status, next_attempt_at, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7, ?7)",
+                    params![
+                        integration.id,
+                        timebox_id,
+                        event.as_str(),
+                        config.url,
+                        payload,
+                        WebhookDeliveryStatus::Pending.as_str(),
+                        now
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+            queued += 1;
+        }
+
+        Ok(queued)
+    }
+
+    /// JSON body sent for an event: the timebox with its sessions as they are right now
+    fn payload(&self, event: TimeboxEvent, timebox_id: i64) -> Result<String, String> {
+        let timebox = TimeboxService::new(self.conn).get_with_sessions(timebox_id)?;
+
+        Ok(json!({
+            "event": event.as_str(),
+            "occurred_at": now(),
+            "timebox": timebox.timebox,
+            "sessions": timebox.sessions,
+            "actual_duration": timebox.actual_duration,
+        })
+        .to_string())
+    }
+
+    pub fn get(&self, id: i64) -> Result<WebhookDelivery, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM webhook_deliveries WHERE id = ?1", WEBHOOK_DELIVERY_SELECT_COLUMNS),
+                params![id],
+                WebhookDelivery::from_row,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    /// Pending deliveries whose next attempt is due, oldest first
+    pub fn due(&self, limit: i64) -> Result<Vec<WebhookDelivery>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM webhook_deliveries WHERE status = ?1 AND next_attempt_at <= ?2 ORDER BY next_attempt_at, id LIMIT ?3",
+                WEBHOOK_DELIVERY_SELECT_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let deliveries = stmt
+            .query_map(params![WebhookDeliveryStatus::Pending.as_str(), now(), limit], WebhookDelivery::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(deliveries)
+    }
+
+    /// Most recent deliveries first, optionally for a single integration
+    pub fn deliveries(&self, integration_id: Option<i64>, limit: i64) -> Result<Vec<WebhookDelivery>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM webhook_deliveries WHERE ?1 IS NULL OR integration_id = ?1 ORDER BY created_at DESC, id DESC LIMIT ?2",
+                WEBHOOK_DELIVERY_SELECT_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let deliveries = stmt
+            .query_map(params![integration_id, limit], WebhookDelivery::from_row)
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(deliveries)
+    }
+
+    /// The secret to sign a delivery with, read at send time so it never lands in the log
+    pub fn secret_for(&self, integration_id: i64) -> Result<Option<String>, String> {
+        Ok(self
+            .webhooks()?
+            .into_iter()
+            .find(|(integration, _)| integration.id == integration_id)
+            .and_then(|(_, config)| config.secret))
+    }
+
+    pub fn record_success(&self, id: i64, response_status: u16) -> Result<WebhookDelivery, String> {
+        let now = now();
+        self.conn
+            .execute(
+                "UPDATE webhook_deliveries SET status = ?1, attempts = attempts + 1, response_status = ?2, last_error = NULL, next_attempt_at = NULL, delivered_at = ?3, updated_at = ?3 WHERE id = ?4",
+                params![WebhookDeliveryStatus::Delivered.as_str(), response_status, now, id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    /// Schedules the next attempt with backoff, or marks the delivery failed once attempts run out
+    pub fn record_failure(&self, id: i64, response_status: Option<u16>, error: &str) -> Result<WebhookDelivery, String> {
+        let delivery = self.get(id)?;
+        let attempts = delivery.attempts + 1;
+        let now = Local::now().naive_local();
+        let error: String = error.chars().take(MAX_ERROR_LENGTH).collect();
+
+        let (status, next_attempt_at) = if attempts >= WEBHOOK_MAX_ATTEMPTS {
+            (WebhookDeliveryStatus::Failed, None)
+        } else {
+            let next = now + Duration::seconds(webhook_backoff_seconds(attempts));
+            (WebhookDeliveryStatus::Pending, Some(format_timestamp(next)))
+        };
+
+        self.conn
+            .execute(
+                "UPDATE webhook_deliveries SET status = ?1, attempts = ?2, response_status = ?3, last_error = ?4, next_attempt_at = ?5, updated_at = ?6 WHERE id = ?7",
+                params![status.as_str(), attempts, response_status, error, next_attempt_at, format_timestamp(now), id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+
+    /// Sends a delivery again on the next worker run, with a fresh set of attempts
+    pub fn retry(&self, id: i64) -> Result<WebhookDelivery, String> {
+        let now = now();
+        self.conn
+            .execute(
+                "UPDATE webhook_deliveries SET status = ?1, attempts = 0, next_attempt_at = ?2, updated_at = ?2 WHERE id = ?3",
+                params![WebhookDeliveryStatus::Pending.as_str(), now, id],
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.get(id)
+    }
+}
+
+fn format_timestamp(time: NaiveDateTime) -> String {
+    time.format("%Y-%m-%d %H:%M:%S").to_string()
+}
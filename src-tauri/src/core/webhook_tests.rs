@@ -0,0 +1,172 @@
+use super::{
+    sign_webhook_payload, webhook_backoff_seconds, TimeboxEvent, TimeboxService, WebhookConfig, WebhookService,
+    WEBHOOK_MAX_ATTEMPTS,
+};
+use crate::models::WebhookDeliveryStatus;
+use crate::test_support::{add_integration, create_timebox, test_db};
+use rusqlite::{params, Connection};
+use serde_json::json;
+
+fn add_webhook(conn: &Connection, config: serde_json::Value) -> i64 {
+    add_integration(conn, "webhook", config)
+}
+
+#[test]
+fn test_config_validation() {
+    let config = WebhookConfig::from_value(&json!({
+        "url": "https://example.com/hook",
+        "secret": "  ",
+        "events": ["started", "auto_stopped"]
+    }))
+    .unwrap();
+    assert_eq!(config.secret, None);
+    assert!(config.subscribes_to(TimeboxEvent::AutoStopped));
+    assert!(!config.subscribes_to(TimeboxEvent::Finished));
+
+    assert!(WebhookConfig::from_value(&json!({ "url": "ftp://example.com", "events": ["started"] })).is_err());
+    assert!(WebhookConfig::from_value(&json!({ "url": "not a url", "events": ["started"] })).is_err());
+    assert!(WebhookConfig::from_value(&json!({ "url": "https://example.com", "events": [] })).is_err());
+    assert!(WebhookConfig::from_value(&json!({ "url": "https://example.com", "events": ["exploded"] })).is_err());
+}
+
+#[test]
+fn test_lifecycle_queues_subscribed_events_only() {
+    let conn = test_db();
+    let hook = add_webhook(&conn, json!({ "url": "https://example.com/hook", "events": ["started", "finished"] }));
+    let service = TimeboxService::new(&conn);
+
+    let id = create_timebox(&conn);
+    service.start(id).unwrap();
+    service.pause(id).unwrap();
+    service.start(id).unwrap();
+    service.finish(id).unwrap();
+
+    let deliveries = WebhookService::new(&conn).deliveries(Some(hook), 50).unwrap();
+    let events: Vec<&str> = deliveries.iter().rev().map(|d| d.event.as_str()).collect();
+    assert_eq!(events, vec!["started", "started", "finished"]);
+
+    let delivery = &deliveries[0];
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(delivery.url, "https://example.com/hook");
+    assert_eq!(delivery.timebox_id, Some(id));
+
+    let payload: serde_json::Value = serde_json::from_str(&delivery.payload).unwrap();
+    assert_eq!(payload["event"], "finished");
+    assert_eq!(payload["timebox"]["intention"], "Write RFC");
+    assert_eq!(payload["timebox"]["status"], "completed");
+    assert_eq!(payload["sessions"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_no_webhooks_queues_nothing() {
+    let conn = test_db();
+    let id = create_timebox(&conn);
+    TimeboxService::new(&conn).start(id).unwrap();
+
+    assert!(WebhookService::new(&conn).deliveries(None, 50).unwrap().is_empty());
+}
+
+#[test]
+fn test_enqueue_once_skips_repeats() {
+    let conn = test_db();
+    add_webhook(&conn, json!({ "url": "https://example.com/hook", "events": ["overtime"] }));
+    let id = create_timebox(&conn);
+    let webhooks = WebhookService::new(&conn);
+
+    assert_eq!(webhooks.enqueue_once(TimeboxEvent::Overtime, id).unwrap(), 1);
+    assert_eq!(webhooks.enqueue_once(TimeboxEvent::Overtime, id).unwrap(), 0);
+}
+
+#[test]
+fn test_overtime_is_queued_for_every_running_timebox() {
+    let conn = test_db();
+    let hook = add_webhook(&conn, json!({ "url": "https://example.com/hook", "events": ["overtime"] }));
+    let service = TimeboxService::new(&conn);
+
+    let first = create_timebox(&conn);
+    let second = create_timebox(&conn);
+    let on_time = create_timebox(&conn);
+    for id in [first, second, on_time] {
+        service.start(id).unwrap();
+    }
+    // Both 25 minute timeboxes started half an hour ago
+    conn.execute(
+        "UPDATE sessions SET started_at = datetime('now', 'localtime', '-30 minutes') WHERE timebox_id IN (?1, ?2)",
+        params![first, second],
+    )
+    .unwrap();
+
+    service.notify_overtime_all().unwrap();
+    service.notify_overtime_all().unwrap();
+
+    let mut overtime: Vec<i64> = WebhookService::new(&conn)
+        .deliveries(Some(hook), 50)
+        .unwrap()
+        .iter()
+        .filter_map(|d| d.timebox_id)
+        .collect();
+    overtime.sort();
+    assert_eq!(overtime, vec![first, second]);
+}
+
+#[test]
+fn test_failures_back_off_then_give_up() {
+    let conn = test_db();
+    add_webhook(&conn, json!({ "url": "https://example.com/hook", "events": ["created"] }));
+    create_timebox(&conn);
+    let webhooks = WebhookService::new(&conn);
+
+    let delivery = webhooks.due(10).unwrap().remove(0);
+
+    let retried = webhooks.record_failure(delivery.id, Some(500), "Internal Server Error").unwrap();
+    assert_eq!(retried.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(retried.attempts, 1);
+    assert_eq!(retried.response_status, Some(500));
+    // Not due again until the backoff has passed
+    assert!(webhooks.due(10).unwrap().is_empty());
+
+    for _ in 1..WEBHOOK_MAX_ATTEMPTS {
+        webhooks.record_failure(delivery.id, None, "Connection refused").unwrap();
+    }
+    let failed = webhooks.get(delivery.id).unwrap();
+    assert_eq!(failed.status, WebhookDeliveryStatus::Failed);
+    assert_eq!(failed.next_attempt_at, None);
+
+    let retried = webhooks.retry(delivery.id).unwrap();
+    assert_eq!(retried.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(retried.attempts, 0);
+    assert_eq!(webhooks.due(10).unwrap().len(), 1);
+
+    let delivered = webhooks.record_success(delivery.id, 204).unwrap();
+    assert_eq!(delivered.status, WebhookDeliveryStatus::Delivered);
+    assert!(delivered.delivered_at.is_some());
+    assert!(webhooks.due(10).unwrap().is_empty());
+}
+
+#[test]
+fn test_secret_for_webhook() {
+    let conn = test_db();
+    let signed = add_webhook(&conn, json!({ "url": "https://a.example", "secret": "s3cret", "events": ["created"] }));
+    let unsigned = add_webhook(&conn, json!({ "url": "https://b.example", "events": ["created"] }));
+    let webhooks = WebhookService::new(&conn);
+
+    assert_eq!(webhooks.secret_for(signed).unwrap().as_deref(), Some("s3cret"));
+    assert_eq!(webhooks.secret_for(unsigned).unwrap(), None);
+}
+
+#[test]
+fn test_backoff_grows_and_caps() {
+    assert_eq!(webhook_backoff_seconds(1), 30);
+    assert_eq!(webhook_backoff_seconds(2), 60);
+    assert_eq!(webhook_backoff_seconds(3), 120);
+    assert_eq!(webhook_backoff_seconds(20), 3600);
+}
+
+#[test]
+fn test_signature_matches_known_hmac() {
+    // RFC 4231 test case 2
+    assert_eq!(
+        sign_webhook_payload("Jefe", "what do ya want for nothing?"),
+        "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
@@ -1,17 +1,38 @@
 use rusqlite::Connection;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
 
+// Must match "identifier" in tauri.conf.json so the CLI finds the app's database
+const APP_IDENTIFIER: &str = "com.genesis.timeboxd";
+const DB_FILE_NAME: &str = "timeboxd.db";
+
 pub fn get_db_path(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
     let app_dir = app_handle.path().app_data_dir()?;
     fs::create_dir_all(&app_dir)?;
-    Ok(app_dir.join("timeboxd.db"))
+    Ok(app_dir.join(DB_FILE_NAME))
+}
+
+/// Same location as `get_db_path` without a running app, for the CLI.
+pub fn default_db_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let app_dir = dirs::data_dir()
+        .ok_or("Could not determine the data directory")?
+        .join(APP_IDENTIFIER);
+    fs::create_dir_all(&app_dir)?;
+    Ok(app_dir.join(DB_FILE_NAME))
 }
 
 pub fn initialize_database(app_handle: &AppHandle) -> Result<Connection, Box<dyn std::error::Error>> {
-    let db_path = get_db_path(app_handle)?;
-    let conn = Connection::open(db_path)?;
+    open_database(&get_db_path(app_handle)?)
+}
+
+/// Opens the database at `path` and runs any pending migrations.
+pub fn open_database(path: &Path) -> Result<Connection, Box<dyn std::error::Error>> {
+    let conn = Connection::open(path)?;
+
+    // The app and the CLI can have the database open at the same time
+    conn.busy_timeout(Duration::from_secs(5))?;
 
     // Enable foreign keys
     conn.execute("PRAGMA foreign_keys = ON", [])?;
@@ -21,7 +42,7 @@ pub fn initialize_database(app_handle: &AppHandle) -> Result<Connection, Box<dyn
     Ok(conn)
 }
 
-fn run_migrations(conn: &Connection) -> Result<(), rusqlite::Error> {
+pub(crate) fn run_migrations(conn: &Connection) -> Result<(), rusqlite::Error> {
     let version: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
 
     if version < 3 {
@@ -211,5 +232,227 @@ fn run_migrations(conn: &Connection) -> Result<(), rusqlite::Error> {
         "#)?;
     }
 
+    // Migration 11: Default global keyboard shortcuts (an empty value disables a shortcut)
+    if version < 11 {
+        conn.execute_batch(r#"
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('shortcut_toggle_pause', 'CommandOrControl+Alt+Shift+Space');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('shortcut_finish_current', 'CommandOrControl+Alt+Shift+F');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('shortcut_quick_create', 'CommandOrControl+Alt+Shift+N');
+
+            PRAGMA user_version = 11;
+        "#)?;
+    }
+
+    // Migration 12: Add tags to timeboxes (JSON array of strings, e.g. from quick capture "#writing")
+    if version < 12 {
+        conn.execute_batch(r#"
+            ALTER TABLE timeboxes ADD COLUMN tags TEXT;
+
+            PRAGMA user_version = 12;
+        "#)?;
+    }
+
+    // Migration 13: Duration presets shown in the timebox form, parsed with the duration parser
+    if version < 13 {
+        conn.execute_batch(r#"
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('duration_presets', '5m,15m,45m');
+
+            PRAGMA user_version = 13;
+        "#)?;
+    }
+
+    // Migration 14: Local HTTP API, off by default (the token is generated on first read)
+    if version < 14 {
+        conn.execute_batch(r#"
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('http_api_enabled', 'false');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('http_api_port', '7523');
+
+            PRAGMA user_version = 14;
+        "#)?;
+    }
+
+    // Migration 15: Delivery log and retry queue for webhook integrations
+    if version < 15 {
+        conn.execute_batch(r#"
+            CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+                integration_id      INTEGER NOT NULL REFERENCES integrations(id) ON DELETE CASCADE,
+                timebox_id          INTEGER REFERENCES timeboxes(id) ON DELETE SET NULL,
+                event               TEXT NOT NULL,
+                url                 TEXT NOT NULL,
+                payload             TEXT NOT NULL,
+                status              TEXT NOT NULL DEFAULT 'pending',
+                attempts            INTEGER NOT NULL DEFAULT 0,
+                response_status     INTEGER,
+                last_error          TEXT,
+                next_attempt_at     TEXT,
+                delivered_at        TEXT,
+                created_at          TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+                updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(status, next_attempt_at);
+            CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_integration ON webhook_deliveries(integration_id, created_at);
+
+            PRAGMA user_version = 15;
+        "#)?;
+    }
+
+    // Migration 16: Local shell-command hooks, off by default
+    if version < 16 {
+        conn.execute_batch(r#"
+            CREATE TABLE IF NOT EXISTS shell_hooks (
+                id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+                event               TEXT NOT NULL,
+                command             TEXT NOT NULL,
+                timeout_seconds     INTEGER NOT NULL DEFAULT 10,
+                enabled             INTEGER NOT NULL DEFAULT 1,
+                created_at          TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+                updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+            );
+
+            CREATE TABLE IF NOT EXISTS shell_hook_runs (
+                id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+                hook_id             INTEGER NOT NULL REFERENCES shell_hooks(id) ON DELETE CASCADE,
+                timebox_id          INTEGER REFERENCES timeboxes(id) ON DELETE SET NULL,
+                event               TEXT NOT NULL,
+                command             TEXT NOT NULL,
+                env                 TEXT NOT NULL,
+                status              TEXT NOT NULL DEFAULT 'pending',
+                exit_code           INTEGER,
+                stdout              TEXT,
+                stderr              TEXT,
+                started_at          TEXT,
+                finished_at         TEXT,
+                created_at          TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_shell_hook_runs_status ON shell_hook_runs(status, created_at);
+
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('shell_hooks_enabled', 'false');
+
+            PRAGMA user_version = 16;
+        "#)?;
+    }
+
+    // Migration 17: Outbox for integration side effects (Todoist tasks, Linear updates)
+    if version < 17 {
+        conn.execute_batch(r#"
+            CREATE TABLE IF NOT EXISTS integration_outbox (
+                id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+                integration_id      INTEGER NOT NULL REFERENCES integrations(id) ON DELETE CASCADE,
+                timebox_id          INTEGER REFERENCES timeboxes(id) ON DELETE SET NULL,
+                action              TEXT NOT NULL,
+                payload             TEXT NOT NULL,
+                status              TEXT NOT NULL DEFAULT 'pending',
+                attempts            INTEGER NOT NULL DEFAULT 0,
+                last_error          TEXT,
+                next_attempt_at     TEXT,
+                delivered_at        TEXT,
+                created_at          TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+                updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+                dedupe_key          TEXT
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_integration_outbox_due ON integration_outbox(status, next_attempt_at);
+
+            -- Actions with a dedupe key (e.g. a Linear comment) are queued once
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_integration_outbox_dedupe ON integration_outbox(integration_id, dedupe_key);
+
+            PRAGMA user_version = 17;
+        "#)?;
+    }
+
+    // Migration 18: Linear state transition rules per saved project, and a cache of
+    // each team's workflow states to resolve the rules' state names offline
+    if version < 18 {
+        conn.execute_batch(r#"
+            CREATE TABLE IF NOT EXISTS linear_team_states (
+                linear_state_id TEXT PRIMARY KEY,
+                linear_team_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                state_type TEXT NOT NULL,
+                fetched_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_linear_team_states_team ON linear_team_states(linear_team_id);
+
+            CREATE TABLE IF NOT EXISTS linear_state_rules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                linear_project_id INTEGER NOT NULL REFERENCES linear_projects(id) ON DELETE CASCADE,
+                event TEXT NOT NULL CHECK (event IN ('started', 'stopped', 'finished')),
+                state_name TEXT NOT NULL,
+                state_type TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                UNIQUE(linear_project_id, event)
+            );
+
+            -- Starting a timebox used to move its issue to the first started state from the UI,
+            -- so existing projects get a rule that keeps doing that. The name is a placeholder
+            -- until the team's states are cached.
+            INSERT INTO linear_state_rules (linear_project_id, event, state_name, state_type, created_at, updated_at)
+            SELECT id, 'started', 'In Progress', 'started', datetime('now', 'localtime'), datetime('now', 'localtime')
+            FROM linear_projects WHERE deleted_at IS NULL;
+
+            PRAGMA user_version = 18;
+        "#)?;
+    }
+
+    // Migration 19: Local copy of Linear project issues, synced incrementally by updatedAt
+    if version < 19 {
+        conn.execute_batch(r#"
+            CREATE TABLE IF NOT EXISTS linear_issues (
+                linear_issue_id TEXT PRIMARY KEY,
+                linear_project_id TEXT NOT NULL,
+                identifier TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT,
+                url TEXT NOT NULL,
+                priority INTEGER,
+                priority_label TEXT,
+                due_date TEXT,
+                estimate REAL,
+                state TEXT,
+                assignee TEXT,
+                labels TEXT,
+                updated_at TEXT,
+                synced_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_linear_issues_project ON linear_issues(linear_project_id);
+
+            CREATE TABLE IF NOT EXISTS linear_issue_syncs (
+                linear_project_id TEXT PRIMARY KEY,
+                last_updated_at TEXT,
+                synced_at TEXT NOT NULL,
+                full_synced_at TEXT NOT NULL
+            );
+
+            PRAGMA user_version = 19;
+        "#)?;
+    }
+
+    // Migration 20: Result of the last check that a timebox's Linear issue still exists
+    // in its project
+    if version < 20 {
+        conn.execute_batch(r#"
+            ALTER TABLE timeboxes ADD COLUMN linear_issue_link_status TEXT;
+            ALTER TABLE timeboxes ADD COLUMN linear_issue_checked_at TEXT;
+
+            PRAGMA user_version = 20;
+        "#)?;
+    }
+
+    // Migration 21: When Linear archived a saved project, as of the last refresh, so a
+    // project is only archived locally once and unarchiving it sticks
+    if version < 21 {
+        conn.execute_batch(r#"
+            ALTER TABLE linear_projects ADD COLUMN linear_archived_at TEXT;
+
+            PRAGMA user_version = 21;
+        "#)?;
+    }
+
     Ok(())
 }
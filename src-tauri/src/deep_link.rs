@@ -0,0 +1,66 @@
+use crate::actions::{notify_timeboxes_changed, run_timebox_action, TimeboxAction};
+use crate::commands::{create_timebox, resolve_linear_issue};
+use crate::parser::parse_deep_link;
+use crate::state::AppState;
+use crate::tray::show_main_window;
+use chrono::Local;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_notification::NotificationExt;
+
+/// Handles `timeboxd://` links opened while the app runs and the one it was launched with.
+pub fn setup_deep_links(app: &AppHandle) -> Result<(), String> {
+    // Installers register the scheme, this covers dev builds and AppImages on Linux/Windows
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        eprintln!("Failed to register timeboxd:// links: {}", e);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            spawn_open_deep_link(&handle, url.to_string());
+        }
+    });
+
+    if let Some(urls) = app.deep_link().get_current().map_err(|e| e.to_string())? {
+        for url in urls {
+            spawn_open_deep_link(app, url.to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Opens a link on a background task, resolving the Linear issue may hit the network
+pub fn spawn_open_deep_link(app: &AppHandle, url: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = open_deep_link(&app, &url).await {
+            eprintln!("Failed to open {}: {}", url, e);
+            let _ = app
+                .notification()
+                .builder()
+                .title("Couldn't open timeboxd link")
+                .body(e)
+                .show();
+        }
+        show_main_window(&app);
+    });
+}
+
+async fn open_deep_link(app: &AppHandle, url: &str) -> Result<(), String> {
+    let link = parse_deep_link(url, Local::now().naive_local())?;
+    let mut request = link.request;
+
+    resolve_linear_issue(&app.state::<AppState>(), &mut request).await?;
+    let timebox = create_timebox(app.state(), request)?;
+
+    if link.start {
+        run_timebox_action(app, TimeboxAction::Start, timebox.id)?;
+    } else {
+        notify_timeboxes_changed(app);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use crate::actions::{notify_timeboxes_changed, run_timebox_action, TimeboxAction};
+use crate::commands::{
+    create_timebox, get_active_timeboxes, get_sessions_for_timebox, get_today_timeboxes,
+    HttpApiSettings,
+};
+use crate::core::TimeboxService;
+use crate::models::CreateTimeboxRequest;
+use crate::parser::parse_duration_details;
+use crate::state::AppState;
+use chrono::Local;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+// How long a restart waits for the old listener to let go of the port
+const BIND_ATTEMPTS: u32 = 10;
+const BIND_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// The running server, if the API is enabled
+#[derive(Default)]
+pub struct HttpApiState {
+    server: Mutex<Option<RunningServer>>,
+}
+
+struct RunningServer {
+    server: Arc<Server>,
+    thread: JoinHandle<()>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateTimeboxBody {
+    intention: String,
+    /// Minutes, like the app's own requests
+    intended_duration: Option<i64>,
+    /// Or a duration the parser understands: "25m", "1h30", "until 3pm"
+    duration: Option<String>,
+    notes: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+type ApiResult = Result<(u16, Value), (u16, String)>;
+
+/// (Re)starts the HTTP API with the given settings, or stops it when disabled.
+pub fn start_http_api(app: &AppHandle, settings: &HttpApiSettings) -> Result<(), String> {
+    stop_http_api(app);
+
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    let server = Arc::new(bind(settings.port)?);
+
+    let thread_app = app.clone();
+    let thread_server = server.clone();
+    let token = settings.token.clone();
+    let thread = thread::spawn(move || {
+        // Ends when the server is unblocked by stop_http_api
+        for request in thread_server.incoming_requests() {
+            handle_request(&thread_app, &token, request);
+        }
+    });
+
+    if let Some(state) = app.try_state::<HttpApiState>() {
+        if let Ok(mut current) = state.server.lock() {
+            *current = Some(RunningServer { server, thread });
+        }
+    }
+
+    Ok(())
+}
+
+// The previous server closes its listener from tiny_http's accept thread, shortly after
+// the server is dropped, so the port can still be taken for a moment after a stop
+fn bind(port: u16) -> Result<Server, String> {
+    let mut attempt = 1;
+    loop {
+        match Server::http(("127.0.0.1", port)) {
+            Ok(server) => return Ok(server),
+            Err(_) if attempt < BIND_ATTEMPTS => {
+                attempt += 1;
+                thread::sleep(BIND_RETRY_DELAY);
+            }
+            Err(e) => return Err(format!("Could not listen on 127.0.0.1:{}: {}", port, e)),
+        }
+    }
+}
+
+pub fn stop_http_api(app: &AppHandle) {
+    let Some(state) = app.try_state::<HttpApiState>() else {
+        return;
+    };
+    let Some(running) = state.server.lock().ok().and_then(|mut current| current.take()) else {
+        return;
+    };
+
+    running.server.unblock();
+    // The server, and with it the listener, is only dropped once the serving thread
+    // lets go of it, so wait for that before anything binds the port again
+    drop(running.server);
+    let _ = running.thread.join();
+}
+
+fn handle_request(app: &AppHandle, token: &str, mut request: Request) {
+    let result = if is_authorized(&request, token) {
+        route(app, &mut request)
+    } else {
+        Err((401, "Missing or invalid bearer token".to_string()))
+    };
+
+    let (status, body) = match result {
+        Ok(ok) => ok,
+        Err((status, message)) => (status, json!({ "error": message })),
+    };
+
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+
+    let _ = request.respond(response);
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .and_then(|h| h.value.as_str().strip_prefix("Bearer "))
+        .is_some_and(|given| constant_time_eq(given.trim().as_bytes(), token.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn route(app: &AppHandle, request: &mut Request) -> ApiResult {
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let method = request.method().clone();
+
+    match (&method, segments.as_slice()) {
+        (Method::Get, ["timeboxes"]) | (Method::Get, ["timeboxes", "today"]) => {
+            to_json(get_today_timeboxes(app.state()))
+        }
+        (Method::Get, ["timeboxes", "active"]) => to_json(get_active_timeboxes(app.state())),
+        (Method::Get, ["timeboxes", "current"]) => {
+            let state = app.state::<AppState>();
+            let conn = state.db.lock().map_err(internal_error)?;
+            to_json(TimeboxService::new(&conn).current())
+        }
+        (Method::Post, ["timeboxes"]) => {
+            let body: CreateTimeboxBody = read_json(request)?;
+            create(app, body)
+        }
+        (Method::Get, ["timeboxes", id]) => {
+            let id = parse_id(id)?;
+            let state = app.state::<AppState>();
+            let conn = state.db.lock().map_err(internal_error)?;
+            TimeboxService::new(&conn)
+                .get(id)
+                .map(|t| (200, json!(t)))
+                .map_err(|_| (404, format!("Timebox {} not found", id)))
+        }
+        (Method::Get, ["timeboxes", id, "sessions"]) => {
+            to_json(get_sessions_for_timebox(app.state(), parse_id(id)?))
+        }
+        (Method::Post, ["timeboxes", id, action]) => {
+            let id = parse_id(id)?;
+            let action = TimeboxAction::from_str(action)
+                .ok_or_else(|| (404, format!("Unknown action '{}'", action)))?;
+            run_timebox_action(app, action, id)
+                .map(|t| (200, json!(t)))
+                .map_err(|e| (400, e))
+        }
+        (_, ["timeboxes", ..]) => Err((405, "Method not allowed".to_string())),
+        _ => Err((404, "Not found".to_string())),
+    }
+}
+
+fn create(app: &AppHandle, body: CreateTimeboxBody) -> ApiResult {
+    let intention = body.intention.trim().to_string();
+    if intention.is_empty() {
+        return Err((400, "intention is required".to_string()));
+    }
+
+    let intended_duration = match (body.intended_duration, body.duration) {
+        (Some(minutes), _) if minutes > 0 => minutes,
+        (None, Some(duration)) => {
+            parse_duration_details(&duration, Local::now().naive_local())
+                .map_err(|e| (400, e.to_string()))?
+                .intended_duration
+        }
+        _ => return Err((400, "intended_duration (minutes) or duration is required".to_string())),
+    };
+
+    let request = CreateTimeboxRequest {
+        intention,
+        intended_duration,
+        notes: body.notes,
+        linear_project_id: None,
+        linear_issue_id: None,
+        linear_issue_identifier: None,
+        linear_issue_url: None,
+        tags: body.tags,
+    };
+
+    let timebox = create_timebox(app.state(), request).map_err(|e| (400, e))?;
+    notify_timeboxes_changed(app);
+    Ok((201, json!(timebox)))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(request: &mut Request) -> Result<T, (u16, String)> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_string(&mut body)
+        .map_err(|e| (400, e.to_string()))?;
+    serde_json::from_str(&body).map_err(|e| (400, format!("Invalid JSON body: {}", e)))
+}
+
+fn parse_id(id: &str) -> Result<i64, (u16, String)> {
+    id.parse().map_err(|_| (404, format!("'{}' is not a timebox id", id)))
+}
+
+fn to_json<T: serde::Serialize>(result: Result<T, String>) -> ApiResult {
+    result.map(|value| (200, json!(value))).map_err(internal_error)
+}
+
+fn internal_error(e: impl ToString) -> (u16, String) {
+    (500, e.to_string())
+}
@@ -0,0 +1,222 @@
+//! HTTP layer shared by the Linear and Todoist integrations: one client with timeouts,
+//! retries with exponential backoff for rate limits and transient failures, and errors
+//! whose kind the UI can act on.
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, Response};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_DELAY: Duration = if cfg!(test) { Duration::from_millis(1) } else { Duration::from_millis(500) };
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
+/// Longest server-requested wait we sleep through; beyond it the error goes to the caller
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(30);
+
+/// Error bodies are appended to messages up to this many characters
+const MAX_BODY_IN_MESSAGE: usize = 200;
+
+// Linear reports its limits per request count and per query complexity
+const RATE_LIMIT_HEADERS: &[(&str, &str)] = &[
+    ("x-ratelimit-requests-remaining", "x-ratelimit-requests-reset"),
+    ("x-ratelimit-complexity-remaining", "x-ratelimit-complexity-reset"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorKind {
+    /// The API key or token was refused
+    Auth,
+    RateLimited,
+    /// No response: DNS, refused connection, TLS or timeout
+    Offline,
+    /// 5xx after retries
+    Server,
+    /// The service understood the request and turned it down
+    Rejected,
+    /// The response wasn't what we expected
+    InvalidResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ApiError {
+    pub kind: ApiErrorKind,
+    pub message: String,
+    pub status: Option<u16>,
+    /// Seconds until a rate limit resets, when the service said
+    pub retry_after_secs: Option<u64>,
+}
+
+impl ApiError {
+    pub fn new(kind: ApiErrorKind, message: impl Into<String>) -> Self {
+        ApiError {
+            kind,
+            message: message.into(),
+            status: None,
+            retry_after_secs: None,
+        }
+    }
+
+    pub fn invalid_response(service: &str, e: impl fmt::Display) -> Self {
+        ApiError::new(ApiErrorKind::InvalidResponse, format!("Failed to parse {} response: {}", service, e))
+    }
+
+    /// The service received the request and answered, as opposed to never getting or
+    /// never understanding the response
+    pub fn is_rejection(&self) -> bool {
+        !matches!(self.kind, ApiErrorKind::Offline | ApiErrorKind::InvalidResponse)
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<ApiError> for String {
+    fn from(e: ApiError) -> Self {
+        e.message
+    }
+}
+
+/// Shared client, so connections are reused across requests
+pub fn http_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("timeboxd/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// Sends a request built by `build`, retrying rate limits and transient failures.
+/// Callers must not hold the DB lock across the await.
+///
+/// Rate-limited and never-connected requests are always retried, since the service
+/// didn't act on them. Timeouts and 5xx responses are only retried when `idempotent`,
+/// because the first attempt may already have taken effect.
+pub async fn send(service: &str, idempotent: bool, build: impl Fn(&Client) -> RequestBuilder) -> Result<Response, ApiError> {
+    let mut attempt = 1;
+    loop {
+        let (error, wait) = match build(http_client()).send().await {
+            Ok(response) if response.status().is_success() => return Ok(response),
+            Ok(response) => {
+                let error = status_error(service, response).await;
+                let retryable = match error.kind {
+                    ApiErrorKind::RateLimited => true,
+                    ApiErrorKind::Server => idempotent,
+                    _ => false,
+                };
+                let wait = error.retry_after_secs.map(Duration::from_secs);
+                if !retryable || wait.is_some_and(|w| w > MAX_RETRY_WAIT) {
+                    return Err(error);
+                }
+                (error, wait)
+            }
+            Err(e) => {
+                if !(e.is_connect() || (idempotent && e.is_timeout())) {
+                    return Err(transport_error(service, e));
+                }
+                (transport_error(service, e), None)
+            }
+        };
+
+        if attempt >= MAX_ATTEMPTS {
+            return Err(error);
+        }
+        tokio::time::sleep(wait.unwrap_or_else(|| backoff(attempt))).await;
+        attempt += 1;
+    }
+}
+
+fn transport_error(service: &str, e: reqwest::Error) -> ApiError {
+    let message = if e.is_timeout() {
+        format!("{} did not respond in time: {}", service, e)
+    } else {
+        format!("Failed to connect to {}: {}", service, e)
+    };
+    ApiError::new(ApiErrorKind::Offline, message)
+}
+
+async fn status_error(service: &str, response: Response) -> ApiError {
+    let status = response.status();
+    let retry_after = retry_after(response.headers());
+    let exhausted = rate_limit_exhausted(response.headers());
+    let body = response.text().await.unwrap_or_default();
+
+    let kind = match status {
+        StatusCode::TOO_MANY_REQUESTS => ApiErrorKind::RateLimited,
+        // Linear answers rate-limited requests with a 400 and an empty budget
+        _ if exhausted && status.is_client_error() => ApiErrorKind::RateLimited,
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ApiErrorKind::Auth,
+        StatusCode::REQUEST_TIMEOUT => ApiErrorKind::Server,
+        _ if status.is_server_error() => ApiErrorKind::Server,
+        _ => ApiErrorKind::Rejected,
+    };
+
+    let mut message = format!("{} API returned status: {}", service, status);
+    let body = body.trim();
+    if !body.is_empty() {
+        message.push_str(": ");
+        message.extend(body.chars().take(MAX_BODY_IN_MESSAGE));
+    }
+
+    ApiError {
+        kind,
+        message,
+        status: Some(status.as_u16()),
+        retry_after_secs: retry_after.map(|d| d.as_secs()),
+    }
+}
+
+/// Wait requested by the server, from `Retry-After` (seconds or an HTTP date) or
+/// Linear's `X-RateLimit-*-Reset` (epoch milliseconds) once a budget is used up
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::trim);
+
+    if let Some(value) = header(RETRY_AFTER.as_str()) {
+        if let Ok(secs) = value.parse::<u64>() {
+            return Some(Duration::from_secs(secs));
+        }
+        if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+            return Some((date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default());
+        }
+    }
+
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+    RATE_LIMIT_HEADERS
+        .iter()
+        .filter(|(remaining, _)| header(remaining).and_then(|v| v.parse::<i64>().ok()).is_some_and(|r| r <= 0))
+        .filter_map(|(_, reset)| header(reset).and_then(|v| v.parse::<u64>().ok()))
+        .map(|reset_ms| Duration::from_millis(reset_ms.saturating_sub(now_ms)))
+        .max()
+}
+
+fn rate_limit_exhausted(headers: &HeaderMap) -> bool {
+    RATE_LIMIT_HEADERS.iter().any(|(remaining, _)| {
+        headers
+            .get(*remaining)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .is_some_and(|r| r <= 0)
+    })
+}
+
+/// Exponential backoff with up to 50% jitter, so clients don't retry in lockstep
+fn backoff(attempt: u32) -> Duration {
+    let delay = BASE_DELAY.saturating_mul(1 << (attempt - 1).min(16)).min(MAX_BACKOFF);
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().subsec_nanos();
+    let jitter = delay.mul_f64(f64::from(nanos % 1000) / 2000.0);
+    delay + jitter
+}
@@ -0,0 +1,102 @@
+use super::http::{retry_after, send, ApiErrorKind};
+use crate::mock_server::{unreachable_url, MockServer};
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::time::Duration;
+use tauri::async_runtime::block_on;
+
+fn get(server: &MockServer, idempotent: bool) -> Result<u16, super::http::ApiError> {
+    let url = format!("{}/resource", server.url());
+    block_on(send("Example", idempotent, |client| client.get(&url))).map(|r| r.status().as_u16())
+}
+
+#[test]
+fn test_retries_rate_limit_with_retry_after() {
+    let server = MockServer::start();
+    server
+        .respond_with_headers(429, &[("Retry-After", "0")], "slow down")
+        .respond(200, "{}");
+
+    assert_eq!(get(&server, false).unwrap(), 200);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_gives_up_when_retry_after_is_too_long() {
+    let server = MockServer::start();
+    server.respond_with_headers(429, &[("Retry-After", "120")], "slow down");
+
+    let error = get(&server, true).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::RateLimited);
+    assert_eq!(error.retry_after_secs, Some(120));
+    assert_eq!(error.message, "Example API returned status: 429 Too Many Requests: slow down");
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_server_errors_retried_only_when_idempotent() {
+    let server = MockServer::start();
+    server.respond(502, "").respond(503, "").respond(200, "{}");
+    assert_eq!(get(&server, true).unwrap(), 200);
+    assert_eq!(server.requests().len(), 3);
+
+    let server = MockServer::start();
+    server.respond(503, "").respond(200, "{}");
+    let error = get(&server, false).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Server);
+    assert_eq!(error.message, "Example API returned status: 503 Service Unavailable");
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_stops_after_max_attempts() {
+    let server = MockServer::start();
+    server.respond(500, "").respond(500, "").respond(500, "").respond(200, "{}");
+
+    let error = get(&server, true).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Server);
+    assert_eq!(error.status, Some(500));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn test_error_kinds() {
+    let server = MockServer::start();
+    server.respond(401, "").respond(403, "").respond(422, "bad field");
+
+    assert_eq!(get(&server, true).unwrap_err().kind, ApiErrorKind::Auth);
+    assert_eq!(get(&server, true).unwrap_err().kind, ApiErrorKind::Auth);
+    let error = get(&server, true).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Rejected);
+    assert!(error.is_rejection());
+    // Client errors are never retried
+    assert_eq!(server.requests().len(), 3);
+
+    let url = unreachable_url();
+    let error = block_on(send("Example", false, |client| client.get(&url))).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Offline);
+    assert!(!error.is_rejection());
+}
+
+#[test]
+fn test_retry_after_header_formats() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(retry_after(&headers), None);
+
+    headers.insert("retry-after", HeaderValue::from_static("7"));
+    assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+    // Dates in the past mean "now"
+    headers.insert("retry-after", HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+    assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+    let mut headers = HeaderMap::new();
+    let reset = (chrono::Utc::now().timestamp_millis() + 60_000).to_string();
+    headers.insert("x-ratelimit-complexity-reset", HeaderValue::from_str(&reset).unwrap());
+    headers.insert("x-ratelimit-complexity-remaining", HeaderValue::from_static("500"));
+    // Budget left: no wait
+    assert_eq!(retry_after(&headers), None);
+
+    headers.insert("x-ratelimit-complexity-remaining", HeaderValue::from_static("0"));
+    let wait = retry_after(&headers).unwrap();
+    assert!(wait > Duration::from_secs(55) && wait <= Duration::from_secs(60), "{:?}", wait);
+}
@@ -0,0 +1,575 @@
+//! Async client for Linear's GraphQL API: auth, error mapping and cursor
+//! pagination on top of the shared HTTP layer. Queries are fixed strings;
+//! everything the user typed travels in `variables`.
+
+use super::linear_graphql_url;
+use crate::integrations::http::{self, ApiError, ApiErrorKind};
+use crate::integrations::{api_base_url, config_str};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Safety stop for pagination, at 100 nodes per page this is 5000 nodes
+pub const MAX_PAGES: usize = 50;
+
+const VIEWER_QUERY: &str = "query { viewer { id name email } }";
+
+const TEAMS_QUERY: &str = "query($after: String) { teams(first: 100, after: $after) { nodes { id name } pageInfo { hasNextPage endCursor } } }";
+
+const TEAM_PROJECTS_QUERY: &str = "query($teamId: String!, $after: String) { team(id: $teamId) { projects(first: 100, after: $after) { nodes { id name description state } pageInfo { hasNextPage endCursor } } } }";
+
+const TEAM_STATES_QUERY: &str = "query($teamId: String!, $after: String) { team(id: $teamId) { states(first: 100, after: $after) { nodes { id name type } pageInfo { hasNextPage endCursor } } } }";
+
+const PROJECT_ISSUES_QUERY: &str = "query($projectId: String!, $after: String) { project(id: $projectId) { issues(first: 100, after: $after) { nodes { id identifier title description url priority priorityLabel dueDate estimate updatedAt state { id name color type } assignee { id name email } labels { nodes { id name color } } } pageInfo { hasNextPage endCursor } } } }";
+
+// Archived issues are included so they can be dropped from the local cache
+const PROJECT_ISSUES_SINCE_QUERY: &str = "query($projectId: String!, $since: DateTimeOrDuration!, $after: String) { project(id: $projectId) { issues(first: 100, after: $after, includeArchived: true, filter: { updatedAt: { gt: $since } }) { nodes { id identifier title description url priority priorityLabel dueDate estimate updatedAt archivedAt state { id name color type } assignee { id name email } labels { nodes { id name color } } } pageInfo { hasNextPage endCursor } } } }";
+
+const PROJECTS_BY_ID_QUERY: &str = "query($ids: [ID!], $after: String) { projects(first: 100, after: $after, includeArchived: true, filter: { id: { in: $ids } }) { nodes { id name description state archivedAt } pageInfo { hasNextPage endCursor } } }";
+
+const SEARCH_TEAMS_QUERY: &str = "query($term: String!, $after: String) { teams(first: 100, after: $after, filter: { name: { containsIgnoreCase: $term } }) { nodes { id name } pageInfo { hasNextPage endCursor } } }";
+
+const SEARCH_PROJECTS_QUERY: &str = "query($term: String!, $after: String) { projects(first: 100, after: $after, filter: { name: { containsIgnoreCase: $term } }) { nodes { id name description state teams { nodes { id name } } } pageInfo { hasNextPage endCursor } } }";
+
+const ASSIGNED_ISSUES_QUERY: &str = r#"query($after: String) { viewer { assignedIssues(first: 100, after: $after, filter: { state: { type: { nin: ["completed", "canceled"] } } }) { nodes { id identifier title url priority priorityLabel estimate state { id name color type } project { id name } } pageInfo { hasNextPage endCursor } } } }"#;
+
+const ACTIVE_CYCLE_QUERY: &str = "query($teamId: String!) { team(id: $teamId) { activeCycle { id number name startsAt endsAt } } }";
+
+const CYCLE_ISSUES_QUERY: &str = "query($cycleId: String!, $after: String) { cycle(id: $cycleId) { issues(first: 100, after: $after) { nodes { id identifier title url priority priorityLabel estimate state { id name color type } project { id name } } pageInfo { hasNextPage endCursor } } } }";
+
+const ISSUE_LOOKUP_QUERY: &str = "query($id: String!) { issue(id: $id) { id identifier url } }";
+
+const ISSUES_BY_ID_QUERY: &str = "query($ids: [ID!], $after: String) { issues(first: 100, after: $after, includeArchived: true, filter: { id: { in: $ids } }) { nodes { id identifier url archivedAt trashed project { id name } } pageInfo { hasNextPage endCursor } } }";
+
+const ISSUE_CREATE_MUTATION: &str = "mutation($input: IssueCreateInput!) { issueCreate(input: $input) { success issue { id identifier url title } } }";
+
+const COMMENT_CREATE_MUTATION: &str = "mutation($input: CommentCreateInput!) { commentCreate(input: $input) { success comment { id } } }";
+
+const COMMENT_LOOKUP_QUERY: &str = "query($id: String!) { comment(id: $id) { id } }";
+
+const ISSUE_UPDATE_STATE_MUTATION: &str = "mutation($id: String!, $stateId: String!) { issueUpdate(id: $id, input: { stateId: $stateId }) { success } }";
+
+// ============================================
+// API types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearViewer {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearTeam {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearApiProject {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearApiIssue {
+    pub id: String,
+    pub identifier: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    pub priority: Option<i32>,
+    #[serde(rename = "priorityLabel")]
+    pub priority_label: Option<String>,
+    #[serde(rename = "dueDate")]
+    pub due_date: Option<String>,
+    pub estimate: Option<f64>,
+    pub state: Option<LinearWorkflowState>,
+    pub assignee: Option<LinearUser>,
+    pub labels: Option<LinearLabelsNodes>,
+    #[serde(rename(deserialize = "updatedAt"), default)]
+    pub updated_at: Option<String>,
+    #[serde(rename(deserialize = "archivedAt"), default, skip_serializing)]
+    pub archived_at: Option<String>,
+}
+
+/// A saved project as Linear has it now, to refresh the local copy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearProjectStatus {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub state: Option<String>,
+    #[serde(rename(deserialize = "archivedAt"), default)]
+    pub archived_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearWorkflowState {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+    #[serde(rename = "type")]
+    pub state_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearUser {
+    pub id: String,
+    pub name: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearLabelsNodes {
+    pub nodes: Vec<LinearLabel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearLabel {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+}
+
+/// An issue offered for planning, from "My issues" or a cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearPlanningIssue {
+    pub id: String,
+    pub identifier: String,
+    pub title: String,
+    pub url: String,
+    pub priority: Option<i32>,
+    #[serde(rename(deserialize = "priorityLabel"))]
+    pub priority_label: Option<String>,
+    pub estimate: Option<f64>,
+    pub state: Option<LinearWorkflowState>,
+    pub project: Option<LinearProjectRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearProjectRef {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearCycle {
+    pub id: String,
+    pub number: i64,
+    pub name: Option<String>,
+    #[serde(rename(deserialize = "startsAt"))]
+    pub starts_at: String,
+    #[serde(rename(deserialize = "endsAt"))]
+    pub ends_at: String,
+    #[serde(default)]
+    pub issues: Vec<LinearPlanningIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearIssue {
+    pub id: String,
+    pub identifier: String,
+    pub url: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearTeamWorkflowState {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub state_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearIssueLink {
+    pub id: String,
+    pub identifier: String,
+    pub url: String,
+}
+
+/// Where a linked issue is now, to tell whether the link still holds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearIssueLocation {
+    pub id: String,
+    pub identifier: String,
+    pub url: String,
+    #[serde(rename(deserialize = "archivedAt"), default)]
+    pub archived_at: Option<String>,
+    /// In the trash, i.e. deleted but still restorable
+    #[serde(default)]
+    pub trashed: Option<bool>,
+    pub project: Option<LinearProjectRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearSearchProject {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub state: Option<String>,
+    pub teams: LinearSearchProjectTeams,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearSearchProjectTeams {
+    pub nodes: Vec<LinearTeam>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueCreateInput {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub project_id: String,
+    pub team_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssueCreateResult {
+    pub success: bool,
+    pub issue: Option<LinearIssue>,
+}
+
+// ============================================
+// Client
+// ============================================
+
+#[derive(Debug, Serialize)]
+struct GraphqlRequest<'a, V: Serialize> {
+    query: &'a str,
+    variables: V,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlResponse<T> {
+    data: Option<T>,
+    errors: Option<Vec<GraphqlError>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlError {
+    message: String,
+    extensions: Option<GraphqlErrorExtensions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlErrorExtensions {
+    code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    nodes: Vec<T>,
+    page_info: PageInfo,
+}
+
+/// Everything a paginated query returned, `truncated` when it stopped at `MAX_PAGES`
+/// with pages left
+#[derive(Debug, Clone)]
+pub struct Paged<T> {
+    pub nodes: Vec<T>,
+    pub truncated: bool,
+}
+
+pub struct LinearClient {
+    endpoint: String,
+    api_key: String,
+}
+
+impl LinearClient {
+    pub fn new(api_key: &str, api_base_url: Option<&str>) -> Self {
+        LinearClient {
+            endpoint: linear_graphql_url(api_base_url),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    /// Client for a Linear integration's connection config
+    pub fn from_config(config: &Value) -> Result<Self, String> {
+        Ok(LinearClient::new(config_str(config, "api_key")?, api_base_url(config)))
+    }
+
+    /// Runs a query and returns its `data`
+    pub async fn query<T: DeserializeOwned>(&self, query: &str, variables: impl Serialize) -> Result<T, ApiError> {
+        self.execute(query, variables, true).await
+    }
+
+    /// Runs a mutation; server errors and timeouts aren't retried since the first
+    /// attempt may have gone through
+    pub async fn mutate<T: DeserializeOwned>(&self, query: &str, variables: impl Serialize) -> Result<T, ApiError> {
+        self.execute(query, variables, false).await
+    }
+
+    async fn execute<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: impl Serialize,
+        idempotent: bool,
+    ) -> Result<T, ApiError> {
+        let body = GraphqlRequest { query, variables };
+        let response = http::send("Linear", idempotent, |client| {
+            client.post(&self.endpoint).header("Authorization", &self.api_key).json(&body)
+        })
+        .await?;
+
+        let result: GraphqlResponse<T> = response.json().await.map_err(|e| ApiError::invalid_response("Linear", e))?;
+
+        if let Some(errors) = result.errors {
+            return Err(graphql_error(errors));
+        }
+
+        result.data.ok_or_else(no_data)
+    }
+
+    /// Runs a paginated query until the last page, for lists where a partial result is
+    /// still useful. See `query_pages` for the arguments.
+    pub async fn query_all<T: DeserializeOwned>(&self, query: &str, variables: Value, path: &[&str]) -> Result<Vec<T>, ApiError> {
+        let paged = self.query_pages(query, variables, path).await?;
+        if paged.truncated {
+            eprintln!("Linear returned more than {} pages, only the first ones are used", MAX_PAGES);
+        }
+        Ok(paged.nodes)
+    }
+
+    /// Runs a paginated query until the last page, or `MAX_PAGES`. The query takes an
+    /// `$after: String` cursor and `path` leads from `data` to the connection, e.g.
+    /// `["team", "projects"]`.
+    pub async fn query_pages<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        mut variables: Value,
+        path: &[&str],
+    ) -> Result<Paged<T>, ApiError> {
+        let mut nodes = Vec::new();
+        let mut after: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            variables["after"] = json!(after);
+            let mut data: Value = self.query(query, &variables).await?;
+
+            let mut connection = &mut data;
+            for key in path {
+                connection = connection.get_mut(*key).ok_or_else(no_data)?;
+            }
+            if connection.is_null() {
+                return Err(no_data());
+            }
+
+            let page: Page<T> =
+                serde_json::from_value(connection.take()).map_err(|e| ApiError::invalid_response("Linear", e))?;
+            nodes.extend(page.nodes);
+
+            match page.page_info.end_cursor {
+                Some(cursor) if page.page_info.has_next_page => after = Some(cursor),
+                _ => return Ok(Paged { nodes, truncated: false }),
+            }
+        }
+
+        Ok(Paged { nodes, truncated: true })
+    }
+
+    pub async fn viewer(&self) -> Result<LinearViewer, ApiError> {
+        #[derive(Deserialize)]
+        struct Data {
+            viewer: LinearViewer,
+        }
+        self.query::<Data>(VIEWER_QUERY, ()).await.map(|d| d.viewer)
+    }
+
+    pub async fn teams(&self) -> Result<Vec<LinearTeam>, ApiError> {
+        self.query_all(TEAMS_QUERY, json!({}), &["teams"]).await
+    }
+
+    pub async fn team_projects(&self, team_id: &str) -> Result<Vec<LinearApiProject>, ApiError> {
+        self.query_all(TEAM_PROJECTS_QUERY, json!({ "teamId": team_id }), &["team", "projects"]).await
+    }
+
+    /// The projects among `ids` that still exist, archived ones included
+    pub async fn projects_by_id(&self, ids: &[String]) -> Result<Vec<LinearProjectStatus>, ApiError> {
+        let mut projects = Vec::new();
+        for chunk in ids.chunks(100) {
+            projects.extend(self.query_all(PROJECTS_BY_ID_QUERY, json!({ "ids": chunk }), &["projects"]).await?);
+        }
+        Ok(projects)
+    }
+
+    pub async fn team_states(&self, team_id: &str) -> Result<Vec<LinearTeamWorkflowState>, ApiError> {
+        self.query_all(TEAM_STATES_QUERY, json!({ "teamId": team_id }), &["team", "states"]).await
+    }
+
+    pub async fn project_issues(&self, project_id: &str) -> Result<Paged<LinearApiIssue>, ApiError> {
+        self.query_pages(PROJECT_ISSUES_QUERY, json!({ "projectId": project_id }), &["project", "issues"]).await
+    }
+
+    /// Issues of a project changed after `since` (an `updatedAt` timestamp), archived ones included
+    pub async fn project_issues_updated_since(&self, project_id: &str, since: &str) -> Result<Paged<LinearApiIssue>, ApiError> {
+        self.query_pages(
+            PROJECT_ISSUES_SINCE_QUERY,
+            json!({ "projectId": project_id, "since": since }),
+            &["project", "issues"],
+        )
+        .await
+    }
+
+    pub async fn search_teams(&self, term: &str) -> Result<Vec<LinearTeam>, ApiError> {
+        self.query_all(SEARCH_TEAMS_QUERY, json!({ "term": term }), &["teams"]).await
+    }
+
+    pub async fn search_projects(&self, term: &str) -> Result<Vec<LinearSearchProject>, ApiError> {
+        self.query_all(SEARCH_PROJECTS_QUERY, json!({ "term": term }), &["projects"]).await
+    }
+
+    /// Open issues assigned to the API key's user, with what planning needs
+    pub async fn assigned_issues(&self) -> Result<Vec<LinearPlanningIssue>, ApiError> {
+        self.query_all(ASSIGNED_ISSUES_QUERY, json!({}), &["viewer", "assignedIssues"]).await
+    }
+
+    /// The team's current cycle with its issues, `None` if the team has no active cycle
+    pub async fn active_cycle(&self, team_id: &str) -> Result<Option<LinearCycle>, ApiError> {
+        #[derive(Deserialize)]
+        struct Team {
+            #[serde(rename = "activeCycle")]
+            active_cycle: Option<LinearCycle>,
+        }
+        #[derive(Deserialize)]
+        struct Data {
+            team: Team,
+        }
+
+        let Some(mut cycle) = self
+            .query::<Data>(ACTIVE_CYCLE_QUERY, json!({ "teamId": team_id }))
+            .await?
+            .team
+            .active_cycle
+        else {
+            return Ok(None);
+        };
+        cycle.issues = self.query_all(CYCLE_ISSUES_QUERY, json!({ "cycleId": cycle.id }), &["cycle", "issues"]).await?;
+        Ok(Some(cycle))
+    }
+
+    /// Looks up an issue by its identifier (e.g. "ENG-123") or id
+    pub async fn find_issue(&self, id: &str) -> Result<Option<LinearIssueLink>, ApiError> {
+        #[derive(Deserialize)]
+        struct Data {
+            issue: Option<LinearIssueLink>,
+        }
+        self.query::<Data>(ISSUE_LOOKUP_QUERY, json!({ "id": id })).await.map(|d| d.issue)
+    }
+
+    /// The issues among `ids` that still exist, archived ones included. Issues deleted for
+    /// good aren't returned.
+    pub async fn issues_by_id(&self, ids: &[String]) -> Result<Vec<LinearIssueLocation>, ApiError> {
+        let mut issues = Vec::new();
+        for chunk in ids.chunks(100) {
+            issues.extend(self.query_all(ISSUES_BY_ID_QUERY, json!({ "ids": chunk }), &["issues"]).await?);
+        }
+        Ok(issues)
+    }
+
+    pub async fn create_issue(&self, input: &IssueCreateInput) -> Result<IssueCreateResult, ApiError> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            issue_create: IssueCreateResult,
+        }
+        self.mutate::<Data>(ISSUE_CREATE_MUTATION, json!({ "input": input }))
+            .await
+            .map(|d| d.issue_create)
+    }
+
+    /// Posts a markdown comment on an issue, returning the comment id. Linear refuses a
+    /// second comment with the same `id`, which makes a retry safe.
+    pub async fn create_comment(&self, issue_id: &str, body: &str, id: Option<&str>) -> Result<String, ApiError> {
+        #[derive(Deserialize)]
+        struct Comment {
+            id: String,
+        }
+        #[derive(Deserialize)]
+        struct Created {
+            success: bool,
+            comment: Option<Comment>,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            comment_create: Created,
+        }
+        let mut input = json!({ "issueId": issue_id, "body": body });
+        if let Some(id) = id {
+            input["id"] = json!(id);
+        }
+        let created = self
+            .mutate::<Data>(COMMENT_CREATE_MUTATION, json!({ "input": input }))
+            .await?
+            .comment_create;
+        match created.comment {
+            Some(comment) if created.success => Ok(comment.id),
+            _ => Err(ApiError::new(ApiErrorKind::Rejected, "Linear did not create the comment")),
+        }
+    }
+
+    /// Whether a comment with this id exists. Linear answers unknown ids with an error,
+    /// which counts as not found.
+    pub async fn comment_exists(&self, id: &str) -> Result<bool, ApiError> {
+        #[derive(Deserialize)]
+        struct Comment {}
+        #[derive(Deserialize)]
+        struct Data {
+            comment: Option<Comment>,
+        }
+        match self.query::<Data>(COMMENT_LOOKUP_QUERY, json!({ "id": id })).await {
+            Ok(data) => Ok(data.comment.is_some()),
+            Err(e) if e.kind == ApiErrorKind::Rejected => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn update_issue_state(&self, issue_id: &str, state_id: &str) -> Result<bool, ApiError> {
+        #[derive(Deserialize)]
+        struct Success {
+            success: bool,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            issue_update: Success,
+        }
+        // Setting the same state twice is harmless, so this one may be retried
+        self.execute::<Data>(ISSUE_UPDATE_STATE_MUTATION, json!({ "id": issue_id, "stateId": state_id }), true)
+            .await
+            .map(|d| d.issue_update.success)
+    }
+}
+
+fn no_data() -> ApiError {
+    ApiError::new(ApiErrorKind::Rejected, "No data returned from Linear")
+}
+
+/// Joins GraphQL error messages, taking the kind from Linear's error codes
+fn graphql_error(errors: Vec<GraphqlError>) -> ApiError {
+    let kind = errors
+        .iter()
+        .find_map(|e| match e.extensions.as_ref()?.code.as_deref()? {
+            "RATELIMITED" => Some(ApiErrorKind::RateLimited),
+            "AUTHENTICATION_ERROR" | "FORBIDDEN" => Some(ApiErrorKind::Auth),
+            _ => None,
+        })
+        .unwrap_or(ApiErrorKind::Rejected);
+    let message = errors.into_iter().map(|e| e.message).collect::<Vec<_>>().join(", ");
+    ApiError::new(kind, message)
+}
@@ -0,0 +1,130 @@
+//! The time-spent comment posted on a timebox's Linear issue
+
+use crate::core::{OutboxAction, TimeboxWithSessions};
+use crate::integrations::provider::ConfigOption;
+use crate::integrations::registry::ProviderHook;
+use crate::models::Session;
+use chrono::NaiveDateTime;
+use serde_json::Value;
+
+/// Config key of the Linear integration that says when to comment
+pub const TIME_COMMENT_KEY: &str = "time_comment";
+
+pub const TIME_COMMENT_OPTIONS: &[ConfigOption] = &[
+    ConfigOption { value: "never", label: "Never" },
+    ConfigOption { value: "on_finish", label: "When a timebox is finished" },
+    ConfigOption { value: "on_stop", label: "Every time a timebox is stopped or finished" },
+];
+
+// Older sessions are summarized in one line
+const MAX_LISTED_SESSIONS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeCommentMode {
+    Never,
+    OnFinish,
+    OnStop,
+}
+
+impl TimeCommentMode {
+    /// Never, unless the integration's config says otherwise
+    pub fn from_config(config: &Value) -> Self {
+        match config.get(TIME_COMMENT_KEY).and_then(|v| v.as_str()) {
+            Some("on_finish") => TimeCommentMode::OnFinish,
+            Some("on_stop") => TimeCommentMode::OnStop,
+            _ => TimeCommentMode::Never,
+        }
+    }
+}
+
+/// The comment to post for a lifecycle hook, if the mode asks for one and the timebox
+/// is linked to an issue.
+///
+/// The dedupe key makes it idempotent: a finish comment is posted once per timebox,
+/// so finishing again after a restart doesn't repeat it, and a stop comment once per
+/// session, so finishing right after stopping doesn't either.
+pub fn time_spent_comment(mode: TimeCommentMode, hook: ProviderHook, timebox: &TimeboxWithSessions) -> Option<OutboxAction> {
+    let issue_id = timebox.timebox.linear_issue_id.as_deref()?;
+    // Ids, unlike start times, can't tie
+    let last_session = timebox.sessions.iter().filter(|s| s.cancelled_at.is_none()).max_by_key(|s| s.id);
+
+    let dedupe_key = match (mode, hook) {
+        (TimeCommentMode::OnFinish, ProviderHook::Finished) => format!("time_comment:{}:finished", timebox.timebox.id),
+        (TimeCommentMode::OnStop, ProviderHook::Stopped | ProviderHook::Finished) => {
+            format!("time_comment:{}:session:{}", timebox.timebox.id, last_session?.id)
+        }
+        _ => return None,
+    };
+
+    Some(OutboxAction::linear_comment(issue_id, &comment_body(hook, timebox), Some(dedupe_key)))
+}
+
+/// Markdown summary of the sessions, time spent against the plan and the notes
+fn comment_body(hook: ProviderHook, timebox: &TimeboxWithSessions) -> String {
+    let spent = timebox.actual_duration.max(0.0).round() as i64;
+    // intended_duration is in minutes
+    let planned = timebox.timebox.intended_duration * 60;
+
+    let verb = if hook == ProviderHook::Finished { "Finished" } else { "Stopped" };
+    let mut body = format!("**{} timebox: {}**\n\n", verb, timebox.timebox.intention);
+
+    body.push_str(&format!("Time spent: **{}** of {} planned", format_duration(spent), format_duration(planned)));
+    match spent - planned {
+        over if over >= 60 => body.push_str(&format!(" ({} over)", format_duration(over))),
+        under if under <= -60 => body.push_str(&format!(" ({} left)", format_duration(-under))),
+        _ => {}
+    }
+    body.push('\n');
+
+    let mut sessions: Vec<&Session> = timebox.sessions.iter().filter(|s| s.cancelled_at.is_none()).collect();
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.id));
+    if !sessions.is_empty() {
+        body.push_str("\nSessions:\n");
+        for session in sessions.iter().take(MAX_LISTED_SESSIONS).rev() {
+            body.push_str(&format!("- {}\n", format_session(session)));
+        }
+        if sessions.len() > MAX_LISTED_SESSIONS {
+            body.push_str(&format!("- …and {} earlier\n", sessions.len() - MAX_LISTED_SESSIONS));
+        }
+    }
+
+    if let Some(notes) = timebox.timebox.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        body.push_str("\nNotes:\n\n");
+        body.push_str(notes);
+        body.push('\n');
+    }
+
+    body
+}
+
+fn format_session(session: &Session) -> String {
+    let parse = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok();
+    let started = parse(&session.started_at);
+    let stopped = session.stopped_at.as_deref().and_then(parse);
+
+    let mut line = match (started, stopped) {
+        (Some(started), Some(stopped)) => format!(
+            "{} – {} ({})",
+            started.format("%Y-%m-%d %H:%M"),
+            stopped.format("%H:%M"),
+            format_duration((stopped - started).num_seconds())
+        ),
+        (Some(started), None) => format!("{} – still running", started.format("%Y-%m-%d %H:%M")),
+        _ => session.started_at.clone(),
+    };
+    if session.auto_stopped_at.is_some() {
+        line.push_str(", stopped when idle");
+    }
+    line
+}
+
+/// "1h 05m", "25m" or "40s"
+fn format_duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (hours, minutes) = (seconds / 3600, (seconds % 3600) / 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{}s", seconds),
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {:02}m", h, m),
+    }
+}
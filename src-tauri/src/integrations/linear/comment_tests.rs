@@ -0,0 +1,104 @@
+use crate::core::{OutboxAction, OutboxService, TimeboxService};
+use crate::models::{CreateTimeboxRequest, OutboxItem};
+use crate::test_support::{add_integration, test_db, timebox_request};
+use rusqlite::{params, Connection};
+use serde_json::json;
+
+fn setup_test_db(time_comment: &str) -> Connection {
+    let conn = test_db();
+    add_integration(&conn, "linear", json!({ "api_key": "key", "time_comment": time_comment }));
+    conn
+}
+
+fn create_linked_timebox(conn: &Connection, linear_issue_id: Option<&str>) -> i64 {
+    let request = CreateTimeboxRequest {
+        notes: Some("Covered the API section".to_string()),
+        linear_issue_id: linear_issue_id.map(str::to_string),
+        linear_issue_identifier: linear_issue_id.map(|_| "ENG-42".to_string()),
+        ..timebox_request()
+    };
+    TimeboxService::new(conn).create(&request).unwrap().id
+}
+
+fn comments(conn: &Connection) -> Vec<OutboxItem> {
+    OutboxService::new(conn)
+        .items(None, 50)
+        .unwrap()
+        .into_iter()
+        .filter(|item| item.action == "linear_post_comment")
+        .collect()
+}
+
+fn body(item: &OutboxItem) -> String {
+    match OutboxAction::from_item(item).unwrap() {
+        OutboxAction::LinearPostComment { body, .. } => body,
+        other => panic!("Unexpected action {:?}", other),
+    }
+}
+
+#[test]
+fn test_finish_comment_is_posted_once() {
+    let conn = setup_test_db("on_finish");
+    let service = TimeboxService::new(&conn);
+    let id = create_linked_timebox(&conn, Some("issue-1"));
+
+    service.start(id).unwrap();
+    // A 30 minute session, so the summary is predictable
+    conn.execute(
+        "UPDATE sessions SET started_at = datetime('now', 'localtime', '-30 minutes') WHERE timebox_id = ?1",
+        params![id],
+    )
+    .unwrap();
+    service.stop(id).unwrap();
+    assert!(comments(&conn).is_empty(), "on_finish doesn't comment on stop");
+
+    service.finish(id).unwrap();
+    assert!(service.finish(id).is_err());
+
+    let comments = comments(&conn);
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].dedupe_key.as_deref(), Some(format!("time_comment:{}:finished", id).as_str()));
+
+    let body = body(&comments[0]);
+    assert!(body.starts_with("**Finished timebox: Write RFC**"), "{}", body);
+    assert!(body.contains("Time spent: **30m** of 25m planned (5m over)"), "{}", body);
+    assert!(body.contains("Sessions:\n- "), "{}", body);
+    assert!(body.ends_with("Notes:\n\nCovered the API section\n"), "{}", body);
+}
+
+#[test]
+fn test_stop_comments_once_per_session() {
+    let conn = setup_test_db("on_stop");
+    let service = TimeboxService::new(&conn);
+    let id = create_linked_timebox(&conn, Some("issue-1"));
+
+    service.start(id).unwrap();
+    service.stop(id).unwrap();
+    assert_eq!(comments(&conn).len(), 1);
+    assert!(body(&comments(&conn)[0]).starts_with("**Stopped timebox"));
+
+    service.start(id).unwrap();
+    service.auto_stop(id).unwrap();
+    // Finishing right after stopping covers the same session
+    service.finish(id).unwrap();
+    let comments = comments(&conn);
+    assert_eq!(comments.len(), 2);
+    assert!(body(&comments[0]).contains("stopped when idle"));
+}
+
+#[test]
+fn test_no_comment_when_disabled_or_unlinked() {
+    let conn = setup_test_db("never");
+    let service = TimeboxService::new(&conn);
+    let id = create_linked_timebox(&conn, Some("issue-1"));
+    service.start(id).unwrap();
+    service.finish(id).unwrap();
+    assert!(comments(&conn).is_empty());
+
+    let conn = setup_test_db("on_stop");
+    let service = TimeboxService::new(&conn);
+    let id = create_linked_timebox(&conn, None);
+    service.start(id).unwrap();
+    service.finish(id).unwrap();
+    assert!(comments(&conn).is_empty());
+}
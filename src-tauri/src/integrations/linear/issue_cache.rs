@@ -0,0 +1,227 @@
+//! Local copy of Linear project issues, so the project issue view works offline and a
+//! refresh only fetches the issues that changed since the last sync
+
+use super::client::LinearApiIssue;
+use chrono::{Duration, Local, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashSet;
+
+const ISSUE_SELECT_COLUMNS: &str = "linear_issue_id, identifier, title, description, url, priority, priority_label, due_date, estimate, state, assignee, labels, updated_at";
+
+/// How often a sync refetches every issue instead of only the changed ones, which is
+/// what drops issues that were moved to another project
+pub const FULL_SYNC_INTERVAL_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueSyncState {
+    /// Newest `updatedAt` seen, where the next incremental sync starts
+    pub last_updated_at: Option<String>,
+    pub synced_at: String,
+    pub full_synced_at: String,
+}
+
+impl IssueSyncState {
+    /// Whether the next sync should refetch everything
+    pub fn needs_full_sync(&self, now: NaiveDateTime) -> bool {
+        if self.last_updated_at.is_none() {
+            return true;
+        }
+        match NaiveDateTime::parse_from_str(&self.full_synced_at, "%Y-%m-%d %H:%M:%S") {
+            Ok(full_synced_at) => now - full_synced_at >= Duration::hours(FULL_SYNC_INTERVAL_HOURS),
+            Err(_) => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CachedProjectIssues {
+    pub issues: Vec<LinearApiIssue>,
+    /// When the project was last synced, `None` if it never was
+    pub synced_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueSyncResult {
+    pub full: bool,
+    /// Issues added or changed
+    pub updated: usize,
+    /// Issues archived upstream or, on a full sync, no longer in the project
+    pub removed: usize,
+    /// Linear had more issues than a sync fetches. What was fetched is saved, but nothing
+    /// is dropped and the next sync starts over from the same point.
+    pub truncated: bool,
+    pub synced_at: String,
+}
+
+// Nested objects are stored as JSON, unreadable ones are treated as missing
+fn to_json<T: Serialize>(value: Option<&T>) -> Option<String> {
+    value.and_then(|v| serde_json::to_string(v).ok())
+}
+
+fn from_json<T: DeserializeOwned>(value: Option<String>) -> Option<T> {
+    value.and_then(|v| serde_json::from_str(&v).ok())
+}
+
+fn issue_from_row(row: &Row) -> rusqlite::Result<LinearApiIssue> {
+    Ok(LinearApiIssue {
+        id: row.get(0)?,
+        identifier: row.get(1)?,
+        title: row.get(2)?,
+        description: row.get(3)?,
+        url: row.get(4)?,
+        priority: row.get(5)?,
+        priority_label: row.get(6)?,
+        due_date: row.get(7)?,
+        estimate: row.get(8)?,
+        state: from_json(row.get(9)?),
+        assignee: from_json(row.get(10)?),
+        labels: from_json(row.get(11)?),
+        updated_at: row.get(12)?,
+        archived_at: None,
+    })
+}
+
+pub fn sync_state(conn: &Connection, linear_project_id: &str) -> Result<Option<IssueSyncState>, String> {
+    conn.query_row(
+        "SELECT last_updated_at, synced_at, full_synced_at FROM linear_issue_syncs WHERE linear_project_id = ?1",
+        params![linear_project_id],
+        |row| {
+            Ok(IssueSyncState {
+                last_updated_at: row.get(0)?,
+                synced_at: row.get(1)?,
+                full_synced_at: row.get(2)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// A project's issues as of the last sync, most recently updated first
+pub fn cached_project_issues(conn: &Connection, linear_project_id: &str) -> Result<CachedProjectIssues, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM linear_issues WHERE linear_project_id = ?1 ORDER BY updated_at DESC, identifier",
+            ISSUE_SELECT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let issues = stmt
+        .query_map(params![linear_project_id], issue_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(CachedProjectIssues {
+        issues,
+        synced_at: sync_state(conn, linear_project_id)?.map(|s| s.synced_at),
+    })
+}
+
+/// Saves the issues a sync fetched. Archived issues are dropped; a full sync also drops
+/// cached issues it didn't return, unless it was `truncated`.
+pub fn store_synced_issues(
+    conn: &Connection,
+    linear_project_id: &str,
+    issues: &[LinearApiIssue],
+    full: bool,
+    truncated: bool,
+) -> Result<IssueSyncResult, String> {
+    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let previous = sync_state(conn, linear_project_id)?;
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let (mut updated, mut removed) = (0, 0);
+
+    for issue in issues {
+        if issue.archived_at.is_some() {
+            removed += tx
+                .execute("DELETE FROM linear_issues WHERE linear_issue_id = ?1", params![issue.id])
+                .map_err(|e| e.to_string())?;
+            continue;
+        }
+
+        tx.execute(
+            r#"INSERT INTO linear_issues (linear_issue_id, linear_project_id, identifier, title, description, url, priority, priority_label, due_date, estimate, state, assignee, labels, updated_at, synced_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+               ON CONFLICT(linear_issue_id) DO UPDATE SET
+                 linear_project_id = excluded.linear_project_id,
+                 identifier = excluded.identifier,
+                 title = excluded.title,
+                 description = excluded.description,
+                 url = excluded.url,
+                 priority = excluded.priority,
+                 priority_label = excluded.priority_label,
+                 due_date = excluded.due_date,
+                 estimate = excluded.estimate,
+                 state = excluded.state,
+                 assignee = excluded.assignee,
+                 labels = excluded.labels,
+                 updated_at = excluded.updated_at,
+                 synced_at = excluded.synced_at"#,
+            params![
+                issue.id,
+                linear_project_id,
+                issue.identifier,
+                issue.title,
+                issue.description,
+                issue.url,
+                issue.priority,
+                issue.priority_label,
+                issue.due_date,
+                issue.estimate,
+                to_json(issue.state.as_ref()),
+                to_json(issue.assignee.as_ref()),
+                to_json(issue.labels.as_ref()),
+                issue.updated_at,
+                now,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        updated += 1;
+    }
+
+    // Issues past the last page fetched would look removed
+    if full && !truncated {
+        let fetched: HashSet<&str> = issues.iter().filter(|i| i.archived_at.is_none()).map(|i| i.id.as_str()).collect();
+        let cached: Vec<String> = tx
+            .prepare("SELECT linear_issue_id FROM linear_issues WHERE linear_project_id = ?1")
+            .map_err(|e| e.to_string())?
+            .query_map(params![linear_project_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+        for id in cached.iter().filter(|id| !fetched.contains(id.as_str())) {
+            removed += tx
+                .execute("DELETE FROM linear_issues WHERE linear_issue_id = ?1", params![id])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    // ISO 8601 timestamps in UTC compare as strings. Pages aren't ordered by updatedAt,
+    // so after a truncated sync the issues not fetched may be older than the newest one.
+    let previous_updated_at = previous.as_ref().and_then(|p| p.last_updated_at.clone());
+    let last_updated_at = match truncated {
+        true => previous_updated_at,
+        false => issues.iter().filter_map(|i| i.updated_at.clone()).chain(previous_updated_at).max(),
+    };
+    let full_synced_at = match (&previous, full && !truncated) {
+        (Some(previous), false) => previous.full_synced_at.clone(),
+        _ => now.clone(),
+    };
+    tx.execute(
+        r#"INSERT INTO linear_issue_syncs (linear_project_id, last_updated_at, synced_at, full_synced_at)
+           VALUES (?1, ?2, ?3, ?4)
+           ON CONFLICT(linear_project_id) DO UPDATE SET
+             last_updated_at = excluded.last_updated_at,
+             synced_at = excluded.synced_at,
+             full_synced_at = excluded.full_synced_at"#,
+        params![linear_project_id, last_updated_at, now, full_synced_at],
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(IssueSyncResult { full, updated, removed, truncated, synced_at: now })
+}
@@ -0,0 +1,190 @@
+use super::client::MAX_PAGES;
+use super::issue_cache::{cached_project_issues, sync_state};
+use crate::commands::sync_project_issues;
+use crate::mock_server::{unreachable_url, MockServer};
+use crate::state::AppState;
+use crate::test_support::{graphql_page, linear_state};
+use rusqlite::params;
+use serde_json::{json, Value};
+use tauri::async_runtime::block_on;
+
+fn issue(id: &str, title: &str, updated_at: &str, archived_at: Option<&str>) -> Value {
+    json!({
+        "id": id,
+        "identifier": format!("ENG-{}", id.trim_start_matches("issue-")),
+        "title": title,
+        "description": null,
+        "url": format!("https://linear.app/acme/issue/{}", id),
+        "priority": 2,
+        "priorityLabel": "High",
+        "dueDate": null,
+        "estimate": 3.0,
+        "updatedAt": updated_at,
+        "archivedAt": archived_at,
+        "state": { "id": "state-1", "name": "Todo", "color": "#e2e2e2", "type": "unstarted" },
+        "assignee": { "id": "user-1", "name": "Ada", "email": null },
+        "labels": { "nodes": [{ "id": "label-1", "name": "Bug", "color": "#eb5757" }] }
+    })
+}
+
+fn issues_page(nodes: Vec<Value>) -> Value {
+    graphql_page(&["project", "issues"], nodes)
+}
+
+fn cached_titles(state: &AppState) -> Vec<String> {
+    let conn = state.db.lock().unwrap();
+    cached_project_issues(&conn, "project-1").unwrap().issues.into_iter().map(|i| i.title).collect()
+}
+
+#[test]
+fn test_incremental_sync() {
+    let server = MockServer::start();
+    server
+        .respond_json(
+            200,
+            issues_page(vec![
+                issue("issue-1", "Fix login", "2025-03-01T10:00:00.000Z", None),
+                issue("issue-2", "Add SSO", "2025-03-02T10:00:00.000Z", None),
+            ]),
+        )
+        .respond_json(
+            200,
+            issues_page(vec![
+                issue("issue-1", "Fix login redirect", "2025-03-03T10:00:00.000Z", None),
+                issue("issue-2", "Add SSO", "2025-03-04T10:00:00.000Z", Some("2025-03-04T10:00:00.000Z")),
+                issue("issue-3", "Audit log", "2025-03-05T10:00:00.000Z", None),
+            ]),
+        );
+    let state = linear_state(server.url());
+
+    // Nothing synced yet, so everything is fetched
+    let result = block_on(sync_project_issues(&state, "project-1", false)).unwrap();
+    assert!(result.full);
+    assert_eq!((result.updated, result.removed), (2, 0));
+    assert!(server.requests()[0].json()["variables"].get("since").is_none());
+
+    let cached = {
+        let conn = state.db.lock().unwrap();
+        cached_project_issues(&conn, "project-1").unwrap()
+    };
+    assert_eq!(cached.synced_at, Some(result.synced_at));
+    let sso = &cached.issues[0];
+    assert_eq!(sso.identifier, "ENG-2");
+    assert_eq!(sso.priority_label.as_deref(), Some("High"));
+    assert_eq!(sso.estimate, Some(3.0));
+    assert_eq!(sso.state.as_ref().unwrap().state_type, "unstarted");
+    assert_eq!(sso.assignee.as_ref().unwrap().name, "Ada");
+    assert_eq!(sso.labels.as_ref().unwrap().nodes[0].name, "Bug");
+
+    // Then only what changed since the newest issue, archived ones included
+    let result = block_on(sync_project_issues(&state, "project-1", false)).unwrap();
+    assert!(!result.full);
+    assert_eq!((result.updated, result.removed), (2, 1));
+    let request = &server.requests()[1];
+    assert_eq!(request.json()["variables"]["since"], "2025-03-02T10:00:00.000Z");
+    assert!(request.graphql_query().contains("includeArchived: true"));
+
+    assert_eq!(cached_titles(&state), vec!["Audit log", "Fix login redirect"]);
+    let conn = state.db.lock().unwrap();
+    let sync = sync_state(&conn, "project-1").unwrap().unwrap();
+    assert_eq!(sync.last_updated_at.as_deref(), Some("2025-03-05T10:00:00.000Z"));
+}
+
+#[test]
+fn test_full_sync_drops_moved_issues() {
+    let server = MockServer::start();
+    let both = || {
+        issues_page(vec![
+            issue("issue-1", "Fix login", "2025-03-01T10:00:00.000Z", None),
+            issue("issue-2", "Add SSO", "2025-03-02T10:00:00.000Z", None),
+        ])
+    };
+    server
+        .respond_json(200, both())
+        // issue-2 was moved to another project
+        .respond_json(200, issues_page(vec![issue("issue-1", "Fix login", "2025-03-01T10:00:00.000Z", None)]))
+        .respond_json(200, both());
+    let state = linear_state(server.url());
+
+    block_on(sync_project_issues(&state, "project-1", false)).unwrap();
+    let result = block_on(sync_project_issues(&state, "project-1", true)).unwrap();
+    assert!(result.full);
+    assert_eq!((result.updated, result.removed), (1, 1));
+    assert_eq!(cached_titles(&state), vec!["Fix login"]);
+
+    // A day after the last full sync the next one is full again
+    state
+        .db
+        .lock()
+        .unwrap()
+        .execute("UPDATE linear_issue_syncs SET full_synced_at = datetime('now', 'localtime', '-25 hours')", [])
+        .unwrap();
+    assert!(block_on(sync_project_issues(&state, "project-1", false)).unwrap().full);
+    assert!(server.requests()[2].json()["variables"].get("since").is_none());
+}
+
+#[test]
+fn test_truncated_sync_keeps_issues_and_cursor() {
+    let server = MockServer::start();
+    server.respond_json(
+        200,
+        issues_page(vec![
+            issue("issue-1", "Fix login", "2025-03-01T10:00:00.000Z", None),
+            issue("issue-2", "Add SSO", "2025-03-02T10:00:00.000Z", None),
+        ]),
+    );
+    // Every page says there are more, so the sync stops at the page limit
+    for page in 0..MAX_PAGES {
+        let id = format!("issue-{}", page + 3);
+        server.respond_json(
+            200,
+            json!({ "data": { "project": { "issues": {
+                "nodes": [issue(&id, "Audit log", "2025-03-05T10:00:00.000Z", None)],
+                "pageInfo": { "hasNextPage": true, "endCursor": format!("cursor-{}", page) }
+            } } } }),
+        );
+    }
+    let state = linear_state(server.url());
+
+    block_on(sync_project_issues(&state, "project-1", false)).unwrap();
+    let result = block_on(sync_project_issues(&state, "project-1", true)).unwrap();
+    assert!(result.full);
+    assert!(result.truncated);
+    assert_eq!((result.updated, result.removed), (MAX_PAGES, 0));
+
+    // The issues it didn't get to may still be in the project
+    let titles = cached_titles(&state);
+    assert_eq!(titles.len(), MAX_PAGES + 2);
+    assert!(titles.contains(&"Add SSO".to_string()));
+
+    // Nor may they be older than the newest one fetched
+    let conn = state.db.lock().unwrap();
+    let sync = sync_state(&conn, "project-1").unwrap().unwrap();
+    assert_eq!(sync.last_updated_at.as_deref(), Some("2025-03-02T10:00:00.000Z"));
+}
+
+#[test]
+fn test_cached_issues_are_readable_offline() {
+    let server = MockServer::start();
+    server.respond_json(200, issues_page(vec![issue("issue-1", "Fix login", "2025-03-01T10:00:00.000Z", None)]));
+    let state = linear_state(server.url());
+    block_on(sync_project_issues(&state, "project-1", false)).unwrap();
+
+    state
+        .db
+        .lock()
+        .unwrap()
+        .execute(
+            "UPDATE integrations SET connection_config = ?1",
+            params![json!({ "api_key": "key", "api_base_url": unreachable_url() }).to_string()],
+        )
+        .unwrap();
+    assert!(block_on(sync_project_issues(&state, "project-1", false)).is_err());
+    assert_eq!(cached_titles(&state), vec!["Fix login"]);
+
+    // Projects that were never synced are empty rather than an error
+    let conn = state.db.lock().unwrap();
+    let cached = cached_project_issues(&conn, "project-2").unwrap();
+    assert!(cached.issues.is_empty());
+    assert_eq!(cached.synced_at, None);
+}
@@ -0,0 +1,126 @@
+//! Checks that the Linear issues timeboxes link to still exist, and are still in the
+//! project the timebox belongs to
+
+use super::client::LinearIssueLocation;
+use crate::models::LinearIssueLinkStatus;
+use chrono::Local;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkedTimebox {
+    pub timebox_id: i64,
+    pub linear_issue_id: String,
+    pub linear_issue_identifier: Option<String>,
+    /// Linear's id of the timebox's saved project, if it has one
+    pub linear_project_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BrokenLink {
+    pub timebox_id: i64,
+    pub linear_issue_identifier: Option<String>,
+    pub status: LinearIssueLinkStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LinkValidationResult {
+    pub checked: usize,
+    pub broken: Vec<BrokenLink>,
+    pub checked_at: String,
+}
+
+/// Timeboxes linked to an issue, deleted ones aside
+pub fn linked_timeboxes(conn: &Connection) -> Result<Vec<LinkedTimebox>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"SELECT t.id, t.linear_issue_id, t.linear_issue_identifier, p.linear_project_id
+               FROM timeboxes t
+               LEFT JOIN linear_projects p ON p.id = t.linear_project_id
+               WHERE t.deleted_at IS NULL AND t.linear_issue_id IS NOT NULL
+               ORDER BY t.id"#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let linked = stmt
+        .query_map([], |row| {
+            Ok(LinkedTimebox {
+                timebox_id: row.get(0)?,
+                linear_issue_id: row.get(1)?,
+                linear_issue_identifier: row.get(2)?,
+                linear_project_id: row.get(3)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(linked)
+}
+
+/// What became of the linked issue, `issue` being `None` when Linear didn't return it
+pub fn link_status(linked: &LinkedTimebox, issue: Option<&LinearIssueLocation>) -> LinearIssueLinkStatus {
+    let Some(issue) = issue.filter(|i| i.trashed != Some(true)) else {
+        return LinearIssueLinkStatus::Deleted;
+    };
+    if issue.archived_at.is_some() {
+        return LinearIssueLinkStatus::Archived;
+    }
+    match &linked.linear_project_id {
+        Some(project_id) if issue.project.as_ref().map(|p| &p.id) != Some(project_id) => LinearIssueLinkStatus::Moved,
+        _ => LinearIssueLinkStatus::Ok,
+    }
+}
+
+/// Saves each link's status. Issues that still exist also refresh the timebox's identifier
+/// and URL, which change when an issue moves to another team. Timeboxes relinked since
+/// `linked` was read are left alone.
+pub fn store_link_checks(
+    conn: &Connection,
+    linked: &[LinkedTimebox],
+    issues: &[LinearIssueLocation],
+) -> Result<LinkValidationResult, String> {
+    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let by_id: HashMap<&str, &LinearIssueLocation> = issues.iter().map(|i| (i.id.as_str(), i)).collect();
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let mut result = LinkValidationResult { checked: 0, broken: Vec::new(), checked_at: now.clone() };
+
+    for link in linked {
+        let issue = by_id.get(link.linear_issue_id.as_str()).copied();
+        let status = link_status(link, issue);
+        let updated = tx
+            .execute(
+                r#"UPDATE timeboxes SET
+                     linear_issue_link_status = ?1,
+                     linear_issue_checked_at = ?2,
+                     linear_issue_identifier = COALESCE(?3, linear_issue_identifier),
+                     linear_issue_url = COALESCE(?4, linear_issue_url)
+                   WHERE id = ?5 AND linear_issue_id = ?6"#,
+                params![
+                    status.as_str(),
+                    now,
+                    issue.map(|i| &i.identifier),
+                    issue.map(|i| &i.url),
+                    link.timebox_id,
+                    link.linear_issue_id,
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        if updated == 0 {
+            continue;
+        }
+
+        result.checked += 1;
+        if status != LinearIssueLinkStatus::Ok {
+            result.broken.push(BrokenLink {
+                timebox_id: link.timebox_id,
+                linear_issue_identifier: issue.map(|i| i.identifier.clone()).or_else(|| link.linear_issue_identifier.clone()),
+                status,
+            });
+        }
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(result)
+}
@@ -0,0 +1,137 @@
+use super::link_validation::{linked_timeboxes, BrokenLink};
+use crate::commands::validate_issue_links;
+use crate::core::TimeboxService;
+use crate::mock_server::{unreachable_url, MockServer};
+use crate::models::{CreateTimeboxRequest, LinearIssueLinkStatus};
+use crate::state::AppState;
+use crate::test_support::{graphql_page, linear_state, timebox_request};
+use serde_json::{json, Value};
+use tauri::async_runtime::block_on;
+
+fn setup_state(api_base_url: String) -> AppState {
+    let state = linear_state(api_base_url);
+    state
+        .db
+        .lock()
+        .unwrap()
+        .execute(
+            "INSERT INTO linear_projects (linear_project_id, linear_team_id, name) VALUES ('project-1', 'team-1', 'Roadmap')",
+            [],
+        )
+        .unwrap();
+    state
+}
+
+fn link_timebox(state: &AppState, issue_id: &str, identifier: &str, in_project: bool) -> i64 {
+    let conn = state.db.lock().unwrap();
+    let project_id: Option<i64> = if in_project {
+        Some(conn.query_row("SELECT id FROM linear_projects", [], |row| row.get(0)).unwrap())
+    } else {
+        None
+    };
+    TimeboxService::new(&conn)
+        .create(&CreateTimeboxRequest {
+            intention: format!("Work on {}", identifier),
+            intended_duration: 30,
+            linear_project_id: project_id,
+            linear_issue_id: Some(issue_id.to_string()),
+            linear_issue_identifier: Some(identifier.to_string()),
+            linear_issue_url: Some(format!("https://linear.app/acme/issue/{}", identifier)),
+            ..timebox_request()
+        })
+        .unwrap()
+        .id
+}
+
+fn location(id: &str, identifier: &str, project_id: Option<&str>, archived: bool, trashed: bool) -> Value {
+    json!({
+        "id": id,
+        "identifier": identifier,
+        "url": format!("https://linear.app/acme/issue/{}", identifier),
+        "archivedAt": if archived { json!("2025-03-04T10:00:00.000Z") } else { Value::Null },
+        "trashed": trashed,
+        "project": project_id.map(|id| json!({ "id": id, "name": "Project" }))
+    })
+}
+
+#[test]
+fn test_validate_issue_links() {
+    let server = MockServer::start();
+    server.respond_json(
+        200,
+        graphql_page(
+            &["issues"],
+            vec![
+                location("issue-1", "ENG-1", Some("project-1"), false, false),
+                // Moved to another team, so it has a new identifier
+                location("issue-2", "OPS-7", Some("project-1"), false, false),
+                location("issue-3", "ENG-3", Some("project-1"), true, false),
+                location("issue-4", "ENG-4", Some("project-2"), false, false),
+                location("issue-5", "ENG-5", Some("project-1"), false, true),
+                location("issue-7", "ENG-7", Some("project-2"), false, false),
+            ],
+        ),
+    );
+    let state = setup_state(server.url());
+    let ok = link_timebox(&state, "issue-1", "ENG-1", true);
+    let renamed = link_timebox(&state, "issue-2", "ENG-2", true);
+    let archived = link_timebox(&state, "issue-3", "ENG-3", true);
+    let moved = link_timebox(&state, "issue-4", "ENG-4", true);
+    let trashed = link_timebox(&state, "issue-5", "ENG-5", true);
+    let deleted = link_timebox(&state, "issue-6", "ENG-6", true);
+    // Without a project there is nothing to be moved out of
+    let unfiled = link_timebox(&state, "issue-7", "ENG-7", false);
+
+    let result = block_on(validate_issue_links(&state)).unwrap();
+    assert_eq!(result.checked, 7);
+    let broken = |timebox_id, identifier: &str, status| BrokenLink {
+        timebox_id,
+        linear_issue_identifier: Some(identifier.to_string()),
+        status,
+    };
+    assert_eq!(
+        result.broken,
+        vec![
+            broken(archived, "ENG-3", LinearIssueLinkStatus::Archived),
+            broken(moved, "ENG-4", LinearIssueLinkStatus::Moved),
+            broken(trashed, "ENG-5", LinearIssueLinkStatus::Deleted),
+            broken(deleted, "ENG-6", LinearIssueLinkStatus::Deleted),
+        ]
+    );
+
+    let request = &server.requests()[0];
+    assert_eq!(
+        request.json()["variables"]["ids"],
+        json!(["issue-1", "issue-2", "issue-3", "issue-4", "issue-5", "issue-6", "issue-7"])
+    );
+    assert!(request.graphql_query().contains("includeArchived: true"));
+
+    let conn = state.db.lock().unwrap();
+    let service = TimeboxService::new(&conn);
+    let timebox = service.get(ok).unwrap();
+    assert_eq!(timebox.linear_issue_link_status, Some(LinearIssueLinkStatus::Ok));
+    assert_eq!(timebox.linear_issue_checked_at, Some(result.checked_at.clone()));
+    let timebox = service.get(renamed).unwrap();
+    assert_eq!(timebox.linear_issue_identifier.as_deref(), Some("OPS-7"));
+    assert_eq!(timebox.linear_issue_url.as_deref(), Some("https://linear.app/acme/issue/OPS-7"));
+    assert_eq!(service.get(unfiled).unwrap().linear_issue_link_status, Some(LinearIssueLinkStatus::Ok));
+    // A deleted issue keeps the identifier the timebox knew it by
+    assert_eq!(service.get(deleted).unwrap().linear_issue_identifier.as_deref(), Some("ENG-6"));
+}
+
+#[test]
+fn test_validate_without_links_skips_linear() {
+    let state = setup_state(unreachable_url());
+    let result = block_on(validate_issue_links(&state)).unwrap();
+    assert_eq!(result.checked, 0);
+    assert!(result.broken.is_empty());
+
+    link_timebox(&state, "issue-1", "ENG-1", true);
+    assert!(block_on(validate_issue_links(&state)).is_err());
+    let conn = state.db.lock().unwrap();
+    let linked = linked_timeboxes(&conn).unwrap();
+    assert_eq!(linked.len(), 1);
+    assert_eq!(linked[0].linear_project_id.as_deref(), Some("project-1"));
+    // A failed check leaves the link unchecked
+    assert!(TimeboxService::new(&conn).get(linked[0].timebox_id).unwrap().linear_issue_link_status.is_none());
+}
@@ -0,0 +1,232 @@
+use super::http::{ApiError, ApiErrorKind};
+use super::registry::ProviderHook;
+use super::provider::{api_base_url, api_base_url_field, api_url, config_str, ConfigField, ConfigFieldKind, ConnectionTestResult, IntegrationProvider, ProviderDescriptor, ProviderFuture, ProviderTask};
+use crate::core::{OutboxAction, TimeboxWithSessions};
+use crate::models::Integration;
+use comment::{time_spent_comment, TimeCommentMode, TIME_COMMENT_KEY, TIME_COMMENT_OPTIONS};
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::Value;
+
+pub mod client;
+pub mod comment;
+pub mod issue_cache;
+pub mod link_validation;
+pub mod planning;
+pub mod project_sync;
+pub mod state_rules;
+
+#[cfg(test)]
+mod comment_tests;
+#[cfg(test)]
+mod issue_cache_tests;
+#[cfg(test)]
+mod link_validation_tests;
+#[cfg(test)]
+mod planning_tests;
+#[cfg(test)]
+mod project_sync_tests;
+#[cfg(test)]
+mod state_rules_tests;
+
+pub use client::LinearClient;
+
+pub const LINEAR_INTEGRATION_TYPE: &str = "linear";
+pub const DEFAULT_LINEAR_API_BASE_URL: &str = "https://api.linear.app";
+
+/// GraphQL endpoint under the configured base URL
+pub fn linear_graphql_url(api_base_url: Option<&str>) -> String {
+    api_url(api_base_url, DEFAULT_LINEAR_API_BASE_URL, "/graphql")
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinearTestResult {
+    pub success: bool,
+    pub user_name: Option<String>,
+    pub user_email: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Checks an API key by fetching its user. A refused key is a failed test rather than
+/// an error.
+pub async fn test_linear_api_key(api_key: &str, api_base_url: Option<&str>) -> Result<LinearTestResult, ApiError> {
+    match LinearClient::new(api_key, api_base_url).viewer().await {
+        Ok(viewer) => Ok(LinearTestResult {
+            success: true,
+            user_name: Some(viewer.name),
+            user_email: Some(viewer.email),
+            error: None,
+        }),
+        Err(e) if e.is_rejection() => Ok(LinearTestResult {
+            success: false,
+            user_name: None,
+            user_email: None,
+            error: Some(e.to_string()),
+        }),
+        Err(e) => Err(e),
+    }
+}
+
+pub struct LinearProvider;
+
+impl IntegrationProvider for LinearProvider {
+    fn descriptor(&self) -> ProviderDescriptor {
+        ProviderDescriptor {
+            integration_type: LINEAR_INTEGRATION_TYPE,
+            name: "Linear",
+            description: "Link timeboxes to Linear issues and browse your projects",
+            allow_multiple: false,
+            config_schema: vec![
+                ConfigField {
+                    key: "api_key",
+                    label: "API Key",
+                    kind: ConfigFieldKind::Secret,
+                    required: true,
+                    placeholder: Some("lin_api_..."),
+                    help: Some("Create one in Linear under Settings → API"),
+                    options: &[],
+                },
+                ConfigField {
+                    key: "open_in_native_app",
+                    label: "Open issues in the Linear app",
+                    kind: ConfigFieldKind::Boolean,
+                    required: false,
+                    placeholder: None,
+                    help: None,
+                    options: &[],
+                },
+                ConfigField {
+                    key: TIME_COMMENT_KEY,
+                    label: "Post time spent on the issue",
+                    kind: ConfigFieldKind::Choice,
+                    required: false,
+                    placeholder: None,
+                    help: Some("Comments on the linked issue with the sessions, time spent and notes"),
+                    options: TIME_COMMENT_OPTIONS,
+                },
+                api_base_url_field(DEFAULT_LINEAR_API_BASE_URL),
+            ],
+        }
+    }
+
+    fn test_connection<'a>(&'a self, config: &'a Value) -> ProviderFuture<'a, Result<ConnectionTestResult, String>> {
+        Box::pin(async move {
+            let result = test_linear_api_key(config_str(config, "api_key")?, api_base_url(config)).await?;
+            Ok(ConnectionTestResult {
+                success: result.success,
+                account_name: result.user_name,
+                error: result.error,
+            })
+        })
+    }
+
+    fn on_timebox_started(
+        &self,
+        conn: &Connection,
+        _integration: &Integration,
+        timebox: &TimeboxWithSessions,
+    ) -> Result<Vec<OutboxAction>, String> {
+        Ok(state_transition(conn, ProviderHook::Started, timebox)?.into_iter().collect())
+    }
+
+    fn on_timebox_stopped(
+        &self,
+        conn: &Connection,
+        integration: &Integration,
+        timebox: &TimeboxWithSessions,
+    ) -> Result<Vec<OutboxAction>, String> {
+        let mode = TimeCommentMode::from_config(&integration.connection_config);
+        Ok(time_spent_comment(mode, ProviderHook::Stopped, timebox)
+            .into_iter()
+            .chain(state_transition(conn, ProviderHook::Stopped, timebox)?)
+            .collect())
+    }
+
+    fn on_timebox_finished(
+        &self,
+        conn: &Connection,
+        integration: &Integration,
+        timebox: &TimeboxWithSessions,
+    ) -> Result<Vec<OutboxAction>, String> {
+        let mode = TimeCommentMode::from_config(&integration.connection_config);
+        Ok(time_spent_comment(mode, ProviderHook::Finished, timebox)
+            .into_iter()
+            .chain(state_transition(conn, ProviderHook::Finished, timebox)?)
+            .collect())
+    }
+
+    fn deliver<'a>(
+        &'a self,
+        integration: &'a Integration,
+        action: &'a OutboxAction,
+    ) -> ProviderFuture<'a, Result<(), ApiError>> {
+        Box::pin(async move {
+            let client = LinearClient::from_config(&integration.connection_config)
+                .map_err(|e| ApiError::new(ApiErrorKind::Auth, e))?;
+
+            match action {
+                OutboxAction::LinearPostComment { issue_id, body, comment_id, .. } => {
+                    match client.create_comment(issue_id, body, comment_id.as_deref()).await {
+                        Ok(_) => Ok(()),
+                        // A retry of a comment whose first attempt timed out after Linear
+                        // created it is turned down for reusing the id
+                        Err(e) if e.kind == ApiErrorKind::Rejected => match comment_id {
+                            Some(id) if client.comment_exists(id).await? => Ok(()),
+                            _ => Err(e),
+                        },
+                        Err(e) => Err(e),
+                    }
+                }
+                OutboxAction::LinearUpdateIssueState { issue_id, state_id, .. } => {
+                    match client.update_issue_state(issue_id, state_id).await? {
+                        true => Ok(()),
+                        false => Err(ApiError::new(ApiErrorKind::Rejected, "Linear did not update the issue state")),
+                    }
+                }
+                _ => Err(ApiError::new(ApiErrorKind::Rejected, format!("Linear can't deliver {}", action.name()))),
+            }
+        })
+    }
+
+    /// Open issues assigned to the API key's user
+    fn fetch_tasks<'a>(&'a self, integration: &'a Integration) -> ProviderFuture<'a, Result<Vec<ProviderTask>, String>> {
+        Box::pin(async move {
+            Ok(LinearClient::from_config(&integration.connection_config)?
+                .assigned_issues()
+                .await?
+                .into_iter()
+                .map(|issue| ProviderTask {
+                    id: issue.id,
+                    title: issue.title,
+                    url: Some(issue.url),
+                    identifier: Some(issue.identifier),
+                })
+                .collect())
+        })
+    }
+}
+
+/// The state change the timebox's project asks for on this event, if any. A rule whose
+/// state can't be resolved is logged rather than failing the hook.
+///
+/// Like time comments, a change is queued once per session, or once per timebox for
+/// finishing, so a repeated event can't move the issue again.
+fn state_transition(conn: &Connection, event: ProviderHook, timebox: &TimeboxWithSessions) -> Result<Option<OutboxAction>, String> {
+    let transition = state_rules::plan_transition(conn, event, &timebox.timebox)?;
+    if let (Some(_), Some(reason)) = (&transition.state_name, &transition.skipped_reason) {
+        eprintln!("Not moving the Linear issue of timebox {}: {}", timebox.timebox.id, reason);
+    }
+
+    let id = timebox.timebox.id;
+    let dedupe_key = match event {
+        ProviderHook::Finished => format!("issue_state:{}:finished", id),
+        _ => {
+            let last_session = timebox.sessions.iter().filter(|s| s.cancelled_at.is_none()).max_by_key(|s| s.id);
+            match last_session {
+                Some(session) => format!("issue_state:{}:session:{}:{}", id, session.id, event.as_str()),
+                None => format!("issue_state:{}:{}", id, event.as_str()),
+            }
+        }
+    };
+    Ok(transition.action(Some(dedupe_key)))
+}
@@ -0,0 +1,138 @@
+//! Planning from Linear: timeboxes created in bulk from issues, sized by their estimate
+
+use crate::core::TimeboxService;
+use crate::models::{CreateTimeboxRequest, Timebox};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Config key of the Linear integration holding the points-to-minutes table
+pub const ESTIMATE_MINUTES_KEY: &str = "estimate_minutes";
+
+/// Duration of timeboxes created from issues without an estimate
+pub const DEFAULT_UNESTIMATED_MINUTES: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EstimateMinutes {
+    pub points: f64,
+    pub minutes: i64,
+}
+
+pub const DEFAULT_ESTIMATE_MINUTES: &[EstimateMinutes] = &[
+    EstimateMinutes { points: 0.0, minutes: 15 },
+    EstimateMinutes { points: 1.0, minutes: 30 },
+    EstimateMinutes { points: 2.0, minutes: 60 },
+    EstimateMinutes { points: 3.0, minutes: 90 },
+    EstimateMinutes { points: 5.0, minutes: 150 },
+    EstimateMinutes { points: 8.0, minutes: 240 },
+];
+
+/// The points-to-minutes table from the Linear integration's config, sorted by points.
+/// Rows that make no sense are ignored, and without any valid row the default applies.
+pub fn estimate_table(config: &Value) -> Vec<EstimateMinutes> {
+    let mut table: Vec<EstimateMinutes> = config
+        .get(ESTIMATE_MINUTES_KEY)
+        .and_then(|v| serde_json::from_value::<Vec<EstimateMinutes>>(v.clone()).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|row| row.points.is_finite() && row.points >= 0.0 && row.minutes > 0)
+        .collect();
+    if table.is_empty() {
+        return DEFAULT_ESTIMATE_MINUTES.to_vec();
+    }
+    table.sort_by(|a, b| a.points.total_cmp(&b.points));
+    table.dedup_by(|a, b| a.points == b.points);
+    table
+}
+
+/// Minutes for an estimate: its row in the table, or the next larger one when it falls
+/// between rows. Estimates past the last row scale it, and issues without an estimate
+/// get `unestimated_minutes`.
+pub fn estimate_to_minutes(table: &[EstimateMinutes], estimate: Option<f64>, unestimated_minutes: i64) -> i64 {
+    let Some(estimate) = estimate.filter(|e| e.is_finite() && *e >= 0.0) else {
+        return unestimated_minutes;
+    };
+    if let Some(row) = table.iter().find(|row| row.points >= estimate) {
+        return row.minutes;
+    }
+    match table.last() {
+        Some(last) if last.points > 0.0 => (last.minutes as f64 * estimate / last.points).round() as i64,
+        Some(last) => last.minutes,
+        None => unestimated_minutes,
+    }
+}
+
+/// An issue picked for planning, as the frontend sends it
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlannedIssue {
+    pub id: String,
+    pub identifier: String,
+    pub title: String,
+    pub url: String,
+    pub estimate: Option<f64>,
+    /// Linear's id of the issue's project, linked when the project is saved
+    pub project_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedTimeboxes {
+    pub created: Vec<Timebox>,
+    /// Identifiers of issues that already had an open timebox
+    pub skipped: Vec<String>,
+}
+
+/// Creates a timebox per issue in one transaction, skipping issues that already have
+/// one that isn't finished
+pub fn create_timeboxes_from_issues(
+    conn: &Connection,
+    issues: &[PlannedIssue],
+    table: &[EstimateMinutes],
+    unestimated_minutes: i64,
+) -> Result<PlannedTimeboxes, String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let service = TimeboxService::new(&tx);
+    let mut planned = PlannedTimeboxes { created: Vec::new(), skipped: Vec::new() };
+
+    for issue in issues {
+        let has_open_timebox: bool = tx
+            .query_row(
+                r#"SELECT EXISTS (SELECT 1 FROM timeboxes
+                   WHERE linear_issue_id = ?1 AND deleted_at IS NULL AND archived_at IS NULL
+                     AND status IN ('not_started', 'in_progress', 'paused'))"#,
+                params![issue.id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if has_open_timebox {
+            planned.skipped.push(issue.identifier.clone());
+            continue;
+        }
+
+        let linear_project_id: Option<i64> = match &issue.project_id {
+            Some(project_id) => tx
+                .query_row(
+                    "SELECT id FROM linear_projects WHERE linear_project_id = ?1 AND deleted_at IS NULL",
+                    params![project_id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?,
+            None => None,
+        };
+
+        let request = CreateTimeboxRequest {
+            intention: issue.title.clone(),
+            intended_duration: estimate_to_minutes(table, issue.estimate, unestimated_minutes),
+            notes: None,
+            linear_project_id,
+            linear_issue_id: Some(issue.id.clone()),
+            linear_issue_identifier: Some(issue.identifier.clone()),
+            linear_issue_url: Some(issue.url.clone()),
+            tags: Vec::new(),
+        };
+        planned.created.push(service.create(&request)?);
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(planned)
+}
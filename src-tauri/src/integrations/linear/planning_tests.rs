@@ -0,0 +1,148 @@
+use super::planning::{
+    create_timeboxes_from_issues, estimate_table, estimate_to_minutes, EstimateMinutes, PlannedIssue,
+    DEFAULT_ESTIMATE_MINUTES,
+};
+use super::LinearClient;
+use crate::core::TimeboxService;
+use crate::mock_server::MockServer;
+use crate::models::CreateTimeboxRequest;
+use crate::test_support::{test_db, timebox_request};
+use serde_json::{json, Value};
+use tauri::async_runtime::block_on;
+
+fn planned(id: &str, estimate: Option<f64>, project_id: Option<&str>) -> PlannedIssue {
+    PlannedIssue {
+        id: id.to_string(),
+        identifier: format!("ENG-{}", id.trim_start_matches("issue-")),
+        title: format!("Issue {}", id),
+        url: format!("https://linear.app/acme/issue/{}", id),
+        estimate,
+        project_id: project_id.map(str::to_string),
+    }
+}
+
+fn planning_issue(id: &str, estimate: Option<f64>) -> Value {
+    json!({
+        "id": id,
+        "identifier": "ENG-1",
+        "title": "Fix login",
+        "url": "https://linear.app/acme/issue/ENG-1",
+        "priority": 2,
+        "priorityLabel": "High",
+        "estimate": estimate,
+        "state": { "id": "state-1", "name": "Todo", "color": "#e2e2e2", "type": "unstarted" },
+        "project": { "id": "project-1", "name": "Roadmap" }
+    })
+}
+
+#[test]
+fn test_estimate_to_minutes() {
+    let table = DEFAULT_ESTIMATE_MINUTES;
+    assert_eq!(estimate_to_minutes(table, None, 45), 45);
+    assert_eq!(estimate_to_minutes(table, Some(0.0), 45), 15);
+    assert_eq!(estimate_to_minutes(table, Some(3.0), 45), 90);
+    // Between rows rounds up to the next one
+    assert_eq!(estimate_to_minutes(table, Some(4.0), 45), 150);
+    // Past the table the last row is scaled
+    assert_eq!(estimate_to_minutes(table, Some(13.0), 45), 390);
+
+    // Configured tables are sorted and rows that make no sense are dropped
+    let config = json!({ "estimate_minutes": [
+        { "points": 4.0, "minutes": 120 },
+        { "points": 1.0, "minutes": 25 },
+        { "points": 2.0, "minutes": 0 },
+        { "points": -1.0, "minutes": 10 }
+    ] });
+    let table = estimate_table(&config);
+    assert_eq!(
+        table,
+        vec![EstimateMinutes { points: 1.0, minutes: 25 }, EstimateMinutes { points: 4.0, minutes: 120 }]
+    );
+    assert_eq!(estimate_to_minutes(&table, Some(2.0), 45), 120);
+
+    assert_eq!(estimate_table(&json!({})), DEFAULT_ESTIMATE_MINUTES.to_vec());
+    assert_eq!(estimate_table(&json!({ "estimate_minutes": "fast" })), DEFAULT_ESTIMATE_MINUTES.to_vec());
+}
+
+#[test]
+fn test_create_timeboxes_from_issues() {
+    let conn = test_db();
+    conn.execute(
+        "INSERT INTO linear_projects (linear_project_id, linear_team_id, name) VALUES ('project-1', 'team-1', 'Roadmap')",
+        [],
+    )
+    .unwrap();
+    let local_project_id = conn.last_insert_rowid();
+
+    // issue-3 is already planned
+    TimeboxService::new(&conn)
+        .create(&CreateTimeboxRequest {
+            intention: "Issue 3".to_string(),
+            intended_duration: 30,
+            linear_issue_id: Some("issue-3".to_string()),
+            linear_issue_identifier: Some("ENG-3".to_string()),
+            ..timebox_request()
+        })
+        .unwrap();
+
+    let issues = vec![
+        planned("issue-1", Some(2.0), Some("project-1")),
+        planned("issue-2", None, Some("unsaved-project")),
+        planned("issue-3", Some(1.0), None),
+    ];
+    let result = create_timeboxes_from_issues(&conn, &issues, DEFAULT_ESTIMATE_MINUTES, 45).unwrap();
+    assert_eq!(result.skipped, vec!["ENG-3"]);
+    assert_eq!(result.created.len(), 2);
+
+    let first = &result.created[0];
+    assert_eq!(first.intention, "Issue issue-1");
+    assert_eq!(first.intended_duration, 60);
+    assert_eq!(first.linear_project_id, Some(local_project_id));
+    assert_eq!(first.linear_issue_id.as_deref(), Some("issue-1"));
+    assert_eq!(first.linear_issue_identifier.as_deref(), Some("ENG-1"));
+    assert_eq!(first.linear_issue_url.as_deref(), Some("https://linear.app/acme/issue/issue-1"));
+
+    let second = &result.created[1];
+    assert_eq!(second.intended_duration, 45);
+    assert_eq!(second.linear_project_id, None);
+
+    // Planning again skips everything that is now open
+    let result = create_timeboxes_from_issues(&conn, &issues, DEFAULT_ESTIMATE_MINUTES, 45).unwrap();
+    assert!(result.created.is_empty());
+    assert_eq!(result.skipped, vec!["ENG-1", "ENG-2", "ENG-3"]);
+}
+
+#[test]
+fn test_my_issues_and_active_cycle() {
+    let server = MockServer::start();
+    let page = |nodes: Vec<Value>| json!({ "nodes": nodes, "pageInfo": { "hasNextPage": false, "endCursor": null } });
+    server
+        .respond_json(200, json!({ "data": { "viewer": { "assignedIssues": page(vec![planning_issue("issue-1", Some(3.0))]) } } }))
+        .respond_json(200, json!({ "data": { "team": { "activeCycle": null } } }))
+        .respond_json(
+            200,
+            json!({ "data": { "team": { "activeCycle": {
+                "id": "cycle-7", "number": 7, "name": null,
+                "startsAt": "2025-03-03T00:00:00.000Z", "endsAt": "2025-03-17T00:00:00.000Z"
+            } } } }),
+        )
+        .respond_json(200, json!({ "data": { "cycle": { "issues": page(vec![planning_issue("issue-2", None)]) } } }));
+    let client = LinearClient::new("key", Some(&server.url()));
+
+    let issues = block_on(client.assigned_issues()).unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].estimate, Some(3.0));
+    assert_eq!(issues[0].priority_label.as_deref(), Some("High"));
+    assert_eq!(issues[0].project.as_ref().unwrap().id, "project-1");
+
+    assert!(block_on(client.active_cycle("team-1")).unwrap().is_none());
+
+    let cycle = block_on(client.active_cycle("team-1")).unwrap().unwrap();
+    assert_eq!(cycle.number, 7);
+    assert_eq!(cycle.issues.len(), 1);
+    assert_eq!(cycle.issues[0].id, "issue-2");
+
+    let requests = server.requests();
+    assert_eq!(requests[2].json()["variables"]["teamId"], "team-1");
+    assert_eq!(requests[3].json()["variables"]["cycleId"], "cycle-7");
+}
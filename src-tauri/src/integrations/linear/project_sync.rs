@@ -0,0 +1,163 @@
+//! Keeps saved Linear projects in step with Linear: name, description and state are
+//! refreshed, and projects archived or completed upstream are archived locally. That
+//! happens once, when the project closes, so a project unarchived here stays active.
+
+use super::client::LinearProjectStatus;
+use chrono::Local;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Project state Linear gives finished projects
+pub const COMPLETED_STATE: &str = "completed";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectChange {
+    pub linear_project_id: String,
+    pub name: String,
+    /// Fields that changed: "name", "description" and/or "state"
+    pub fields: Vec<&'static str>,
+    /// Archived locally because it was archived or completed in Linear
+    pub archived: bool,
+}
+
+impl ProjectChange {
+    /// One line for the log
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.fields.is_empty() {
+            parts.push(format!("updated {}", self.fields.join(", ")));
+        }
+        if self.archived {
+            parts.push("archived, as it was archived or completed in Linear".to_string());
+        }
+        format!("Linear project \"{}\": {}", self.name, parts.join("; "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectRefreshResult {
+    pub checked: usize,
+    pub changed: Vec<ProjectChange>,
+    /// Saved projects Linear no longer returns, e.g. deleted ones. They are left as is.
+    pub missing: Vec<String>,
+    pub refreshed_at: String,
+}
+
+// The fields a refresh compares
+struct LocalProject {
+    name: String,
+    description: Option<String>,
+    state: Option<String>,
+    archived_at: Option<String>,
+    linear_archived_at: Option<String>,
+}
+
+/// Linear ids of the saved projects, deleted ones aside
+pub fn saved_project_ids(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT linear_project_id FROM linear_projects WHERE deleted_at IS NULL ORDER BY name")
+        .map_err(|e| e.to_string())?;
+
+    let ids = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(ids)
+}
+
+/// Whether the project was archived or completed in Linear since the last refresh, so
+/// it shouldn't stay active here
+fn closed_upstream_since(local: &LocalProject, upstream: &LinearProjectStatus) -> bool {
+    let newly_archived = upstream.archived_at.is_some() && local.linear_archived_at != upstream.archived_at;
+    let newly_completed = upstream.state.as_deref() == Some(COMPLETED_STATE) && local.state != upstream.state;
+    newly_archived || newly_completed
+}
+
+/// Applies what Linear returned for the saved projects `ids`. Projects closed upstream
+/// since the last refresh are archived and stop being active timebox projects.
+pub fn store_refreshed_projects(
+    conn: &Connection,
+    ids: &[String],
+    projects: &[LinearProjectStatus],
+) -> Result<ProjectRefreshResult, String> {
+    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let by_id: HashMap<&str, &LinearProjectStatus> = projects.iter().map(|p| (p.id.as_str(), p)).collect();
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let mut result = ProjectRefreshResult { checked: 0, changed: Vec::new(), missing: Vec::new(), refreshed_at: now.clone() };
+
+    for id in ids {
+        let local = tx
+            .query_row(
+                "SELECT name, description, state, archived_at, linear_archived_at FROM linear_projects WHERE linear_project_id = ?1 AND deleted_at IS NULL",
+                params![id],
+                |row| {
+                    Ok(LocalProject {
+                        name: row.get(0)?,
+                        description: row.get(1)?,
+                        state: row.get(2)?,
+                        archived_at: row.get(3)?,
+                        linear_archived_at: row.get(4)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        // Deleted locally since the ids were read
+        let Some(local) = local else {
+            continue;
+        };
+        let Some(upstream) = by_id.get(id.as_str()) else {
+            result.missing.push(id.clone());
+            continue;
+        };
+        result.checked += 1;
+
+        let mut fields = Vec::new();
+        if local.name != upstream.name {
+            fields.push("name");
+        }
+        if local.description != upstream.description {
+            fields.push("description");
+        }
+        if local.state != upstream.state {
+            fields.push("state");
+        }
+        let archive = local.archived_at.is_none() && closed_upstream_since(&local, upstream);
+        // Kept even when nothing else changed, it's what the next refresh compares to
+        let archived_upstream_changed = local.linear_archived_at != upstream.archived_at;
+        if fields.is_empty() && !archive && !archived_upstream_changed {
+            continue;
+        }
+
+        tx.execute(
+            r#"UPDATE linear_projects SET
+                 name = ?1,
+                 description = ?2,
+                 state = ?3,
+                 archived_at = CASE WHEN ?4 THEN ?5 ELSE archived_at END,
+                 is_active_timebox_project = CASE WHEN ?4 THEN 0 ELSE is_active_timebox_project END,
+                 linear_archived_at = ?6,
+                 updated_at = ?5
+               WHERE linear_project_id = ?7"#,
+            params![upstream.name, upstream.description, upstream.state, archive, now, upstream.archived_at, id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        if fields.is_empty() && !archive {
+            continue;
+        }
+
+        result.changed.push(ProjectChange {
+            linear_project_id: id.clone(),
+            name: upstream.name.clone(),
+            fields,
+            archived: archive,
+        });
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(result)
+}
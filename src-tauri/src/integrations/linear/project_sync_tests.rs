@@ -0,0 +1,150 @@
+use super::project_sync::ProjectChange;
+use crate::commands::refresh_saved_projects;
+use crate::mock_server::{unreachable_url, MockServer};
+use crate::state::AppState;
+use crate::test_support::{graphql_page, linear_state};
+use rusqlite::params;
+use serde_json::{json, Value};
+use tauri::async_runtime::block_on;
+
+fn save_project(state: &AppState, id: &str, name: &str, state_name: &str) {
+    state
+        .db
+        .lock()
+        .unwrap()
+        .execute(
+            r#"INSERT INTO linear_projects (linear_project_id, linear_team_id, name, description, state, is_active_timebox_project)
+               VALUES (?1, 'team-1', ?2, 'Description', ?3, 1)"#,
+            params![id, name, state_name],
+        )
+        .unwrap();
+}
+
+fn project(id: &str, name: &str, state: &str, archived: bool) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "description": "Description",
+        "state": state,
+        "archivedAt": if archived { json!("2025-03-04T10:00:00.000Z") } else { Value::Null }
+    })
+}
+
+fn projects_page(nodes: Vec<Value>) -> Value {
+    graphql_page(&["projects"], nodes)
+}
+
+// (name, state, is active, archived)
+fn local_project(state: &AppState, id: &str) -> (String, Option<String>, bool, bool) {
+    state
+        .db
+        .lock()
+        .unwrap()
+        .query_row(
+            "SELECT name, state, is_active_timebox_project, archived_at IS NOT NULL FROM linear_projects WHERE linear_project_id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_refresh_saved_projects() {
+    let server = MockServer::start();
+    server.respond_json(
+        200,
+        projects_page(vec![
+            project("project-1", "Roadmap", "started", false),
+            project("project-2", "Billing v2", "started", false),
+            project("project-3", "Onboarding", "completed", false),
+            project("project-4", "Search", "paused", true),
+        ]),
+    );
+    let state = linear_state(server.url());
+    save_project(&state, "project-1", "Roadmap", "started");
+    save_project(&state, "project-2", "Billing", "planned");
+    save_project(&state, "project-3", "Onboarding", "started");
+    save_project(&state, "project-4", "Search", "paused");
+    save_project(&state, "project-5", "Gone", "started");
+
+    let result = block_on(refresh_saved_projects(&state)).unwrap();
+    assert_eq!(result.checked, 4);
+    assert_eq!(result.missing, vec!["project-5"]);
+    assert_eq!(
+        result.changed,
+        vec![
+            ProjectChange {
+                linear_project_id: "project-2".to_string(),
+                name: "Billing v2".to_string(),
+                fields: vec!["name", "state"],
+                archived: false,
+            },
+            ProjectChange {
+                linear_project_id: "project-3".to_string(),
+                name: "Onboarding".to_string(),
+                fields: vec!["state"],
+                archived: true,
+            },
+            ProjectChange {
+                linear_project_id: "project-4".to_string(),
+                name: "Search".to_string(),
+                fields: vec![],
+                archived: true,
+            },
+        ]
+    );
+    assert_eq!(result.changed[0].describe(), "Linear project \"Billing v2\": updated name, state");
+
+    let variables = &server.requests()[0].json()["variables"];
+    assert_eq!(variables["ids"].as_array().unwrap().len(), 5);
+    assert!(server.requests()[0].graphql_query().contains("includeArchived: true"));
+
+    assert_eq!(local_project(&state, "project-1"), ("Roadmap".to_string(), Some("started".to_string()), true, false));
+    assert_eq!(local_project(&state, "project-2"), ("Billing v2".to_string(), Some("started".to_string()), true, false));
+    assert_eq!(local_project(&state, "project-3"), ("Onboarding".to_string(), Some("completed".to_string()), false, true));
+    assert_eq!(local_project(&state, "project-4"), ("Search".to_string(), Some("paused".to_string()), false, true));
+    // Not found upstream, so left alone
+    assert_eq!(local_project(&state, "project-5"), ("Gone".to_string(), Some("started".to_string()), true, false));
+}
+
+#[test]
+fn test_refresh_archives_closed_projects_once() {
+    let server = MockServer::start();
+    let completed = || projects_page(vec![project("project-1", "Roadmap", "completed", false)]);
+    server
+        .respond_json(200, completed())
+        .respond_json(200, completed())
+        .respond_json(200, completed())
+        .respond_json(200, projects_page(vec![project("project-1", "Roadmap", "completed", true)]));
+    let state = linear_state(server.url());
+    save_project(&state, "project-1", "Roadmap", "started");
+
+    assert!(block_on(refresh_saved_projects(&state)).unwrap().changed[0].archived);
+    // Already archived, so the next refresh has nothing to do
+    assert!(block_on(refresh_saved_projects(&state)).unwrap().changed.is_empty());
+
+    // Unarchived by hand, which later refreshes respect
+    state
+        .db
+        .lock()
+        .unwrap()
+        .execute("UPDATE linear_projects SET archived_at = NULL, is_active_timebox_project = 1", [])
+        .unwrap();
+    assert!(block_on(refresh_saved_projects(&state)).unwrap().changed.is_empty());
+    assert_eq!(local_project(&state, "project-1"), ("Roadmap".to_string(), Some("completed".to_string()), true, false));
+
+    // Until the project is archived in Linear too
+    assert!(block_on(refresh_saved_projects(&state)).unwrap().changed[0].archived);
+    assert!(local_project(&state, "project-1").3);
+}
+
+#[test]
+fn test_refresh_without_projects_skips_linear() {
+    let state = linear_state(unreachable_url());
+    let result = block_on(refresh_saved_projects(&state)).unwrap();
+    assert_eq!(result.checked, 0);
+
+    save_project(&state, "project-1", "Roadmap", "started");
+    assert!(block_on(refresh_saved_projects(&state)).is_err());
+    assert_eq!(local_project(&state, "project-1").0, "Roadmap");
+}
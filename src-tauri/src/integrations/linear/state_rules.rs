@@ -0,0 +1,304 @@
+//! Rules that move a timebox's Linear issue to another workflow state when the timebox
+//! starts, stops or finishes, configured per saved project
+
+use super::client::LinearTeamWorkflowState;
+use crate::core::OutboxAction;
+use crate::integrations::registry::ProviderHook;
+use crate::models::Timebox;
+use chrono::Local;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+
+const STATE_RULE_SELECT_COLUMNS: &str = "id, linear_project_id, event, state_name, state_type, created_at, updated_at";
+
+/// Type of the state a newly saved project moves its issues to when a timebox starts.
+/// The team's first state of this type is used, whatever it's called.
+pub const DEFAULT_STARTED_STATE_TYPE: &str = "started";
+
+/// Name shown for the default start rule until the team's states are cached
+pub const DEFAULT_STARTED_STATE: &str = "In Progress";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LinearStateRule {
+    pub id: i64,
+    /// Local id of the saved project
+    pub linear_project_id: i64,
+    pub event: ProviderHook,
+    /// Resolved to a state id of the project's team when the event happens
+    pub state_name: String,
+    /// Set for default rules, which follow the team's first state of this type rather
+    /// than `state_name`. The name is then the one that state had when last cached.
+    pub state_type: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl LinearStateRule {
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let event: String = row.get(2)?;
+        Ok(LinearStateRule {
+            id: row.get(0)?,
+            linear_project_id: row.get(1)?,
+            event: ProviderHook::from_str(&event),
+            state_name: row.get(3)?,
+            state_type: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        })
+    }
+}
+
+/// What an event would do to a timebox's issue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StateTransition {
+    pub event: ProviderHook,
+    pub issue_id: Option<String>,
+    pub issue_identifier: Option<String>,
+    pub state_name: Option<String>,
+    pub state_id: Option<String>,
+    /// Why the issue would be left alone, `None` when it would be moved
+    pub skipped_reason: Option<String>,
+}
+
+impl StateTransition {
+    fn skipped(event: ProviderHook, timebox: &Timebox, reason: impl Into<String>) -> Self {
+        StateTransition {
+            event,
+            issue_id: timebox.linear_issue_id.clone(),
+            issue_identifier: timebox.linear_issue_identifier.clone(),
+            state_name: None,
+            state_id: None,
+            skipped_reason: Some(reason.into()),
+        }
+    }
+
+    pub fn action(&self, dedupe_key: Option<String>) -> Option<OutboxAction> {
+        Some(OutboxAction::LinearUpdateIssueState {
+            issue_id: self.issue_id.clone()?,
+            state_id: self.state_id.clone()?,
+            dedupe_key,
+        })
+    }
+}
+
+pub fn state_rules(conn: &Connection, linear_project_id: i64) -> Result<Vec<LinearStateRule>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM linear_state_rules WHERE linear_project_id = ?1 ORDER BY id",
+            STATE_RULE_SELECT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rules = stmt
+        .query_map(params![linear_project_id], LinearStateRule::from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(rules)
+}
+
+/// Sets the state an event moves the project's issues to, or removes the rule for `None`.
+/// A rule set by name stops being a default rule.
+pub fn set_state_rule(
+    conn: &Connection,
+    linear_project_id: i64,
+    event: ProviderHook,
+    state_name: Option<&str>,
+) -> Result<(), String> {
+    match state_name.map(str::trim).filter(|name| !name.is_empty()) {
+        Some(state_name) => {
+            let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+            conn.execute(
+                r#"INSERT INTO linear_state_rules (linear_project_id, event, state_name, created_at, updated_at)
+                   VALUES (?1, ?2, ?3, ?4, ?4)
+                   ON CONFLICT(linear_project_id, event) DO UPDATE SET
+                     state_name = excluded.state_name,
+                     state_type = NULL,
+                     updated_at = excluded.updated_at"#,
+                params![linear_project_id, event.as_str(), state_name, now],
+            )
+        }
+        None => conn.execute(
+            "DELETE FROM linear_state_rules WHERE linear_project_id = ?1 AND event = ?2",
+            params![linear_project_id, event.as_str()],
+        ),
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Gives a newly saved project the default start rule, named after the team's first
+/// started state if its states are cached
+pub fn set_default_state_rule(conn: &Connection, linear_project_id: i64, linear_team_id: &str) -> Result<(), String> {
+    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let state_name = first_state_of_type(&cached_team_states(conn, linear_team_id)?, DEFAULT_STARTED_STATE_TYPE)
+        .map_or(DEFAULT_STARTED_STATE.to_string(), |state| state.name.clone());
+
+    conn.execute(
+        r#"INSERT INTO linear_state_rules (linear_project_id, event, state_name, state_type, created_at, updated_at)
+           VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+           ON CONFLICT(linear_project_id, event) DO UPDATE SET
+             state_name = excluded.state_name,
+             state_type = excluded.state_type,
+             updated_at = excluded.updated_at"#,
+        params![linear_project_id, ProviderHook::Started.as_str(), state_name, DEFAULT_STARTED_STATE_TYPE, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Teams of saved projects with a default rule whose states were never fetched, so the
+/// rule can't be resolved yet
+pub fn teams_missing_states(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"SELECT DISTINCT p.linear_team_id
+               FROM linear_state_rules r
+               JOIN linear_projects p ON p.id = r.linear_project_id
+               WHERE r.state_type IS NOT NULL AND p.deleted_at IS NULL
+                 AND NOT EXISTS (SELECT 1 FROM linear_team_states s WHERE s.linear_team_id = p.linear_team_id)
+               ORDER BY p.linear_team_id"#,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let teams = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(teams)
+}
+
+// States are cached in the order Linear returns them, which is the workflow's order
+fn first_state_of_type<'a>(states: &'a [LinearTeamWorkflowState], state_type: &str) -> Option<&'a LinearTeamWorkflowState> {
+    states.iter().find(|state| state.state_type == state_type)
+}
+
+/// Workflow states of a team as last fetched from Linear, empty if never fetched
+pub fn cached_team_states(conn: &Connection, linear_team_id: &str) -> Result<Vec<LinearTeamWorkflowState>, String> {
+    let mut stmt = conn
+        .prepare("SELECT linear_state_id, name, state_type FROM linear_team_states WHERE linear_team_id = ?1 ORDER BY rowid")
+        .map_err(|e| e.to_string())?;
+
+    let states = stmt
+        .query_map(params![linear_team_id], |row| {
+            Ok(LinearTeamWorkflowState {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                state_type: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(states)
+}
+
+/// Replaces a team's cached workflow states with a fresh list from Linear, and renames
+/// the default rules of the team's projects after the states they now resolve to
+pub fn cache_team_states(conn: &Connection, linear_team_id: &str, states: &[LinearTeamWorkflowState]) -> Result<(), String> {
+    let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    tx.execute("DELETE FROM linear_team_states WHERE linear_team_id = ?1", params![linear_team_id])
+        .map_err(|e| e.to_string())?;
+    for state in states {
+        tx.execute(
+            r#"INSERT INTO linear_team_states (linear_state_id, linear_team_id, name, state_type, fetched_at)
+               VALUES (?1, ?2, ?3, ?4, ?5)
+               ON CONFLICT(linear_state_id) DO UPDATE SET
+                 linear_team_id = excluded.linear_team_id,
+                 name = excluded.name,
+                 state_type = excluded.state_type,
+                 fetched_at = excluded.fetched_at"#,
+            params![state.id, linear_team_id, state.name, state.state_type, now],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    let mut stmt = tx
+        .prepare(
+            r#"SELECT r.id, r.state_type FROM linear_state_rules r
+               JOIN linear_projects p ON p.id = r.linear_project_id
+               WHERE r.state_type IS NOT NULL AND p.linear_team_id = ?1"#,
+        )
+        .map_err(|e| e.to_string())?;
+    let default_rules = stmt
+        .query_map(params![linear_team_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    drop(stmt);
+    for (rule_id, state_type) in default_rules {
+        if let Some(state) = first_state_of_type(states, &state_type) {
+            tx.execute("UPDATE linear_state_rules SET state_name = ?1 WHERE id = ?2", params![state.name, rule_id])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Works out what an event does to the timebox's issue, from the rules of its project
+/// and the cached states of the project's team. Never touches the network, so it runs
+/// in the provider hooks and backs the dry-run preview.
+pub fn plan_transition(conn: &Connection, event: ProviderHook, timebox: &Timebox) -> Result<StateTransition, String> {
+    if timebox.linear_issue_id.is_none() {
+        return Ok(StateTransition::skipped(event, timebox, "The timebox isn't linked to a Linear issue"));
+    }
+    let Some(project_id) = timebox.linear_project_id else {
+        return Ok(StateTransition::skipped(event, timebox, "The timebox isn't in a saved Linear project"));
+    };
+
+    let rule: Option<(String, Option<String>, String)> = conn
+        .query_row(
+            r#"SELECT r.state_name, r.state_type, p.linear_team_id
+               FROM linear_state_rules r
+               JOIN linear_projects p ON p.id = r.linear_project_id
+               WHERE r.linear_project_id = ?1 AND r.event = ?2 AND p.deleted_at IS NULL"#,
+            params![project_id, event.as_str()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some((state_name, state_type, team_id)) = rule else {
+        return Ok(StateTransition::skipped(event, timebox, format!("The project has no rule for when a timebox is {}", event.as_str())));
+    };
+
+    let states = cached_team_states(conn, &team_id)?;
+    let state = match &state_type {
+        Some(state_type) => first_state_of_type(&states, state_type),
+        None => states.iter().find(|state| state.name.eq_ignore_ascii_case(&state_name)),
+    };
+
+    Ok(match state {
+        Some(state) => StateTransition {
+            event,
+            issue_id: timebox.linear_issue_id.clone(),
+            issue_identifier: timebox.linear_issue_identifier.clone(),
+            state_name: Some(state.name.clone()),
+            state_id: Some(state.id.clone()),
+            skipped_reason: None,
+        },
+        None => {
+            let missing = match &state_type {
+                Some(state_type) => format!("{} state", state_type),
+                None => format!("'{}' state", state_name),
+            };
+            StateTransition {
+                state_name: Some(state_name.clone()),
+                ..StateTransition::skipped(event, timebox, format!("The team has no {}, refresh its workflow states", missing))
+            }
+        }
+    })
+}
+
+/// What each event would do to the timebox's issue, without queueing anything
+pub fn preview_transitions(conn: &Connection, timebox: &Timebox) -> Result<Vec<StateTransition>, String> {
+    ProviderHook::ALL.iter().map(|&event| plan_transition(conn, event, timebox)).collect()
+}
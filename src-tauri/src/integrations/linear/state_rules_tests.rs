@@ -0,0 +1,211 @@
+use super::client::LinearTeamWorkflowState;
+use super::state_rules::{cache_team_states, preview_transitions, set_state_rule, state_rules, teams_missing_states};
+use crate::commands::{cache_missing_team_states, save_project, save_state_rule, team_workflow_states};
+use crate::core::{OutboxAction, OutboxService, TimeboxService};
+use crate::integrations::ProviderHook;
+use crate::mock_server::MockServer;
+use crate::models::{CreateTimeboxRequest, SaveLinearProjectRequest};
+use crate::test_support::{add_integration, graphql_page, linear_state, test_db, timebox_request};
+use rusqlite::Connection;
+use serde_json::json;
+use tauri::async_runtime::block_on;
+
+fn add_project(conn: &Connection) -> i64 {
+    conn.execute(
+        "INSERT INTO linear_projects (linear_project_id, linear_team_id, name) VALUES ('project-1', 'team-1', 'Roadmap')",
+        [],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+fn setup_test_db() -> (Connection, i64) {
+    let conn = test_db();
+    add_integration(&conn, "linear", json!({ "api_key": "key" }));
+    let project_id = add_project(&conn);
+    (conn, project_id)
+}
+
+fn team_states() -> Vec<LinearTeamWorkflowState> {
+    [("state-todo", "Todo", "unstarted"), ("state-progress", "In Progress", "started"), ("state-done", "Done", "completed")]
+        .into_iter()
+        .map(|(id, name, state_type)| LinearTeamWorkflowState {
+            id: id.to_string(),
+            name: name.to_string(),
+            state_type: state_type.to_string(),
+        })
+        .collect()
+}
+
+fn create_linked_timebox(conn: &Connection, linear_project_id: Option<i64>, linear_issue_id: Option<&str>) -> i64 {
+    let request = CreateTimeboxRequest {
+        linear_project_id,
+        linear_issue_id: linear_issue_id.map(str::to_string),
+        linear_issue_identifier: linear_issue_id.map(|_| "ENG-42".to_string()),
+        ..timebox_request()
+    };
+    TimeboxService::new(conn).create(&request).unwrap().id
+}
+
+fn queued_states(conn: &Connection) -> Vec<String> {
+    OutboxService::new(conn)
+        .items(None, 50)
+        .unwrap()
+        .iter()
+        .rev()
+        .filter_map(|item| match OutboxAction::from_item(item).unwrap() {
+            OutboxAction::LinearUpdateIssueState { issue_id, state_id, .. } => {
+                assert_eq!(issue_id, "issue-1");
+                Some(state_id)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_transitions_follow_project_rules() {
+    let (conn, project_id) = setup_test_db();
+    cache_team_states(&conn, "team-1", &team_states()).unwrap();
+    set_state_rule(&conn, project_id, ProviderHook::Started, Some("In Progress")).unwrap();
+    // Names match regardless of case
+    set_state_rule(&conn, project_id, ProviderHook::Finished, Some("done")).unwrap();
+
+    let service = TimeboxService::new(&conn);
+    let id = create_linked_timebox(&conn, Some(project_id), Some("issue-1"));
+    service.start(id).unwrap();
+    // No rule for stopping
+    service.stop(id).unwrap();
+    service.start(id).unwrap();
+    service.finish(id).unwrap();
+    assert_eq!(queued_states(&conn), vec!["state-progress", "state-progress", "state-done"]);
+
+    // Timeboxes outside the project are left alone
+    let other = create_linked_timebox(&conn, None, Some("issue-1"));
+    service.start(other).unwrap();
+    assert_eq!(queued_states(&conn).len(), 3);
+
+    // Clearing a rule stops the transition
+    set_state_rule(&conn, project_id, ProviderHook::Started, None).unwrap();
+    let rules = state_rules(&conn, project_id).unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].event, ProviderHook::Finished);
+    let id = create_linked_timebox(&conn, Some(project_id), Some("issue-1"));
+    service.start(id).unwrap();
+    assert_eq!(queued_states(&conn).len(), 3);
+}
+
+#[test]
+fn test_preview_explains_skipped_events() {
+    let (conn, project_id) = setup_test_db();
+    set_state_rule(&conn, project_id, ProviderHook::Started, Some("In Progress")).unwrap();
+    set_state_rule(&conn, project_id, ProviderHook::Finished, Some("In Review")).unwrap();
+    cache_team_states(&conn, "team-1", &team_states()).unwrap();
+
+    let service = TimeboxService::new(&conn);
+    let id = create_linked_timebox(&conn, Some(project_id), Some("issue-1"));
+    let preview = preview_transitions(&conn, &service.get(id).unwrap()).unwrap();
+    assert_eq!(preview.len(), 3);
+
+    assert_eq!(preview[0].event, ProviderHook::Started);
+    assert_eq!(preview[0].issue_identifier.as_deref(), Some("ENG-42"));
+    assert_eq!(preview[0].state_id.as_deref(), Some("state-progress"));
+    assert_eq!(preview[0].skipped_reason, None);
+
+    assert!(preview[1].skipped_reason.as_deref().unwrap().contains("no rule"));
+
+    // The rule names a state the cached team doesn't have
+    assert_eq!(preview[2].state_name.as_deref(), Some("In Review"));
+    assert_eq!(preview[2].state_id, None);
+    assert!(preview[2].skipped_reason.as_deref().unwrap().contains("'In Review'"));
+
+    let unlinked = create_linked_timebox(&conn, Some(project_id), None);
+    let preview = preview_transitions(&conn, &service.get(unlinked).unwrap()).unwrap();
+    assert!(preview.iter().all(|t| t.action(None).is_none()));
+
+    // A preview queues nothing
+    assert!(queued_states(&conn).is_empty());
+}
+
+#[test]
+fn test_rules_resolve_against_fetched_states() {
+    let server = MockServer::start();
+    let states = |names: &[(&str, &str)]| {
+        let nodes = names.iter().map(|(id, name)| json!({ "id": id, "name": name, "type": "started" })).collect();
+        graphql_page(&["team", "states"], nodes)
+    };
+    server
+        .respond_json(200, states(&[("state-progress", "In Progress")]))
+        .respond_json(200, states(&[("state-progress", "In Progress"), ("state-review", "In Review")]))
+        .respond_json(200, states(&[("state-progress", "In Progress")]));
+
+    let state = linear_state(server.url());
+    let project_id = add_project(&state.db.lock().unwrap());
+
+    // Fetched once, then served from the cache
+    let rules = block_on(save_state_rule(&state, project_id, ProviderHook::Started, Some("In Progress".to_string()))).unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(block_on(team_workflow_states(&state, "team-1", false)).unwrap().len(), 1);
+    assert_eq!(server.requests().len(), 1);
+
+    // An unknown name refreshes the cache before giving up
+    let rules = block_on(save_state_rule(&state, project_id, ProviderHook::Finished, Some("In Review".to_string()))).unwrap();
+    assert_eq!(rules[1].state_name, "In Review");
+    assert_eq!(server.requests().len(), 2);
+
+    let error = block_on(save_state_rule(&state, project_id, ProviderHook::Stopped, Some("Blocked".to_string()))).unwrap_err();
+    assert_eq!(error, "The team has no 'Blocked' workflow state");
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(block_on(save_state_rule(&state, project_id, ProviderHook::Finished, None)).unwrap().len(), 1);
+}
+
+#[test]
+fn test_default_rule_follows_first_started_state() {
+    let server = MockServer::start();
+    // State ids are unique across teams
+    let states = |team: &str| {
+        graphql_page(
+            &["team", "states"],
+            vec![
+                json!({ "id": format!("{}-todo", team), "name": "Todo", "type": "unstarted" }),
+                json!({ "id": format!("{}-doing", team), "name": "Doing", "type": "started" }),
+                json!({ "id": format!("{}-review", team), "name": "In Review", "type": "started" }),
+            ],
+        )
+    };
+    server.respond_json(200, states("team-2")).respond(401, "Unauthorized").respond_json(200, states("team-3"));
+
+    let state = linear_state(server.url());
+    let request = |id: &str, team_id: &str| SaveLinearProjectRequest {
+        linear_project_id: id.to_string(),
+        linear_team_id: team_id.to_string(),
+        name: "Billing".to_string(),
+        description: None,
+        state: None,
+    };
+
+    // Saving fetches the team's states, so the rule resolves without opening the editor
+    let project = block_on(save_project(&state, &request("project-2", "team-2"))).unwrap();
+    {
+        let conn = state.db.lock().unwrap();
+        let rules = state_rules(&conn, project.id).unwrap();
+        assert_eq!(rules[0].event, ProviderHook::Started);
+        assert_eq!((rules[0].state_name.as_str(), rules[0].state_type.as_deref()), ("Doing", Some("started")));
+
+        let id = create_linked_timebox(&conn, Some(project.id), Some("issue-1"));
+        TimeboxService::new(&conn).start(id).unwrap();
+        assert_eq!(queued_states(&conn), vec!["team-2-doing"]);
+    }
+
+    // Saved while the states couldn't be fetched, so the next refresh does
+    let project = block_on(save_project(&state, &request("project-3", "team-3"))).unwrap();
+    assert_eq!(teams_missing_states(&state.db.lock().unwrap()).unwrap(), vec!["team-3"]);
+    block_on(cache_missing_team_states(&state)).unwrap();
+    let conn = state.db.lock().unwrap();
+    assert!(teams_missing_states(&conn).unwrap().is_empty());
+    assert_eq!(state_rules(&conn, project.id).unwrap()[0].state_name, "Doing");
+
+    // Picking a state by name makes it an ordinary rule
+    set_state_rule(&conn, project.id, ProviderHook::Started, Some("In Review")).unwrap();
+    assert_eq!(state_rules(&conn, project.id).unwrap()[0].state_type, None);
+}
@@ -0,0 +1,18 @@
+pub mod http;
+pub mod linear;
+pub mod provider;
+pub mod registry;
+pub mod store;
+pub mod todoist;
+pub mod webhook;
+
+#[cfg(test)]
+mod http_tests;
+#[cfg(test)]
+mod registry_tests;
+#[cfg(test)]
+mod todoist_tests;
+
+pub use provider::*;
+pub use registry::*;
+pub use store::*;
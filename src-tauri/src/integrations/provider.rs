@@ -0,0 +1,235 @@
+use super::http::{ApiError, ApiErrorKind};
+use crate::core::{OutboxAction, TimeboxWithSessions};
+use crate::models::Integration;
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use url::Url;
+
+/// Config key for overriding a service's API base URL, e.g. to point at a proxy or mock server
+pub const API_BASE_URL_KEY: &str = "api_base_url";
+
+/// How the frontend should render a config field
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFieldKind {
+    Secret,
+    Url,
+    Boolean,
+    /// A list of timebox events to subscribe to
+    Events,
+    /// One of `options`
+    Choice,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ConfigOption {
+    pub value: &'static str,
+    pub label: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigField {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub kind: ConfigFieldKind,
+    pub required: bool,
+    pub placeholder: Option<&'static str>,
+    pub help: Option<&'static str>,
+    /// Allowed values of a `Choice` field, empty otherwise
+    pub options: &'static [ConfigOption],
+}
+
+/// What the frontend needs to offer a provider and build its connection form
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderDescriptor {
+    pub integration_type: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Whether several connections of this type make sense (e.g. webhooks)
+    pub allow_multiple: bool,
+    pub config_schema: Vec<ConfigField>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionTestResult {
+    pub success: bool,
+    /// Who the credentials belong to, when the service tells us
+    pub account_name: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ConnectionTestResult {
+    pub fn ok(account_name: Option<String>) -> Self {
+        ConnectionTestResult { success: true, account_name, error: None }
+    }
+
+    pub fn failed(error: impl Into<String>) -> Self {
+        ConnectionTestResult { success: false, account_name: None, error: Some(error.into()) }
+    }
+}
+
+/// A task or issue from a provider that a timebox can be created from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProviderTask {
+    pub id: String,
+    pub title: String,
+    pub url: Option<String>,
+    /// Human readable key such as a Linear identifier ("ENG-42")
+    pub identifier: Option<String>,
+}
+
+/// What the network methods of `IntegrationProvider` return, so the trait stays object safe
+pub type ProviderFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// An external service timeboxd can connect to. Implementations are registered in
+/// `registry::PROVIDERS` under their `integration_type`.
+///
+/// Lifecycle hooks run inside the transaction that saves the timebox, on its connection,
+/// and must not touch the network: they return side effects, which are queued in the outbox and handed
+/// back to `deliver` by the outbox worker. Hook errors are logged and never fail the
+/// action that triggered them.
+pub trait IntegrationProvider: Send + Sync {
+    fn descriptor(&self) -> ProviderDescriptor;
+
+    fn integration_type(&self) -> &'static str {
+        self.descriptor().integration_type
+    }
+
+    /// Checks and normalizes a config before it is saved: required fields must be set,
+    /// choices must be one of their options and an `api_base_url`, if any, must be an
+    /// http(s) URL.
+    fn validate_config(&self, config: Value) -> Result<Value, String> {
+        for field in self.descriptor().config_schema.iter() {
+            let value = config.get(field.key);
+            let present = match value {
+                Some(Value::String(s)) => !s.trim().is_empty(),
+                Some(Value::Null) | None => false,
+                Some(_) => true,
+            };
+            if field.required && !present {
+                return Err(format!("{} is required", field.label));
+            }
+            if field.kind == ConfigFieldKind::Choice && present {
+                let valid = value
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|v| field.options.iter().any(|o| o.value == v));
+                if !valid {
+                    return Err(format!("{} must be one of the listed options", field.label));
+                }
+            }
+        }
+        normalize_api_base_url(config)
+    }
+
+    fn test_connection<'a>(&'a self, config: &'a Value) -> ProviderFuture<'a, Result<ConnectionTestResult, String>>;
+
+    fn on_timebox_started(
+        &self,
+        _conn: &Connection,
+        _integration: &Integration,
+        _timebox: &TimeboxWithSessions,
+    ) -> Result<Vec<OutboxAction>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Stopped by the user, by idle detection or because the time ran out
+    fn on_timebox_stopped(
+        &self,
+        _conn: &Connection,
+        _integration: &Integration,
+        _timebox: &TimeboxWithSessions,
+    ) -> Result<Vec<OutboxAction>, String> {
+        Ok(Vec::new())
+    }
+
+    fn on_timebox_finished(
+        &self,
+        _conn: &Connection,
+        _integration: &Integration,
+        _timebox: &TimeboxWithSessions,
+    ) -> Result<Vec<OutboxAction>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Performs a queued side effect. Auth and rejected errors fail the item for good,
+    /// anything else is retried with backoff.
+    fn deliver<'a>(
+        &'a self,
+        _integration: &'a Integration,
+        action: &'a OutboxAction,
+    ) -> ProviderFuture<'a, Result<(), ApiError>> {
+        let error = ApiError::new(
+            ApiErrorKind::Rejected,
+            format!("{} can't deliver {}", self.descriptor().name, action.name()),
+        );
+        Box::pin(async move { Err(error) })
+    }
+
+    /// Open tasks the user could timebox, empty for providers without tasks
+    fn fetch_tasks<'a>(&'a self, _integration: &'a Integration) -> ProviderFuture<'a, Result<Vec<ProviderTask>, String>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+}
+
+/// Reads a required string from a connection config
+pub fn config_str<'a>(config: &'a Value, key: &str) -> Result<&'a str, String> {
+    config
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Integration config is missing {}", key))
+}
+
+/// The configured API base URL, `None` meaning the service's production API
+pub fn api_base_url(config: &Value) -> Option<&str> {
+    config
+        .get(API_BASE_URL_KEY)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Drops an empty `api_base_url` and strips its trailing slash, rejecting anything
+/// that isn't an http(s) URL
+pub fn normalize_api_base_url(mut config: Value) -> Result<Value, String> {
+    let base_url = api_base_url(&config).map(|s| s.trim_end_matches('/').to_string());
+    let Some(map) = config.as_object_mut() else {
+        return Ok(config);
+    };
+
+    match base_url {
+        None => {
+            map.remove(API_BASE_URL_KEY);
+        }
+        Some(base_url) => {
+            let parsed = Url::parse(&base_url).map_err(|e| format!("Invalid API base URL: {}", e))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err("API base URL must start with http:// or https://".to_string());
+            }
+            map.insert(API_BASE_URL_KEY.to_string(), Value::String(base_url));
+        }
+    }
+
+    Ok(config)
+}
+
+/// Joins a path onto a base URL, falling back to `default_base` when none is configured
+pub fn api_url(api_base_url: Option<&str>, default_base: &str, path: &str) -> String {
+    format!("{}{}", api_base_url.unwrap_or(default_base).trim_end_matches('/'), path)
+}
+
+/// Schema entry shared by providers with a configurable API base URL
+pub fn api_base_url_field(default_base: &'static str) -> ConfigField {
+    ConfigField {
+        key: API_BASE_URL_KEY,
+        label: "API Base URL",
+        kind: ConfigFieldKind::Url,
+        required: false,
+        placeholder: Some(default_base),
+        help: Some("Leave empty to use the production API"),
+        options: &[],
+    }
+}
@@ -0,0 +1,96 @@
+use super::linear::LinearProvider;
+use super::provider::{IntegrationProvider, ProviderDescriptor};
+use super::store::list_integrations;
+use super::todoist::TodoistProvider;
+use super::webhook::WebhookProvider;
+use crate::core::{OutboxService, TimeboxWithSessions};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Every provider timeboxd knows about. Adding an integration means implementing
+/// `IntegrationProvider` and listing it here.
+static PROVIDERS: &[&dyn IntegrationProvider] = &[&LinearProvider, &TodoistProvider, &WebhookProvider];
+
+pub fn provider(integration_type: &str) -> Option<&'static dyn IntegrationProvider> {
+    PROVIDERS.iter().copied().find(|p| p.integration_type() == integration_type)
+}
+
+pub fn provider_descriptors() -> Vec<ProviderDescriptor> {
+    PROVIDERS.iter().map(|p| p.descriptor()).collect()
+}
+
+/// Rejects unknown integration types and lets the provider check the config
+pub fn validate_integration_config(integration_type: &str, config: serde_json::Value) -> Result<serde_json::Value, String> {
+    provider(integration_type)
+        .ok_or_else(|| format!("Unknown integration type '{}'", integration_type))?
+        .validate_config(config)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderHook {
+    Started,
+    Stopped,
+    Finished,
+}
+
+impl ProviderHook {
+    pub const ALL: [ProviderHook; 3] = [ProviderHook::Started, ProviderHook::Stopped, ProviderHook::Finished];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProviderHook::Started => "started",
+            ProviderHook::Stopped => "stopped",
+            ProviderHook::Finished => "finished",
+        }
+    }
+
+    // Infallible (unknown values are started), so not FromStr
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "stopped" => ProviderHook::Stopped,
+            "finished" => ProviderHook::Finished,
+            _ => ProviderHook::Started,
+        }
+    }
+}
+
+/// Runs a lifecycle hook on every connected integration and queues the side effects
+/// they ask for. Errors are logged so a misconfigured integration never fails the
+/// timebox action.
+pub fn run_provider_hooks(conn: &Connection, hook: ProviderHook, timebox: &TimeboxWithSessions) {
+    let integrations = match list_integrations(conn) {
+        Ok(integrations) => integrations,
+        Err(e) => {
+            eprintln!("Failed to load integrations: {}", e);
+            return;
+        }
+    };
+
+    let outbox = OutboxService::new(conn);
+    for integration in integrations {
+        let Some(provider) = provider(&integration.integration_type) else {
+            continue;
+        };
+        let actions = match hook {
+            ProviderHook::Started => provider.on_timebox_started(conn, &integration, timebox),
+            ProviderHook::Stopped => provider.on_timebox_stopped(conn, &integration, timebox),
+            ProviderHook::Finished => provider.on_timebox_finished(conn, &integration, timebox),
+        };
+        let result = actions.and_then(|actions| {
+            actions
+                .iter()
+                .try_for_each(|action| outbox.enqueue(integration.id, Some(timebox.timebox.id), action).map(|_| ()))
+        });
+        if let Err(e) = result {
+            eprintln!(
+                "{} integration '{}' failed for timebox {}: {}",
+                provider.descriptor().name,
+                integration.connection_name,
+                timebox.timebox.id,
+                e
+            );
+        }
+    }
+}
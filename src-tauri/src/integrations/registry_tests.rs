@@ -0,0 +1,77 @@
+use super::linear::linear_graphql_url;
+use super::todoist::todoist_api_url;
+use super::{api_base_url, provider, provider_descriptors, validate_integration_config, ConfigFieldKind};
+use serde_json::json;
+use std::collections::HashSet;
+
+#[test]
+fn test_lookup_by_type() {
+    assert_eq!(provider("linear").unwrap().descriptor().name, "Linear");
+    assert_eq!(provider("todoist").unwrap().descriptor().name, "Todoist");
+    assert_eq!(provider("webhook").unwrap().descriptor().name, "Webhook");
+    assert!(provider("jira").is_none());
+}
+
+#[test]
+fn test_descriptors_are_unique_and_have_schemas() {
+    let descriptors = provider_descriptors();
+    let types: HashSet<&str> = descriptors.iter().map(|d| d.integration_type).collect();
+    assert_eq!(types.len(), descriptors.len());
+
+    for descriptor in &descriptors {
+        assert!(descriptor.config_schema.iter().any(|f| f.required), "{}", descriptor.name);
+    }
+
+    let linear = descriptors.iter().find(|d| d.integration_type == "linear").unwrap();
+    assert_eq!(linear.config_schema[0].key, "api_key");
+    assert_eq!(linear.config_schema[0].kind, ConfigFieldKind::Secret);
+}
+
+#[test]
+fn test_validation() {
+    assert!(validate_integration_config("jira", json!({})).is_err());
+
+    // Required fields come from the schema
+    assert!(validate_integration_config("linear", json!({})).is_err());
+    assert!(validate_integration_config("linear", json!({ "api_key": "  " })).is_err());
+    let config = validate_integration_config("linear", json!({ "api_key": "lin_api_x" })).unwrap();
+    assert_eq!(config["api_key"], "lin_api_x");
+    assert!(validate_integration_config("todoist", json!({ "api_key": "wrong field" })).is_err());
+
+    // Choices must be one of their options
+    assert!(validate_integration_config("linear", json!({ "api_key": "x", "time_comment": "on_stop" })).is_ok());
+    assert!(validate_integration_config("linear", json!({ "api_key": "x", "time_comment": "always" })).is_err());
+    assert!(validate_integration_config("linear", json!({ "api_key": "x", "time_comment": 1 })).is_err());
+
+    // Providers can normalize their own config
+    let config = validate_integration_config(
+        "webhook",
+        json!({ "url": "https://example.com/hook", "secret": "", "events": ["finished"] }),
+    )
+    .unwrap();
+    assert_eq!(config["secret"], serde_json::Value::Null);
+    assert!(validate_integration_config("webhook", json!({ "url": "https://example.com", "events": [] })).is_err());
+}
+
+#[test]
+fn test_api_base_url() {
+    let config = validate_integration_config(
+        "linear",
+        json!({ "api_key": "lin_api_x", "api_base_url": " http://127.0.0.1:8080/ " }),
+    )
+    .unwrap();
+    assert_eq!(config["api_base_url"], "http://127.0.0.1:8080");
+    assert_eq!(linear_graphql_url(api_base_url(&config)), "http://127.0.0.1:8080/graphql");
+
+    // Empty means production
+    let config = validate_integration_config("todoist", json!({ "api_token": "t", "api_base_url": "" })).unwrap();
+    assert!(config.get("api_base_url").is_none());
+    assert_eq!(
+        todoist_api_url(api_base_url(&config), "/rest/v2/tasks"),
+        "https://api.todoist.com/rest/v2/tasks"
+    );
+    assert_eq!(linear_graphql_url(None), "https://api.linear.app/graphql");
+
+    assert!(validate_integration_config("linear", json!({ "api_key": "x", "api_base_url": "ftp://mock" })).is_err());
+    assert!(validate_integration_config("linear", json!({ "api_key": "x", "api_base_url": "not a url" })).is_err());
+}
@@ -0,0 +1,50 @@
+use crate::models::Integration;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const INTEGRATION_SELECT_COLUMNS: &str = "id, connection_name, integration_type, connection_config, created_at, updated_at";
+
+/// Connection config of the Linear integration, if one exists
+pub fn get_linear_config(conn: &Connection) -> Option<serde_json::Value> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM integrations WHERE integration_type = 'linear' LIMIT 1",
+            INTEGRATION_SELECT_COLUMNS
+        ))
+        .ok()?;
+
+    let integration: Option<Integration> = stmt
+        .query_row([], Integration::from_row)
+        .optional()
+        .ok()?;
+
+    integration.map(|i| i.connection_config)
+}
+
+/// All connected integrations, newest first
+pub fn list_integrations(conn: &Connection) -> Result<Vec<Integration>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM integrations ORDER BY created_at DESC",
+            INTEGRATION_SELECT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let integrations: Vec<Integration> = stmt
+        .query_map([], Integration::from_row)
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(integrations)
+}
+
+/// The integration with an id, if it still exists
+pub fn find_integration(conn: &Connection, id: i64) -> Result<Option<Integration>, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM integrations WHERE id = ?1", INTEGRATION_SELECT_COLUMNS),
+        params![id],
+        Integration::from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
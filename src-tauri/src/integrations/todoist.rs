@@ -0,0 +1,232 @@
+use super::http::{self, ApiError, ApiErrorKind};
+use super::provider::{api_base_url, api_base_url_field, api_url, config_str, ConfigField, ConfigFieldKind, ConnectionTestResult, IntegrationProvider, ProviderDescriptor, ProviderFuture, ProviderTask};
+use crate::core::{OutboxAction, TimeboxWithSessions};
+use crate::models::Integration;
+use chrono::Local;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const TODOIST_INTEGRATION_TYPE: &str = "todoist";
+pub const DEFAULT_TODOIST_API_BASE_URL: &str = "https://api.todoist.com";
+
+/// URL of an API path under the configured base URL
+pub fn todoist_api_url(api_base_url: Option<&str>, path: &str) -> String {
+    api_url(api_base_url, DEFAULT_TODOIST_API_BASE_URL, path)
+}
+
+#[derive(Debug, Serialize)]
+pub struct TodoistTestResult {
+    pub success: bool,
+    pub user_name: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoistUser {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TodoistTask {
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoistApiTask {
+    id: String,
+    content: String,
+    url: Option<String>,
+}
+
+/// Checks an API token by fetching its user. A refused token is a failed test rather
+/// than an error.
+pub async fn test_todoist_token(api_token: &str, api_base_url: Option<&str>) -> Result<TodoistTestResult, ApiError> {
+    let response = http::send("Todoist", true, |client| {
+        client
+            .get(todoist_api_url(api_base_url, "/sync/v9/user"))
+            .header("Authorization", format!("Bearer {}", api_token))
+    })
+    .await;
+
+    let user: TodoistUser = match response {
+        Ok(response) => response.json().await.map_err(|e| ApiError::invalid_response("Todoist", e))?,
+        Err(e) if e.is_rejection() => {
+            return Ok(TodoistTestResult {
+                success: false,
+                user_name: None,
+                error: Some(e.message),
+            })
+        }
+        Err(e) => return Err(e),
+    };
+
+    Ok(TodoistTestResult {
+        success: true,
+        user_name: Some(user.full_name),
+        error: None,
+    })
+}
+
+/// Creates a task in Todoist with today's due date, returning its id. Todoist drops
+/// repeats of a `request_id`, which makes retrying the create safe.
+pub async fn create_todoist_task(
+    api_base_url: Option<&str>,
+    api_token: &str,
+    content: &str,
+    description: Option<&str>,
+    request_id: &str,
+) -> Result<String, ApiError> {
+    let today = Local::now().format("%Y-%m-%d").to_string();
+
+    let mut body = serde_json::json!({
+        "content": content,
+        "due_date": today
+    });
+
+    if let Some(desc) = description {
+        body["description"] = Value::String(desc.to_string());
+    }
+
+    let response = http::send("Todoist", true, |client| {
+        client
+            .post(todoist_api_url(api_base_url, "/rest/v2/tasks"))
+            .header("Authorization", format!("Bearer {}", api_token))
+            .header("X-Request-Id", request_id)
+            .json(&body)
+    })
+    .await?;
+
+    let task: TodoistTask = response.json().await.map_err(|e| ApiError::invalid_response("Todoist", e))?;
+    Ok(task.id)
+}
+
+/// Marks a Todoist task as completed
+pub async fn complete_todoist_task(api_base_url: Option<&str>, api_token: &str, task_id: &str) -> Result<(), ApiError> {
+    http::send("Todoist", true, |client| {
+        client
+            .post(todoist_api_url(api_base_url, &format!("/rest/v2/tasks/{}/close", task_id)))
+            .header("Authorization", format!("Bearer {}", api_token))
+    })
+    .await?;
+    Ok(())
+}
+
+/// Creates a task in Todoist and immediately marks it as completed, returning its id.
+/// This is used when finishing a timebox to record it as a completed task.
+pub async fn create_completed_todoist_task(
+    api_base_url: Option<&str>,
+    api_token: &str,
+    content: &str,
+    description: Option<&str>,
+    request_id: &str,
+) -> Result<String, ApiError> {
+    let task_id = create_todoist_task(api_base_url, api_token, content, description, request_id).await?;
+
+    complete_todoist_task(api_base_url, api_token, &task_id).await.map_err(|e| ApiError {
+        message: format!("Task created but failed to mark as completed: {}", e.message),
+        ..e
+    })?;
+
+    Ok(task_id)
+}
+
+pub struct TodoistProvider;
+
+impl IntegrationProvider for TodoistProvider {
+    fn descriptor(&self) -> ProviderDescriptor {
+        ProviderDescriptor {
+            integration_type: TODOIST_INTEGRATION_TYPE,
+            name: "Todoist",
+            description: "Record finished timeboxes as completed Todoist tasks",
+            allow_multiple: false,
+            config_schema: vec![
+                ConfigField {
+                    key: "api_token",
+                    label: "API Token",
+                    kind: ConfigFieldKind::Secret,
+                    required: true,
+                    placeholder: None,
+                    help: Some("Find it in Todoist under Settings → Integrations → Developer"),
+                    options: &[],
+                },
+                api_base_url_field(DEFAULT_TODOIST_API_BASE_URL),
+            ],
+        }
+    }
+
+    fn test_connection<'a>(&'a self, config: &'a Value) -> ProviderFuture<'a, Result<ConnectionTestResult, String>> {
+        Box::pin(async move {
+            let result = test_todoist_token(config_str(config, "api_token")?, api_base_url(config)).await?;
+            Ok(ConnectionTestResult {
+                success: result.success,
+                account_name: result.user_name,
+                error: result.error,
+            })
+        })
+    }
+
+    /// Records the finished timebox as a completed Todoist task, once per timebox
+    fn on_timebox_finished(
+        &self,
+        _conn: &Connection,
+        _integration: &Integration,
+        timebox: &TimeboxWithSessions,
+    ) -> Result<Vec<OutboxAction>, String> {
+        let timebox = &timebox.timebox;
+        let dedupe_key = format!("todoist_completed:{}", timebox.id);
+        Ok(vec![OutboxAction::todoist_completed_task(&timebox.intention, timebox.notes.as_deref(), Some(dedupe_key))])
+    }
+
+    fn deliver<'a>(
+        &'a self,
+        integration: &'a Integration,
+        action: &'a OutboxAction,
+    ) -> ProviderFuture<'a, Result<(), ApiError>> {
+        Box::pin(async move {
+            let OutboxAction::TodoistCreateCompletedTask { content, description, request_id, .. } = action else {
+                return Err(ApiError::new(ApiErrorKind::Rejected, format!("Todoist can't deliver {}", action.name())));
+            };
+            let api_token = config_str(&integration.connection_config, "api_token")
+                .map_err(|e| ApiError::new(ApiErrorKind::Auth, e))?;
+
+            create_completed_todoist_task(
+                api_base_url(&integration.connection_config),
+                api_token,
+                content,
+                description.as_deref(),
+                request_id,
+            )
+            .await?;
+            Ok(())
+        })
+    }
+
+    /// Tasks due today or overdue
+    fn fetch_tasks<'a>(&'a self, integration: &'a Integration) -> ProviderFuture<'a, Result<Vec<ProviderTask>, String>> {
+        Box::pin(async move {
+            let api_token = config_str(&integration.connection_config, "api_token")?;
+
+            let response = http::send("Todoist", true, |client| {
+                client
+                    .get(todoist_api_url(api_base_url(&integration.connection_config), "/rest/v2/tasks"))
+                    .query(&[("filter", "today | overdue")])
+                    .header("Authorization", format!("Bearer {}", api_token))
+            })
+            .await?;
+
+            let tasks: Vec<TodoistApiTask> =
+                response.json().await.map_err(|e| ApiError::invalid_response("Todoist", e))?;
+
+            Ok(tasks
+                .into_iter()
+                .map(|task| ProviderTask {
+                    id: task.id,
+                    title: task.content,
+                    url: task.url,
+                    identifier: None,
+                })
+                .collect())
+        })
+    }
+}
@@ -0,0 +1,110 @@
+use super::todoist::{create_completed_todoist_task, test_todoist_token};
+use crate::integrations::http::ApiErrorKind;
+use crate::mock_server::{unreachable_url, MockServer};
+use chrono::Local;
+use serde_json::json;
+use tauri::async_runtime::block_on;
+
+const API_TOKEN: &str = "todoist-token";
+const REQUEST_ID: &str = "4f1c2a9e-7d2b-4c1e-9a55-0b6f3e2d8c71";
+
+#[test]
+fn test_todoist_connection_responses() {
+    let server = MockServer::start();
+    server
+        .respond_json(200, json!({ "full_name": "Ada Lovelace", "email": "ada@example.com" }))
+        .respond(401, "Unauthorized")
+        .respond(200, "[]");
+
+    let result = block_on(test_todoist_token(API_TOKEN, Some(&server.url()))).unwrap();
+    assert!(result.success);
+    assert_eq!(result.user_name.as_deref(), Some("Ada Lovelace"));
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/sync/v9/user");
+    assert_eq!(request.authorization.as_deref(), Some("Bearer todoist-token"));
+
+    let result = block_on(test_todoist_token(API_TOKEN, Some(&server.url()))).unwrap();
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("401"));
+
+    let error = block_on(test_todoist_token(API_TOKEN, Some(&server.url()))).unwrap_err();
+    assert!(error.message.starts_with("Failed to parse Todoist response"), "{}", error);
+}
+
+#[test]
+fn test_create_completed_task() {
+    let server = MockServer::start();
+    server.respond_json(200, json!({ "id": "task-7", "content": "Write RFC" })).respond(204, "");
+
+    let task_id =
+        block_on(create_completed_todoist_task(Some(&server.url()), API_TOKEN, "Write RFC", Some("Draft \"v2\""), REQUEST_ID))
+            .unwrap();
+    assert_eq!(task_id, "task-7");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/rest/v2/tasks");
+    assert_eq!(requests[0].authorization.as_deref(), Some("Bearer todoist-token"));
+    assert_eq!(requests[0].header("X-Request-Id"), Some(REQUEST_ID));
+    let body = requests[0].json();
+    assert_eq!(body["content"], "Write RFC");
+    assert_eq!(body["description"], "Draft \"v2\"");
+    assert_eq!(body["due_date"], Local::now().format("%Y-%m-%d").to_string());
+
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(requests[1].path, "/rest/v2/tasks/task-7/close");
+}
+
+#[test]
+fn test_create_completed_task_failures() {
+    let server = MockServer::start();
+    server
+        // Creating fails: nothing is closed
+        .respond(403, "Forbidden")
+        // Created but closing fails
+        .respond_json(200, json!({ "id": "task-8" }))
+        .respond(404, "Task not found")
+        // Malformed create response
+        .respond(200, r#"{"id": "#);
+
+    let create = || block_on(create_completed_todoist_task(Some(&server.url()), API_TOKEN, "Task", None, REQUEST_ID));
+
+    let error = create().unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Auth);
+    assert!(error.message.contains("403 Forbidden: Forbidden"), "{}", error);
+    assert_eq!(server.requests().len(), 1);
+
+    let error = create().unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Rejected);
+    assert!(error.message.starts_with("Task created but failed to mark as completed"), "{}", error);
+
+    let error = create().unwrap_err();
+    assert!(error.message.starts_with("Failed to parse Todoist response"), "{}", error);
+
+    // No description is sent when there are no notes
+    assert!(server.requests()[1].json().get("description").is_none());
+
+    let error = block_on(create_completed_todoist_task(Some(&unreachable_url()), API_TOKEN, "Task", None, REQUEST_ID)).unwrap_err();
+    assert_eq!(error.kind, ApiErrorKind::Offline);
+    assert!(error.message.starts_with("Failed to connect to Todoist"), "{}", error);
+}
+
+#[test]
+fn test_create_task_retries_with_same_request_id() {
+    let server = MockServer::start();
+    server
+        .respond(503, "Service Unavailable")
+        .respond_json(200, json!({ "id": "task-9" }))
+        .respond(204, "");
+
+    let task_id = block_on(create_completed_todoist_task(Some(&server.url()), API_TOKEN, "Task", None, REQUEST_ID)).unwrap();
+    assert_eq!(task_id, "task-9");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].header("X-Request-Id"), Some(REQUEST_ID));
+    assert_eq!(requests[1].header("X-Request-Id"), Some(REQUEST_ID));
+}
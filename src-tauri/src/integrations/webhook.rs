@@ -0,0 +1,63 @@
+use super::provider::{ConfigField, ConfigFieldKind, ConnectionTestResult, IntegrationProvider, ProviderDescriptor, ProviderFuture};
+use crate::core::{WebhookConfig, WEBHOOK_INTEGRATION_TYPE};
+use crate::webhooks::ping_webhook;
+use serde_json::Value;
+
+/// Webhooks don't act in the lifecycle hooks: the timebox services queue deliveries
+/// and the webhook worker sends them with retries.
+pub struct WebhookProvider;
+
+impl IntegrationProvider for WebhookProvider {
+    fn descriptor(&self) -> ProviderDescriptor {
+        ProviderDescriptor {
+            integration_type: WEBHOOK_INTEGRATION_TYPE,
+            name: "Webhook",
+            description: "POST timebox events as signed JSON to your own endpoint",
+            allow_multiple: true,
+            config_schema: vec![
+                ConfigField {
+                    key: "url",
+                    label: "URL",
+                    kind: ConfigFieldKind::Url,
+                    required: true,
+                    placeholder: Some("https://example.com/hooks/timeboxd"),
+                    help: None,
+                    options: &[],
+                },
+                ConfigField {
+                    key: "secret",
+                    label: "Secret",
+                    kind: ConfigFieldKind::Secret,
+                    required: false,
+                    placeholder: None,
+                    help: Some("Used to sign payloads with HMAC-SHA256"),
+                    options: &[],
+                },
+                ConfigField {
+                    key: "events",
+                    label: "Events",
+                    kind: ConfigFieldKind::Events,
+                    required: true,
+                    placeholder: None,
+                    help: None,
+                    options: &[],
+                },
+            ],
+        }
+    }
+
+    fn validate_config(&self, config: Value) -> Result<Value, String> {
+        let config = WebhookConfig::from_value(&config)?;
+        serde_json::to_value(config).map_err(|e| e.to_string())
+    }
+
+    fn test_connection<'a>(&'a self, config: &'a Value) -> ProviderFuture<'a, Result<ConnectionTestResult, String>> {
+        Box::pin(async move {
+            let config = WebhookConfig::from_value(config)?;
+            Ok(match ping_webhook(&config).await {
+                Ok(_) => ConnectionTestResult::ok(None),
+                Err((_, error)) => ConnectionTestResult::failed(error),
+            })
+        })
+    }
+}
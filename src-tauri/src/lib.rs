@@ -1,12 +1,35 @@
+mod actions;
 mod commands;
-mod database;
-mod models;
+#[cfg(unix)]
+mod control_socket;
+pub mod core;
+pub mod database;
+mod deep_link;
+mod http_api;
+mod integrations;
+mod linear_projects;
+mod outbox;
+pub mod models;
+pub mod parser;
+mod quick_capture;
+mod shell_hooks;
+mod shortcuts;
 mod state;
+mod tray;
+mod webhooks;
 
 #[cfg(test)]
 mod database_tests;
+#[cfg(test)]
+mod mock_server;
+#[cfg(test)]
+mod outbox_tests;
+#[cfg(all(test, unix))]
+mod shell_hooks_tests;
+#[cfg(test)]
+mod test_support;
 
-use tauri::Manager;
+use tauri::{Manager, WindowEvent};
 use commands::{
     archive_timebox, cancel_session, cancel_timebox, create_timebox, delete_timebox,
     finish_timebox, get_active_session_for_timebox, get_active_timeboxes, get_archived_timeboxes,
@@ -15,37 +38,100 @@ use commands::{
     unarchive_timebox, update_timebox, set_timebox_linear_issue, set_timebox_linear_project,
     auto_stop_timebox,
     // Integration commands
-    create_integration, delete_integration, get_integration_by_type, get_integrations,
-    test_linear_connection, test_todoist_connection, update_integration_config,
+    create_integration, delete_integration, fetch_integration_tasks, get_integration_by_type,
+    get_integration_providers, get_integrations, test_integration_connection, test_linear_connection,
+    test_todoist_connection, update_integration_config,
     // Linear project commands
     get_linear_teams, get_linear_team_projects, save_linear_project, toggle_linear_project_active,
     get_linear_projects, get_linear_project_by_id, get_active_timebox_projects, archive_linear_project, delete_linear_project,
     // Linear issue commands
     create_linear_issue, get_linear_team_states, update_linear_issue_state, get_linear_project_issues,
+    get_cached_linear_project_issues, sync_linear_project_issues,
+    // Linear state transition commands
+    get_linear_workflow_states, get_linear_state_rules, set_linear_state_rule, preview_linear_state_transitions,
+    // Linear planning commands
+    get_linear_my_issues, get_linear_current_cycle, get_linear_estimate_minutes, create_timeboxes_from_linear_issues,
+    // Linear issue link commands
+    validate_linear_issue_links,
+    // Linear project refresh commands
+    refresh_linear_projects,
     // Linear search commands
     search_linear_teams, search_linear_projects,
     // Idle detection commands
     get_system_idle_time, get_idle_settings, set_idle_settings,
+    // Shortcut commands
+    get_shortcut_settings, set_shortcut_settings,
+    // HTTP API commands
+    get_http_api_settings, set_http_api_settings, regenerate_http_api_token,
+    // Webhook commands
+    get_webhook_deliveries, retry_webhook_delivery, test_webhook,
+    // Outbox commands
+    get_outbox_items, retry_outbox_item, discard_outbox_item,
+    // Shell hook commands
+    get_shell_hooks_enabled, set_shell_hooks_enabled, get_shell_hooks, create_shell_hook,
+    update_shell_hook, delete_shell_hook, get_shell_hook_runs,
+    // Quick capture commands
+    parse_quick_capture, quick_capture_timebox,
+    // Duration commands
+    parse_duration, get_duration_presets,
     // Sound commands
     play_system_sound,
 };
+use commands::{load_http_api_settings, load_shortcut_settings};
 use database::initialize_database;
 use state::AppState;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must come first so a second launch hands its deep link to the running app
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            tray::show_main_window(app);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .setup(|app| {
             let db = initialize_database(app.handle())
                 .expect("Failed to initialize database");
+            let shortcut_settings = load_shortcut_settings(&db);
+            let http_api_settings = load_http_api_settings(&db);
             app.manage(AppState::new(db));
+            app.manage(http_api::HttpApiState::default());
+            // A shortcut taken by another app shouldn't prevent startup
+            if let Err(e) = shortcuts::register_shortcuts(app.handle(), &shortcut_settings) {
+                eprintln!("Failed to register global shortcuts: {}", e);
+            }
+            if let Err(e) = http_api::start_http_api(app.handle(), &http_api_settings) {
+                eprintln!("Failed to start HTTP API: {}", e);
+            }
+            #[cfg(unix)]
+            if let Err(e) = control_socket::start_control_socket(app.handle()) {
+                eprintln!("Failed to start control socket: {}", e);
+            }
+            if let Err(e) = deep_link::setup_deep_links(app.handle()) {
+                eprintln!("Failed to set up deep links: {}", e);
+            }
+            webhooks::start_webhook_worker(app.handle());
+            shell_hooks::start_shell_hook_worker(app.handle());
+            outbox::start_outbox_worker(app.handle());
+            linear_projects::start_linear_project_refresh(app.handle());
+            tray::setup_tray(app.handle())?;
             Ok(())
         })
+        .on_window_event(|window, event| {
+            // Keep running in the tray when the main window is closed
+            if let WindowEvent::CloseRequested { api, .. } = event {
+                if window.label() == "main" {
+                    let _ = window.hide();
+                    api.prevent_close();
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             create_timebox,
             update_timebox,
@@ -73,6 +159,9 @@ pub fn run() {
             get_integration_by_type,
             delete_integration,
             update_integration_config,
+            get_integration_providers,
+            test_integration_connection,
+            fetch_integration_tasks,
             test_linear_connection,
             test_todoist_connection,
             // Linear project commands
@@ -90,8 +179,24 @@ pub fn run() {
             get_linear_team_states,
             update_linear_issue_state,
             get_linear_project_issues,
+            get_cached_linear_project_issues,
+            sync_linear_project_issues,
             set_timebox_linear_issue,
             set_timebox_linear_project,
+            // Linear state transition commands
+            get_linear_workflow_states,
+            get_linear_state_rules,
+            set_linear_state_rule,
+            preview_linear_state_transitions,
+            // Linear planning commands
+            get_linear_my_issues,
+            get_linear_current_cycle,
+            get_linear_estimate_minutes,
+            create_timeboxes_from_linear_issues,
+            // Linear issue link commands
+            validate_linear_issue_links,
+            // Linear project refresh commands
+            refresh_linear_projects,
             // Linear search commands
             search_linear_teams,
             search_linear_projects,
@@ -100,6 +205,35 @@ pub fn run() {
             get_idle_settings,
             set_idle_settings,
             auto_stop_timebox,
+            // Shortcut commands
+            get_shortcut_settings,
+            set_shortcut_settings,
+            // HTTP API commands
+            get_http_api_settings,
+            set_http_api_settings,
+            regenerate_http_api_token,
+            // Webhook commands
+            get_webhook_deliveries,
+            retry_webhook_delivery,
+            test_webhook,
+            // Outbox commands
+            get_outbox_items,
+            retry_outbox_item,
+            discard_outbox_item,
+            // Shell hook commands
+            get_shell_hooks_enabled,
+            set_shell_hooks_enabled,
+            get_shell_hooks,
+            create_shell_hook,
+            update_shell_hook,
+            delete_shell_hook,
+            get_shell_hook_runs,
+            // Quick capture commands
+            parse_quick_capture,
+            quick_capture_timebox,
+            // Duration commands
+            parse_duration,
+            get_duration_presets,
             // Sound commands
             play_system_sound,
         ])
@@ -0,0 +1,28 @@
+use crate::commands::{cache_missing_team_states, refresh_saved_projects};
+use crate::integrations::get_linear_config;
+use crate::state::AppState;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Refreshes saved Linear projects at startup and then hourly, while Linear is connected.
+/// Also fetches the workflow states default state rules are still missing.
+pub fn start_linear_project_refresh(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            let connected = state.db.lock().map(|conn| get_linear_config(&conn).is_some()).unwrap_or(false);
+            if connected {
+                if let Err(e) = refresh_saved_projects(&state).await {
+                    eprintln!("Failed to refresh Linear projects: {}", e);
+                }
+                if let Err(e) = cache_missing_team_states(&state).await {
+                    eprintln!("Failed to fetch Linear workflow states: {}", e);
+                }
+            }
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,169 @@
+//! A local HTTP stand-in for the APIs integrations talk to. Tests queue canned
+//! responses, point an integration's `api_base_url` at `url()` and then inspect
+//! the requests that were made.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tiny_http::{Header, Response, Server};
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub authorization: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).expect("request body is not JSON")
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The `query` string of a GraphQL request
+    pub fn graphql_query(&self) -> String {
+        self.json()["query"].as_str().expect("request has no GraphQL query").to_string()
+    }
+}
+
+struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+    delay: Duration,
+}
+
+pub struct MockServer {
+    server: Arc<Server>,
+    url: String,
+    responses: Arc<Mutex<VecDeque<MockResponse>>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start() -> Self {
+        let server = Arc::new(Server::http("127.0.0.1:0").expect("Failed to start mock server"));
+        let url = format!("http://{}", server.server_addr().to_ip().expect("mock server has no IP address"));
+        let responses: Arc<Mutex<VecDeque<MockResponse>>> = Arc::default();
+        let requests: Arc<Mutex<Vec<RecordedRequest>>> = Arc::default();
+
+        let handle = {
+            let server = Arc::clone(&server);
+            let responses = Arc::clone(&responses);
+            let requests = Arc::clone(&requests);
+            thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let mut body = String::new();
+                    let _ = request.as_reader().read_to_string(&mut body);
+                    let headers: Vec<(String, String)> = request
+                        .headers()
+                        .iter()
+                        .map(|h| (h.field.to_string(), h.value.to_string()))
+                        .collect();
+                    let authorization = headers
+                        .iter()
+                        .find(|(n, _)| n.eq_ignore_ascii_case("Authorization"))
+                        .map(|(_, v)| v.clone());
+
+                    requests.lock().unwrap().push(RecordedRequest {
+                        method: request.method().to_string(),
+                        path: request.url().to_string(),
+                        authorization,
+                        headers,
+                        body,
+                    });
+
+                    let response = responses.lock().unwrap().pop_front().unwrap_or(MockResponse {
+                        status: 500,
+                        headers: Vec::new(),
+                        body: "no response queued".to_string(),
+                        delay: Duration::ZERO,
+                    });
+                    thread::sleep(response.delay);
+                    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+                    let mut reply = Response::from_string(response.body)
+                        .with_status_code(response.status)
+                        .with_header(content_type);
+                    for (name, value) in &response.headers {
+                        reply.add_header(Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap());
+                    }
+                    let _ = request.respond(reply);
+                }
+            })
+        };
+
+        MockServer {
+            server,
+            url,
+            responses,
+            requests,
+            handle: Some(handle),
+        }
+    }
+
+    /// Base URL to use as an integration's `api_base_url`
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// Queues a response; requests are answered in order, with a 500 once the queue is empty
+    pub fn respond(&self, status: u16, body: impl Into<String>) -> &Self {
+        self.respond_with_headers(status, &[], body)
+    }
+
+    pub fn respond_with_headers(&self, status: u16, headers: &[(&str, &str)], body: impl Into<String>) -> &Self {
+        self.responses.lock().unwrap().push_back(MockResponse {
+            status,
+            headers: headers.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+            body: body.into(),
+            delay: Duration::ZERO,
+        });
+        self
+    }
+
+    /// Queues a response that is sent only after `delay`, for checking what happens mid-request
+    pub fn respond_after(&self, delay: Duration, status: u16, body: impl Into<String>) -> &Self {
+        self.responses.lock().unwrap().push_back(MockResponse {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+            delay,
+        });
+        self
+    }
+
+    pub fn respond_json(&self, status: u16, body: serde_json::Value) -> &Self {
+        self.respond(status, body.to_string())
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// A base URL nothing listens on
+pub fn unreachable_url() -> String {
+    let server = Server::http("127.0.0.1:0").expect("Failed to reserve a port");
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    drop(server);
+    url
+}
@@ -3,9 +3,15 @@ pub mod session;
 pub mod timebox_change_log;
 pub mod integration;
 pub mod linear_project;
+pub mod outbox_item;
+pub mod shell_hook;
+pub mod webhook_delivery;
 
 pub use timebox::*;
 pub use session::*;
 pub use timebox_change_log::*;
 pub use integration::*;
 pub use linear_project::*;
+pub use outbox_item::*;
+pub use shell_hook::*;
+pub use webhook_delivery::*;
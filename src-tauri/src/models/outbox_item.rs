@@ -0,0 +1,76 @@
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxStatus {
+    Pending,
+    Delivered,
+    Failed,
+    /// Given up on by the user
+    Discarded,
+}
+
+impl OutboxStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Delivered => "delivered",
+            OutboxStatus::Failed => "failed",
+            OutboxStatus::Discarded => "discarded",
+        }
+    }
+
+    // Infallible (unknown values are pending), so not FromStr
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "delivered" => OutboxStatus::Delivered,
+            "failed" => OutboxStatus::Failed,
+            "discarded" => OutboxStatus::Discarded,
+            _ => OutboxStatus::Pending,
+        }
+    }
+}
+
+/// A side effect on an integration (e.g. a Todoist task), queued with the timebox
+/// change that caused it and delivered by the outbox worker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxItem {
+    pub id: i64,
+    pub integration_id: i64,
+    pub timebox_id: Option<i64>,
+    pub action: String,
+    pub payload: String,
+    pub status: OutboxStatus,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<String>,
+    pub delivered_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Set for actions that must only happen once, see `OutboxAction::dedupe_key`
+    pub dedupe_key: Option<String>,
+}
+
+impl OutboxItem {
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let status: String = row.get(5)?;
+
+        Ok(OutboxItem {
+            id: row.get(0)?,
+            integration_id: row.get(1)?,
+            timebox_id: row.get(2)?,
+            action: row.get(3)?,
+            payload: row.get(4)?,
+            status: OutboxStatus::from_str(&status),
+            attempts: row.get(6)?,
+            last_error: row.get(7)?,
+            next_attempt_at: row.get(8)?,
+            delivered_at: row.get(9)?,
+            created_at: row.get(10)?,
+            updated_at: row.get(11)?,
+            dedupe_key: row.get(12)?,
+        })
+    }
+}
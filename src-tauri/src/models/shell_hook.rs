@@ -0,0 +1,115 @@
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+/// A local command run when a timebox event happens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellHook {
+    pub id: i64,
+    pub event: String,
+    pub command: String,
+    pub timeout_seconds: i64,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SaveShellHookRequest {
+    pub event: String,
+    pub command: String,
+    pub timeout_seconds: Option<i64>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellHookRunStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    TimedOut,
+    /// The app wasn't running when the event happened, and a late run could do more harm than good
+    Skipped,
+}
+
+impl ShellHookRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShellHookRunStatus::Pending => "pending",
+            ShellHookRunStatus::Running => "running",
+            ShellHookRunStatus::Succeeded => "succeeded",
+            ShellHookRunStatus::Failed => "failed",
+            ShellHookRunStatus::TimedOut => "timed_out",
+            ShellHookRunStatus::Skipped => "skipped",
+        }
+    }
+
+    // Infallible (unknown values are pending), so not FromStr
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "running" => ShellHookRunStatus::Running,
+            "succeeded" => ShellHookRunStatus::Succeeded,
+            "failed" => ShellHookRunStatus::Failed,
+            "timed_out" => ShellHookRunStatus::TimedOut,
+            "skipped" => ShellHookRunStatus::Skipped,
+            _ => ShellHookRunStatus::Pending,
+        }
+    }
+}
+
+/// One execution of a shell hook, with what it printed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellHookRun {
+    pub id: i64,
+    pub hook_id: i64,
+    pub timebox_id: Option<i64>,
+    pub event: String,
+    pub command: String,
+    /// TIMEBOXD_* variables as a JSON object, captured when the event happened
+    pub env: String,
+    pub status: ShellHookRunStatus,
+    pub exit_code: Option<i64>,
+    pub stdout: Option<String>,
+    pub stderr: Option<String>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub created_at: String,
+}
+
+impl ShellHook {
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(ShellHook {
+            id: row.get(0)?,
+            event: row.get(1)?,
+            command: row.get(2)?,
+            timeout_seconds: row.get(3)?,
+            enabled: row.get::<_, i64>(4)? != 0,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        })
+    }
+}
+
+impl ShellHookRun {
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let status: String = row.get(6)?;
+
+        Ok(ShellHookRun {
+            id: row.get(0)?,
+            hook_id: row.get(1)?,
+            timebox_id: row.get(2)?,
+            event: row.get(3)?,
+            command: row.get(4)?,
+            env: row.get(5)?,
+            status: ShellHookRunStatus::from_str(&status),
+            exit_code: row.get(7)?,
+            stdout: row.get(8)?,
+            stderr: row.get(9)?,
+            started_at: row.get(10)?,
+            finished_at: row.get(11)?,
+            created_at: row.get(12)?,
+        })
+    }
+}
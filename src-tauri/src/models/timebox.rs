@@ -29,6 +29,8 @@ impl TimeboxStatus {
         }
     }
 
+    // Infallible (unknown values are not_started), so not FromStr
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s {
             "not_started" => TimeboxStatus::NotStarted,
@@ -42,12 +44,45 @@ impl TimeboxStatus {
     }
 }
 
+/// Whether a timebox's Linear issue was still there when its link was last checked
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinearIssueLinkStatus {
+    Ok,
+    Deleted,
+    Archived,
+    /// The issue now belongs to a different project than the timebox
+    Moved,
+}
+
+impl LinearIssueLinkStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinearIssueLinkStatus::Ok => "ok",
+            LinearIssueLinkStatus::Deleted => "deleted",
+            LinearIssueLinkStatus::Archived => "archived",
+            LinearIssueLinkStatus::Moved => "moved",
+        }
+    }
+
+    // Infallible (unknown values are ok), so not FromStr
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "deleted" => LinearIssueLinkStatus::Deleted,
+            "archived" => LinearIssueLinkStatus::Archived,
+            "moved" => LinearIssueLinkStatus::Moved,
+            _ => LinearIssueLinkStatus::Ok,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Timebox {
     pub id: i64,
     pub intention: String,
     pub notes: Option<String>,
-    pub intended_duration: i64, // in seconds
+    pub intended_duration: i64, // in minutes
     pub status: TimeboxStatus,
     pub created_at: String,
     pub updated_at: String,
@@ -63,17 +98,23 @@ pub struct Timebox {
     pub linear_issue_id: Option<String>,
     pub linear_issue_identifier: Option<String>,
     pub linear_issue_url: Option<String>,
+    pub tags: Vec<String>,
+    /// `None` until the link is checked, and again after relinking
+    pub linear_issue_link_status: Option<LinearIssueLinkStatus>,
+    pub linear_issue_checked_at: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CreateTimeboxRequest {
     pub intention: String,
-    pub intended_duration: i64, // in seconds
+    pub intended_duration: i64, // in minutes
     pub notes: Option<String>,
     pub linear_project_id: Option<i64>,
     pub linear_issue_id: Option<String>,
     pub linear_issue_identifier: Option<String>,
     pub linear_issue_url: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -86,6 +127,10 @@ pub struct UpdateTimeboxRequest {
 impl Timebox {
     pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
         let status_str: String = row.get(4)?;
+        let tags_str: Option<String> = row.get(19)?;
+        let tags: Vec<String> = tags_str
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
         Ok(Timebox {
             id: row.get(0)?,
             intention: row.get(1)?,
@@ -106,6 +151,11 @@ impl Timebox {
             linear_issue_id: row.get(16)?,
             linear_issue_identifier: row.get(17)?,
             linear_issue_url: row.get(18)?,
+            tags,
+            linear_issue_link_status: row
+                .get::<_, Option<String>>(20)?
+                .map(|s| LinearIssueLinkStatus::from_str(&s)),
+            linear_issue_checked_at: row.get(21)?,
         })
     }
 }
@@ -0,0 +1,72 @@
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+
+    // Infallible (unknown values are pending), so not FromStr
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "delivered" => WebhookDeliveryStatus::Delivered,
+            "failed" => WebhookDeliveryStatus::Failed,
+            _ => WebhookDeliveryStatus::Pending,
+        }
+    }
+}
+
+/// One event sent (or waiting to be sent) to a webhook integration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub integration_id: i64,
+    pub timebox_id: Option<i64>,
+    pub event: String,
+    pub url: String,
+    pub payload: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i64,
+    pub response_status: Option<i64>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<String>,
+    pub delivered_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl WebhookDelivery {
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let status: String = row.get(6)?;
+
+        Ok(WebhookDelivery {
+            id: row.get(0)?,
+            integration_id: row.get(1)?,
+            timebox_id: row.get(2)?,
+            event: row.get(3)?,
+            url: row.get(4)?,
+            payload: row.get(5)?,
+            status: WebhookDeliveryStatus::from_str(&status),
+            attempts: row.get(7)?,
+            response_status: row.get(8)?,
+            last_error: row.get(9)?,
+            next_attempt_at: row.get(10)?,
+            delivered_at: row.get(11)?,
+            created_at: row.get(12)?,
+            updated_at: row.get(13)?,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+use crate::core::{OutboxAction, OutboxService};
+use crate::integrations::http::{ApiError, ApiErrorKind};
+use crate::integrations::{find_integration, provider};
+use crate::models::{Integration, OutboxItem, OutboxStatus};
+use crate::state::AppState;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+// Items delivered per poll, the rest wait for the next one
+const BATCH_SIZE: i64 = 20;
+
+/// Delivers queued integration side effects in the background
+pub fn start_outbox_worker(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(e) = deliver_due(&app.state::<AppState>()).await {
+                eprintln!("Failed to deliver outbox items: {}", e);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+/// One pass over the due items, returning how many were attempted. The DB lock is
+/// not held while waiting on the network.
+pub async fn deliver_due(state: &AppState) -> Result<usize, String> {
+    let due: Vec<(OutboxItem, Option<Integration>)> = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        OutboxService::new(&conn)
+            .due(BATCH_SIZE)?
+            .into_iter()
+            .map(|item| {
+                let integration = find_integration(&conn, item.integration_id).ok().flatten();
+                (item, integration)
+            })
+            .collect()
+    };
+
+    let attempted = due.len();
+    for (item, integration) in due {
+        // Discarded since the batch was read
+        let pending = {
+            let conn = state.db.lock().map_err(|e| e.to_string())?;
+            OutboxService::new(&conn).get(item.id)?.status == OutboxStatus::Pending
+        };
+        if !pending {
+            continue;
+        }
+
+        let result = deliver(&item, integration.as_ref()).await;
+
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        let outbox = OutboxService::new(&conn);
+        match result {
+            Ok(()) => outbox.record_success(item.id)?,
+            Err(error) => outbox.record_failure(item.id, &error)?,
+        };
+    }
+
+    Ok(attempted)
+}
+
+async fn deliver(item: &OutboxItem, integration: Option<&Integration>) -> Result<(), ApiError> {
+    let rejected = |message: String| ApiError::new(ApiErrorKind::Rejected, message);
+
+    let integration = integration.ok_or_else(|| rejected("Integration no longer exists".to_string()))?;
+    let provider = provider(&integration.integration_type)
+        .ok_or_else(|| rejected(format!("Unknown integration type '{}'", integration.integration_type)))?;
+    let action = OutboxAction::from_item(item).map_err(rejected)?;
+
+    provider.deliver(integration, &action).await
+}
@@ -0,0 +1,158 @@
+use crate::core::{OutboxAction, OutboxService};
+use crate::mock_server::MockServer;
+use crate::models::OutboxStatus;
+use crate::outbox::deliver_due;
+use crate::state::AppState;
+use crate::test_support::{add_integration, test_db};
+use serde_json::json;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::async_runtime::block_on;
+
+fn setup_state(integration_type: &str, config: serde_json::Value) -> (AppState, i64) {
+    let conn = test_db();
+    let integration_id = add_integration(&conn, integration_type, config);
+    (AppState::new(conn), integration_id)
+}
+
+fn enqueue(state: &AppState, integration_id: i64, action: &OutboxAction) -> i64 {
+    let conn = state.db.lock().unwrap();
+    OutboxService::new(&conn).enqueue(integration_id, None, action).unwrap().id
+}
+
+fn item_status(state: &AppState, id: i64) -> (OutboxStatus, i64, Option<String>) {
+    let conn = state.db.lock().unwrap();
+    let item = OutboxService::new(&conn).get(id).unwrap();
+    (item.status, item.attempts, item.last_error)
+}
+
+#[test]
+fn test_delivers_todoist_task() {
+    let server = MockServer::start();
+    server.respond_json(200, json!({ "id": "task-1" })).respond(204, "");
+    let (state, integration) = setup_state("todoist", json!({ "api_token": "token", "api_base_url": server.url() }));
+
+    let action = OutboxAction::todoist_completed_task("Write RFC", None, None);
+    let id = enqueue(&state, integration, &action);
+
+    assert_eq!(block_on(deliver_due(&state)).unwrap(), 1);
+    assert_eq!(item_status(&state, id), (OutboxStatus::Delivered, 1, None));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    let OutboxAction::TodoistCreateCompletedTask { request_id, .. } = action else {
+        unreachable!()
+    };
+    assert_eq!(requests[0].header("X-Request-Id"), Some(request_id.as_str()));
+
+    // Nothing left to deliver
+    assert_eq!(block_on(deliver_due(&state)).unwrap(), 0);
+}
+
+#[test]
+fn test_server_errors_are_retried_later() {
+    let server = MockServer::start();
+    server.respond(503, "Service Unavailable");
+    let (state, integration) = setup_state("linear", json!({ "api_key": "key", "api_base_url": server.url() }));
+
+    let action = OutboxAction::LinearPostComment {
+        issue_id: "issue-1".to_string(),
+        body: "Spent 25 minutes".to_string(),
+        dedupe_key: None,
+        comment_id: None,
+    };
+    let id = enqueue(&state, integration, &action);
+
+    assert_eq!(block_on(deliver_due(&state)).unwrap(), 1);
+    let (status, attempts, error) = item_status(&state, id);
+    assert_eq!(status, OutboxStatus::Pending);
+    assert_eq!(attempts, 1);
+    assert!(error.unwrap().contains("503"));
+
+    // Creating a comment isn't idempotent, so the HTTP layer didn't retry it itself
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(block_on(deliver_due(&state)).unwrap(), 0);
+}
+
+#[test]
+fn test_linear_comment_retry_is_idempotent() {
+    let server = MockServer::start();
+    server
+        .respond_json(200, json!({ "errors": [{ "message": "Entity already exists" }] }))
+        .respond_json(200, json!({ "data": { "comment": { "id": "comment-1" } } }));
+    let (state, integration) = setup_state("linear", json!({ "api_key": "key", "api_base_url": server.url() }));
+
+    let action = OutboxAction::linear_comment("issue-1", "Spent 25 minutes", None);
+    let id = enqueue(&state, integration, &action);
+
+    // The first attempt timed out after Linear created the comment, so the retry is
+    // turned down and the comment found under the id it was sent with
+    assert_eq!(block_on(deliver_due(&state)).unwrap(), 1);
+    assert_eq!(item_status(&state, id), (OutboxStatus::Delivered, 1, None));
+
+    let OutboxAction::LinearPostComment { comment_id: Some(comment_id), .. } = action else {
+        unreachable!()
+    };
+    let requests = server.requests();
+    assert_eq!(requests[0].json()["variables"]["input"]["id"], json!(comment_id));
+    assert_eq!(requests[1].json()["variables"]["id"], json!(comment_id));
+}
+
+#[test]
+fn test_auth_errors_fail_for_good() {
+    let server = MockServer::start();
+    server.respond(401, "Unauthorized");
+    let (state, integration) = setup_state("todoist", json!({ "api_token": "bad", "api_base_url": server.url() }));
+
+    let id = enqueue(&state, integration, &OutboxAction::todoist_completed_task("Write RFC", None, None));
+
+    assert_eq!(block_on(deliver_due(&state)).unwrap(), 1);
+    let (status, attempts, _) = item_status(&state, id);
+    assert_eq!(status, OutboxStatus::Failed);
+    assert_eq!(attempts, 1);
+}
+
+#[test]
+fn test_unsupported_actions_fail_for_good() {
+    let (state, integration) = setup_state("todoist", json!({ "api_token": "token" }));
+
+    let action = OutboxAction::LinearUpdateIssueState {
+        issue_id: "issue-1".to_string(),
+        state_id: "state-1".to_string(),
+        dedupe_key: None,
+    };
+    let id = enqueue(&state, integration, &action);
+
+    assert_eq!(block_on(deliver_due(&state)).unwrap(), 1);
+    let (status, _, error) = item_status(&state, id);
+    assert_eq!(status, OutboxStatus::Failed);
+    assert_eq!(error.as_deref(), Some("Todoist can't deliver linear_update_issue_state"));
+}
+
+#[test]
+fn test_db_lock_is_free_during_delivery() {
+    let server = MockServer::start();
+    server
+        .respond_after(Duration::from_millis(500), 200, json!({ "id": "task-1" }).to_string())
+        .respond(204, "");
+    let (state, integration) = setup_state("todoist", json!({ "api_token": "token", "api_base_url": server.url() }));
+    let id = enqueue(&state, integration, &OutboxAction::todoist_completed_task("Write RFC", None, None));
+
+    let state = Arc::new(state);
+    let worker = {
+        let state = Arc::clone(&state);
+        thread::spawn(move || block_on(deliver_due(&state)))
+    };
+
+    let started = Instant::now();
+    while server.requests().is_empty() {
+        assert!(started.elapsed() < Duration::from_secs(5), "Delivery never reached the server");
+        thread::sleep(Duration::from_millis(10));
+    }
+    // Todoist hasn't answered yet, but other commands can use the database
+    assert!(state.db.try_lock().is_ok());
+
+    assert_eq!(worker.join().unwrap().unwrap(), 1);
+    assert_eq!(item_status(&state, id).0, OutboxStatus::Delivered);
+}
@@ -0,0 +1,106 @@
+use super::duration::parse_duration_details;
+use super::quick_capture::is_issue_identifier;
+use crate::models::CreateTimeboxRequest;
+use chrono::NaiveDateTime;
+use url::Url;
+
+pub const DEEP_LINK_SCHEME: &str = "timeboxd";
+
+// Links come from issue descriptions and wikis, so keep what they can put in the UI sane
+const MAX_INTENTION_LENGTH: usize = 500;
+const MAX_NOTES_LENGTH: usize = 5000;
+
+/// A `timeboxd://` link resolved into a timebox to create
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeepLink {
+    pub request: CreateTimeboxRequest,
+    /// `timeboxd://start` starts the timebox right away, `timeboxd://new` only adds it
+    pub start: bool,
+}
+
+/// Parses links like `timeboxd://start?intention=Review%20PR&duration=25m&linear=ENG-42`.
+///
+/// Supported parameters: `intention` and `duration` (required), `linear` (an issue
+/// identifier), `notes` and `tags` (comma separated). Unknown parameters are ignored
+/// so older versions keep opening links written for newer ones.
+pub fn parse_deep_link(input: &str, now: NaiveDateTime) -> Result<DeepLink, String> {
+    let url = Url::parse(input.trim()).map_err(|e| format!("Invalid link: {}", e))?;
+
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("Not a {}:// link", DEEP_LINK_SCHEME));
+    }
+
+    // `timeboxd://start` has "start" as the host, `timeboxd:start` as the path
+    let action = url
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| url.path().trim_matches('/'))
+        .to_lowercase();
+    let start = match action.as_str() {
+        "start" => true,
+        "new" | "create" => false,
+        "" => return Err("Link has no action, use timeboxd://start or timeboxd://new".to_string()),
+        other => return Err(format!("Unknown link action '{}'", other)),
+    };
+
+    let mut intention = None;
+    let mut duration = None;
+    let mut linear = None;
+    let mut notes = None;
+    let mut tags: Vec<String> = Vec::new();
+
+    for (key, value) in url.query_pairs() {
+        let value = value.trim().to_string();
+        match key.as_ref() {
+            "intention" => intention = Some(value),
+            "duration" => duration = Some(value),
+            "linear" => linear = Some(value),
+            "notes" => notes = Some(value),
+            "tags" => {
+                for tag in value.split(',').map(|t| t.trim().trim_start_matches('#')) {
+                    if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                        tags.push(tag.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let intention = intention
+        .filter(|i| !i.is_empty())
+        .ok_or_else(|| "Link is missing an intention".to_string())?;
+    if intention.chars().count() > MAX_INTENTION_LENGTH {
+        return Err(format!("Intention can't be longer than {} characters", MAX_INTENTION_LENGTH));
+    }
+
+    let duration = duration
+        .filter(|d| !d.is_empty())
+        .ok_or_else(|| "Link is missing a duration".to_string())?;
+    let parsed = parse_duration_details(&duration, now).map_err(|e| e.to_string())?;
+
+    let linear_issue_identifier = match linear.filter(|l| !l.is_empty()) {
+        Some(identifier) if is_issue_identifier(&identifier) => Some(identifier.to_uppercase()),
+        Some(identifier) => return Err(format!("\"{}\" is not a Linear issue identifier", identifier)),
+        None => None,
+    };
+
+    let notes = notes.filter(|n| !n.is_empty());
+    if notes.as_ref().is_some_and(|n| n.chars().count() > MAX_NOTES_LENGTH) {
+        return Err(format!("Notes can't be longer than {} characters", MAX_NOTES_LENGTH));
+    }
+
+    Ok(DeepLink {
+        request: CreateTimeboxRequest {
+            intention,
+            intended_duration: parsed.intended_duration,
+            notes,
+            linear_project_id: None,
+            linear_issue_id: None,
+            linear_issue_identifier,
+            linear_issue_url: None,
+            tags,
+        },
+        start,
+    })
+}
@@ -0,0 +1,82 @@
+use super::deep_link;
+use super::deep_link::DeepLink;
+use chrono::{NaiveDate, NaiveDateTime};
+
+fn now() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 3, 14)
+        .unwrap()
+        .and_hms_opt(13, 0, 0)
+        .unwrap()
+}
+
+fn parse_deep_link(input: &str) -> Result<DeepLink, String> {
+    deep_link::parse_deep_link(input, now())
+}
+
+#[test]
+fn test_start_link_with_linear_issue() {
+    let link = parse_deep_link("timeboxd://start?intention=Review%20PR&duration=25m&linear=eng-42").unwrap();
+
+    assert!(link.start);
+    assert_eq!(link.request.intention, "Review PR");
+    assert_eq!(link.request.intended_duration, 25);
+    assert_eq!(link.request.linear_issue_identifier.as_deref(), Some("ENG-42"));
+    assert!(link.request.linear_issue_id.is_none());
+}
+
+#[test]
+fn test_new_link_does_not_start() {
+    let link = parse_deep_link("timeboxd://new?intention=Write+docs&duration=1h30").unwrap();
+
+    assert!(!link.start);
+    assert_eq!(link.request.intention, "Write docs");
+    assert_eq!(link.request.intended_duration, 90);
+    assert!(link.request.linear_issue_identifier.is_none());
+}
+
+#[test]
+fn test_accepts_path_form_and_natural_durations() {
+    let link = parse_deep_link("timeboxd:start?intention=Focus&duration=until%203pm").unwrap();
+
+    assert!(link.start);
+    assert_eq!(link.request.intended_duration, 120);
+}
+
+#[test]
+fn test_parses_notes_and_deduplicated_tags() {
+    let link = parse_deep_link(
+        "timeboxd://new?intention=Triage&duration=15m&notes=From%20runbook&tags=ops,%23oncall,Ops,",
+    )
+    .unwrap();
+
+    assert_eq!(link.request.notes.as_deref(), Some("From runbook"));
+    assert_eq!(link.request.tags, vec!["ops".to_string(), "oncall".to_string()]);
+}
+
+#[test]
+fn test_ignores_unknown_parameters() {
+    let link = parse_deep_link("timeboxd://start?intention=Focus&duration=25m&utm_source=wiki").unwrap();
+
+    assert_eq!(link.request.intention, "Focus");
+}
+
+#[test]
+fn test_rejects_invalid_links() {
+    assert!(parse_deep_link("https://start?intention=Focus&duration=25m").is_err());
+    assert!(parse_deep_link("timeboxd://delete?intention=Focus&duration=25m").is_err());
+    assert!(parse_deep_link("timeboxd://?intention=Focus&duration=25m").is_err());
+    assert!(parse_deep_link("timeboxd://start?duration=25m").is_err());
+    assert!(parse_deep_link("timeboxd://start?intention=%20&duration=25m").is_err());
+    assert!(parse_deep_link("timeboxd://start?intention=Focus").is_err());
+    assert!(parse_deep_link("timeboxd://start?intention=Focus&duration=soon").is_err());
+    assert!(parse_deep_link("timeboxd://start?intention=Focus&duration=48h").is_err());
+    assert!(parse_deep_link("timeboxd://start?intention=Focus&duration=25m&linear=42").is_err());
+}
+
+#[test]
+fn test_rejects_overly_long_intention() {
+    let intention = "a".repeat(501);
+    let link = format!("timeboxd://start?intention={}&duration=25m", intention);
+
+    assert!(parse_deep_link(&link).is_err());
+}
@@ -0,0 +1,290 @@
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const POMODORO_SECONDS: f64 = 25.0 * 60.0;
+const MAX_DURATION_SECONDS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParsedDuration {
+    pub seconds: i64,
+    // Rounded up to whole minutes, which is what timeboxes store
+    pub intended_duration: i64,
+    pub ends_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DurationParseError {
+    Empty,
+    Unrecognized(String),
+    InvalidTime(String),
+    TimeInPast(String),
+    Zero,
+    TooLong,
+}
+
+impl DurationParseError {
+    /// Human readable message in the given locale (e.g. "de-DE"), falling back to English
+    pub fn message(&self, locale: &str) -> String {
+        let language = locale.split(['-', '_']).next().unwrap_or("").to_lowercase();
+
+        match (language.as_str(), self) {
+            ("de", DurationParseError::Empty) => "Gib eine Dauer ein, z. B. 25m oder 1h30".to_string(),
+            ("de", DurationParseError::Unrecognized(s)) => format!("\"{}\" ist keine gültige Dauer", s),
+            ("de", DurationParseError::InvalidTime(s)) => format!("\"{}\" ist keine gültige Uhrzeit", s),
+            ("de", DurationParseError::TimeInPast(s)) => format!("{} ist heute schon vorbei", s),
+            ("de", DurationParseError::Zero) => "Die Dauer muss größer als null sein".to_string(),
+            ("de", DurationParseError::TooLong) => "Die Dauer darf höchstens 24 Stunden betragen".to_string(),
+
+            ("es", DurationParseError::Empty) => "Introduce una duración, p. ej. 25m o 1h30".to_string(),
+            ("es", DurationParseError::Unrecognized(s)) => format!("\"{}\" no es una duración válida", s),
+            ("es", DurationParseError::InvalidTime(s)) => format!("\"{}\" no es una hora válida", s),
+            ("es", DurationParseError::TimeInPast(s)) => format!("Las {} ya han pasado hoy", s),
+            ("es", DurationParseError::Zero) => "La duración debe ser mayor que cero".to_string(),
+            ("es", DurationParseError::TooLong) => "La duración no puede superar las 24 horas".to_string(),
+
+            ("fr", DurationParseError::Empty) => "Saisissez une durée, par ex. 25m ou 1h30".to_string(),
+            ("fr", DurationParseError::Unrecognized(s)) => format!("« {} » n'est pas une durée valide", s),
+            ("fr", DurationParseError::InvalidTime(s)) => format!("« {} » n'est pas une heure valide", s),
+            ("fr", DurationParseError::TimeInPast(s)) => format!("{} est déjà passé aujourd'hui", s),
+            ("fr", DurationParseError::Zero) => "La durée doit être supérieure à zéro".to_string(),
+            ("fr", DurationParseError::TooLong) => "La durée ne peut pas dépasser 24 heures".to_string(),
+
+            (_, DurationParseError::Empty) => "Enter a duration, e.g. 25m or 1h30".to_string(),
+            (_, DurationParseError::Unrecognized(s)) => format!("\"{}\" is not a duration", s),
+            (_, DurationParseError::InvalidTime(s)) => format!("\"{}\" is not a valid time", s),
+            (_, DurationParseError::TimeInPast(s)) => format!("{} has already passed today", s),
+            (_, DurationParseError::Zero) => "Duration must be longer than zero".to_string(),
+            (_, DurationParseError::TooLong) => "Duration can't be longer than 24 hours".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for DurationParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message("en"))
+    }
+}
+
+impl std::error::Error for DurationParseError {}
+
+/// Parses a duration typed by the user into seconds, relative to `now`.
+///
+/// Accepts compact forms ("90m", "1h30", "1.5h"), words ("1 hour 30 minutes",
+/// "half an hour"), pomodoros ("2 pomodoros") and end times ("until 3pm",
+/// "till 15:30"). A bare number is taken as minutes.
+pub fn parse_duration(input: &str, now: NaiveDateTime) -> Result<i64, DurationParseError> {
+    let normalized = input.trim().to_lowercase();
+    if normalized.is_empty() {
+        return Err(DurationParseError::Empty);
+    }
+
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+
+    let seconds = if let Some(rest) = words.first().filter(|w| is_until_keyword(w)).map(|_| &words[1..]) {
+        let time = rest.concat();
+        if time.is_empty() {
+            return Err(DurationParseError::Unrecognized(input.trim().to_string()));
+        }
+        seconds_until(&time, now)?
+    } else {
+        parse_duration_words(&words)
+            .ok_or_else(|| DurationParseError::Unrecognized(input.trim().to_string()))?
+    };
+
+    validate(seconds)
+}
+
+/// Like `parse_duration` but also reports when the duration ends and the rounded minutes.
+pub fn parse_duration_details(input: &str, now: NaiveDateTime) -> Result<ParsedDuration, DurationParseError> {
+    let seconds = parse_duration(input, now)?;
+
+    Ok(ParsedDuration {
+        seconds,
+        intended_duration: (seconds + 59) / 60,
+        ends_at: (now + Duration::seconds(seconds)).format("%Y-%m-%d %H:%M:%S").to_string(),
+    })
+}
+
+/// Finds a duration at the start of `words` for line-based input like quick capture,
+/// returning the seconds and how many words it used. Unlike `parse_duration`, bare
+/// numbers are not durations here so "Read 3 chapters" stays intact.
+pub fn match_duration_words(words: &[&str], now: NaiveDateTime) -> Option<(i64, usize)> {
+    let lowered: Vec<String> = words.iter().take(3).map(|w| w.to_lowercase()).collect();
+    let lowered: Vec<&str> = lowered.iter().map(String::as_str).collect();
+
+    match lowered.as_slice() {
+        [keyword, time, suffix, ..] if is_until_keyword(keyword) && is_meridiem(suffix) => {
+            let seconds = seconds_until(&format!("{}{}", time, suffix), now).ok()?;
+            validate(seconds).ok().map(|s| (s, 3))
+        }
+        [keyword, time, ..] if is_until_keyword(keyword) => {
+            let seconds = seconds_until(time, now).ok()?;
+            validate(seconds).ok().map(|s| (s, 2))
+        }
+        [number, unit, ..] if is_number(number) && unit.chars().all(|c| c.is_alphabetic()) => {
+            let seconds = parse_compact(&format!("{}{}", number, unit), false)?;
+            validate(seconds).ok().map(|s| (s, 2))
+        }
+        [token, ..] => {
+            let seconds = parse_compact(token, false)?;
+            validate(seconds).ok().map(|s| (s, 1))
+        }
+        [] => None,
+    }
+}
+
+fn validate(seconds: i64) -> Result<i64, DurationParseError> {
+    if seconds <= 0 {
+        Err(DurationParseError::Zero)
+    } else if seconds > MAX_DURATION_SECONDS {
+        Err(DurationParseError::TooLong)
+    } else {
+        Ok(seconds)
+    }
+}
+
+fn is_until_keyword(word: &str) -> bool {
+    matches!(word, "until" | "till" | "til")
+}
+
+fn is_meridiem(word: &str) -> bool {
+    matches!(word, "am" | "pm" | "a.m." | "p.m.")
+}
+
+fn is_number(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+/// Handles word forms like "an hour", "half an hour" and "1 hour and 30 minutes"
+fn parse_duration_words(words: &[&str]) -> Option<i64> {
+    let words: Vec<&str> = words.iter().copied().filter(|w| *w != "and").collect();
+
+    match words.as_slice() {
+        ["half", "an", "hour"] | ["half", "hour"] => return Some(30 * 60),
+        ["a" | "an" | "one", rest @ ..] if !rest.is_empty() => {
+            return parse_compact(&format!("1{}", rest.concat()), false);
+        }
+        [unit] if unit_seconds(unit).is_some() => return unit_seconds(unit).map(|s| s as i64),
+        _ => {}
+    }
+
+    parse_compact(&words.concat(), true)
+}
+
+/// Parses compact durations such as "45m", "1h30", "1.5h" or "2pomodoros" into seconds.
+/// A bare number is minutes when `allow_bare_number` is set.
+fn parse_compact(token: &str, allow_bare_number: bool) -> Option<i64> {
+    let token = token.to_lowercase();
+    let mut rest = token.as_str();
+    let mut total = 0.0;
+    let mut matched_unit = false;
+
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        if number_end == 0 {
+            return None;
+        }
+        let value: f64 = rest[..number_end].parse().ok()?;
+        rest = &rest[number_end..];
+
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let unit = &rest[..unit_end];
+        rest = &rest[unit_end..];
+
+        let multiplier = match unit {
+            // Trailing minutes after an hour, as in "1h30"
+            "" if matched_unit => 60.0,
+            "" if allow_bare_number && rest.is_empty() => 60.0,
+            _ => unit_seconds(unit)?,
+        };
+
+        matched_unit = true;
+        total += value * multiplier;
+    }
+
+    if matched_unit && total.is_finite() && total < i64::MAX as f64 {
+        Some(total.round() as i64)
+    } else {
+        None
+    }
+}
+
+fn unit_seconds(unit: &str) -> Option<f64> {
+    match unit {
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(3600.0),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(60.0),
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(1.0),
+        "pomodoro" | "pomodoros" | "pomo" | "pomos" => Some(POMODORO_SECONDS),
+        _ => None,
+    }
+}
+
+/// Seconds from `now` until a clock time later today
+fn seconds_until(time: &str, now: NaiveDateTime) -> Result<i64, DurationParseError> {
+    let (hour, minute, meridiem) =
+        parse_clock_time(time).ok_or_else(|| DurationParseError::InvalidTime(time.to_string()))?;
+
+    let candidates: Vec<u32> = match meridiem {
+        Some(false) => vec![hour % 12],
+        Some(true) => vec![hour % 12 + 12],
+        // "until 3" means whichever 3 o'clock comes next
+        None if (1..=12).contains(&hour) => vec![hour % 12, hour % 12 + 12],
+        None => vec![hour],
+    };
+
+    let mut passed = None;
+    for hour in candidates {
+        let target_time = NaiveTime::from_hms_opt(hour, minute, 0)
+            .ok_or_else(|| DurationParseError::InvalidTime(time.to_string()))?;
+        let target = now.date().and_time(target_time);
+        if target > now {
+            return Ok((target - now).num_seconds());
+        }
+        passed = Some(target_time);
+    }
+
+    let passed = passed.map(|t| t.format("%H:%M").to_string()).unwrap_or_else(|| time.to_string());
+    Err(DurationParseError::TimeInPast(passed))
+}
+
+/// Parses "3pm", "3:30pm", "15:00" or "1530" into (hour, minute, is_pm)
+fn parse_clock_time(time: &str) -> Option<(u32, u32, Option<bool>)> {
+    let time = time.replace('.', "");
+    let (clock, meridiem) = if let Some(clock) = time.strip_suffix("pm") {
+        (clock, Some(true))
+    } else if let Some(clock) = time.strip_suffix("am") {
+        (clock, Some(false))
+    } else {
+        (time.as_str(), None)
+    };
+
+    // Also keeps the byte split below on a char boundary
+    if !clock.chars().all(|c| c.is_ascii_digit() || c == ':') {
+        return None;
+    }
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None if clock.len() > 2 => {
+            let (hour, minute) = clock.split_at(clock.len() - 2);
+            (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?)
+        }
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+
+    let valid_hour = match meridiem {
+        Some(_) => (1..=12).contains(&hour),
+        None => hour < 24,
+    };
+
+    if valid_hour && minute < 60 {
+        Some((hour, minute, meridiem))
+    } else {
+        None
+    }
+}
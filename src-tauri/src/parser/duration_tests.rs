@@ -0,0 +1,126 @@
+use super::duration::{match_duration_words, parse_duration, parse_duration_details, DurationParseError};
+use chrono::{NaiveDate, NaiveDateTime};
+
+fn at(hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 3, 14)
+        .unwrap()
+        .and_hms_opt(hour, minute, 0)
+        .unwrap()
+}
+
+fn minutes(input: &str) -> i64 {
+    parse_duration(input, at(10, 0)).unwrap() / 60
+}
+
+#[test]
+fn test_compact_durations() {
+    assert_eq!(minutes("90m"), 90);
+    assert_eq!(minutes("1h30"), 90);
+    assert_eq!(minutes("1h30m"), 90);
+    assert_eq!(minutes("1h 30m"), 90);
+    assert_eq!(minutes("1.5h"), 90);
+    assert_eq!(minutes("2H"), 120);
+}
+
+#[test]
+fn test_bare_number_is_minutes() {
+    assert_eq!(minutes("45"), 45);
+    assert_eq!(minutes(" 15 "), 15);
+}
+
+#[test]
+fn test_word_durations() {
+    assert_eq!(minutes("90 minutes"), 90);
+    assert_eq!(minutes("1 hour 30 minutes"), 90);
+    assert_eq!(minutes("1 hour and 30 minutes"), 90);
+    assert_eq!(minutes("an hour"), 60);
+    assert_eq!(minutes("half an hour"), 30);
+}
+
+#[test]
+fn test_pomodoros() {
+    assert_eq!(minutes("2 pomodoros"), 50);
+    assert_eq!(minutes("a pomodoro"), 25);
+    assert_eq!(minutes("pomodoro"), 25);
+    assert_eq!(minutes("3pomos"), 75);
+}
+
+#[test]
+fn test_until_clock_time() {
+    let now = at(13, 20);
+
+    assert_eq!(parse_duration("until 3pm", now).unwrap(), 100 * 60);
+    assert_eq!(parse_duration("until 3 pm", now).unwrap(), 100 * 60);
+    assert_eq!(parse_duration("till 3:30pm", now).unwrap(), 130 * 60);
+    assert_eq!(parse_duration("until 15:00", now).unwrap(), 100 * 60);
+    assert_eq!(parse_duration("until 1530", now).unwrap(), 130 * 60);
+}
+
+#[test]
+fn test_until_without_meridiem_uses_next_occurrence() {
+    // 3 o'clock has passed in the morning, so "until 3" means 3pm
+    assert_eq!(parse_duration("until 3", at(13, 0)).unwrap(), 120 * 60);
+    assert_eq!(parse_duration("until 11", at(10, 0)).unwrap(), 60 * 60);
+}
+
+#[test]
+fn test_until_time_in_past() {
+    assert_eq!(
+        parse_duration("until 9am", at(10, 0)),
+        Err(DurationParseError::TimeInPast("09:00".to_string()))
+    );
+}
+
+#[test]
+fn test_invalid_input() {
+    let now = at(10, 0);
+
+    assert_eq!(parse_duration("  ", now), Err(DurationParseError::Empty));
+    assert_eq!(parse_duration("soon", now), Err(DurationParseError::Unrecognized("soon".to_string())));
+    assert_eq!(parse_duration("until 25:00", now), Err(DurationParseError::InvalidTime("25:00".to_string())));
+    assert_eq!(parse_duration("0m", now), Err(DurationParseError::Zero));
+    assert_eq!(parse_duration("25h", now), Err(DurationParseError::TooLong));
+}
+
+#[test]
+fn test_until_non_ascii_time_is_invalid() {
+    let now = at(10, 0);
+
+    assert_eq!(parse_duration("until €", now), Err(DurationParseError::InvalidTime("€".to_string())));
+    assert_eq!(parse_duration("until 1€", now), Err(DurationParseError::InvalidTime("1€".to_string())));
+    assert_eq!(parse_duration("till 12€pm", now), Err(DurationParseError::InvalidTime("12€pm".to_string())));
+    assert_eq!(match_duration_words(&["until", "1€", "x"], now), None);
+}
+
+#[test]
+fn test_error_messages_are_localized() {
+    let error = DurationParseError::Unrecognized("soon".to_string());
+
+    assert_eq!(error.message("en-US"), "\"soon\" is not a duration");
+    assert_eq!(error.message("de-DE"), "\"soon\" ist keine gültige Dauer");
+    assert_eq!(error.message("es"), "\"soon\" no es una duración válida");
+    // Unknown locales fall back to English
+    assert_eq!(error.message("ja-JP"), error.to_string());
+}
+
+#[test]
+fn test_details_round_up_to_minutes() {
+    let details = parse_duration_details("90s", at(10, 0)).unwrap();
+
+    assert_eq!(details.seconds, 90);
+    assert_eq!(details.intended_duration, 2);
+    assert_eq!(details.ends_at, "2025-03-14 10:01:30");
+}
+
+#[test]
+fn test_match_duration_words() {
+    let now = at(13, 0);
+
+    assert_eq!(match_duration_words(&["45m", "#tag"], now), Some((45 * 60, 1)));
+    assert_eq!(match_duration_words(&["2", "pomodoros", "x"], now), Some((50 * 60, 2)));
+    assert_eq!(match_duration_words(&["until", "3", "pm"], now), Some((120 * 60, 3)));
+    assert_eq!(match_duration_words(&["until", "3pm"], now), Some((120 * 60, 2)));
+    // Bare numbers and plain words are not durations in running text
+    assert_eq!(match_duration_words(&["3", "chapters"], now), None);
+    assert_eq!(match_duration_words(&["Read"], now), None);
+}
@@ -0,0 +1,14 @@
+pub mod deep_link;
+pub mod duration;
+pub mod quick_capture;
+
+#[cfg(test)]
+mod deep_link_tests;
+#[cfg(test)]
+mod duration_tests;
+#[cfg(test)]
+mod quick_capture_tests;
+
+pub use deep_link::*;
+pub use duration::*;
+pub use quick_capture::*;
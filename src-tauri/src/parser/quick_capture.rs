@@ -0,0 +1,70 @@
+use super::duration::match_duration_words;
+use crate::models::CreateTimeboxRequest;
+use chrono::NaiveDateTime;
+
+/// Parses a single quick-capture line like "Write RFC draft 45m #writing @ENG-123"
+/// into a timebox request.
+///
+/// - `45m`, `1h30`, `2 pomodoros`, `until 3pm` set the duration (the last one wins)
+/// - `#tag` adds a tag
+/// - `@ENG-123` links a Linear issue by its identifier
+///
+/// Everything else becomes the intention.
+pub fn parse_quick_capture(input: &str, now: NaiveDateTime) -> Result<CreateTimeboxRequest, String> {
+    let mut words: Vec<&str> = Vec::new();
+    let mut duration_seconds: Option<i64> = None;
+    let mut tags: Vec<String> = Vec::new();
+    let mut linear_issue_identifier: Option<String> = None;
+
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let token = tokens[index];
+        index += 1;
+
+        if let Some(tag) = token.strip_prefix('#').filter(|t| !t.is_empty()) {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        } else if let Some(identifier) = token.strip_prefix('@').filter(|t| is_issue_identifier(t)) {
+            linear_issue_identifier = Some(identifier.to_uppercase());
+        } else if let Some((seconds, used)) = match_duration_words(&tokens[index - 1..], now) {
+            duration_seconds = Some(seconds);
+            index += used - 1;
+        } else {
+            words.push(token);
+        }
+    }
+
+    let intention = words.join(" ");
+    if intention.is_empty() {
+        return Err("Add what you're working on, e.g. \"Write RFC draft 45m\"".to_string());
+    }
+
+    let seconds = duration_seconds
+        .ok_or_else(|| "Add a duration, e.g. \"25m\", \"1h30\" or \"until 3pm\"".to_string())?;
+
+    Ok(CreateTimeboxRequest {
+        intention,
+        // The UI stores intended_duration in minutes
+        intended_duration: (seconds + 59) / 60,
+        notes: None,
+        linear_project_id: None,
+        linear_issue_id: None,
+        linear_issue_identifier,
+        linear_issue_url: None,
+        tags,
+    })
+}
+
+/// Linear identifiers are a team key and a number, e.g. "ENG-123"
+pub(crate) fn is_issue_identifier(s: &str) -> bool {
+    let Some((team, number)) = s.split_once('-') else {
+        return false;
+    };
+    team.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && team.chars().all(|c| c.is_ascii_alphanumeric())
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
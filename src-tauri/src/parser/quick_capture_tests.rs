@@ -0,0 +1,86 @@
+use super::quick_capture;
+use chrono::{NaiveDate, NaiveDateTime};
+
+fn now() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 3, 14)
+        .unwrap()
+        .and_hms_opt(13, 0, 0)
+        .unwrap()
+}
+
+fn parse_quick_capture(input: &str) -> Result<crate::models::CreateTimeboxRequest, String> {
+    quick_capture::parse_quick_capture(input, now())
+}
+
+#[test]
+fn test_parses_intention_duration_tags_and_issue() {
+    let request = parse_quick_capture("Write RFC draft 45m #writing @ENG-123").unwrap();
+
+    assert_eq!(request.intention, "Write RFC draft");
+    assert_eq!(request.intended_duration, 45);
+    assert_eq!(request.tags, vec!["writing".to_string()]);
+    assert_eq!(request.linear_issue_identifier.as_deref(), Some("ENG-123"));
+    assert!(request.linear_issue_id.is_none());
+}
+
+#[test]
+fn test_tokens_can_appear_anywhere() {
+    let request = parse_quick_capture("#deep 1h30m Review @eng-42 the PR").unwrap();
+
+    assert_eq!(request.intention, "Review the PR");
+    assert_eq!(request.intended_duration, 90);
+    assert_eq!(request.tags, vec!["deep".to_string()]);
+    assert_eq!(request.linear_issue_identifier.as_deref(), Some("ENG-42"));
+}
+
+#[test]
+fn test_duration_formats() {
+    let minutes = |input: &str| parse_quick_capture(input).unwrap().intended_duration;
+
+    assert_eq!(minutes("Task 25m"), 25);
+    assert_eq!(minutes("Task 90min"), 90);
+    assert_eq!(minutes("Task 2h"), 120);
+    assert_eq!(minutes("Task 1h30"), 90);
+    assert_eq!(minutes("Task 1H15M"), 75);
+}
+
+#[test]
+fn test_natural_language_durations() {
+    let request = parse_quick_capture("Deep work until 3pm #focus").unwrap();
+    assert_eq!(request.intention, "Deep work");
+    assert_eq!(request.intended_duration, 120);
+
+    let request = parse_quick_capture("Write tests 2 pomodoros").unwrap();
+    assert_eq!(request.intention, "Write tests");
+    assert_eq!(request.intended_duration, 50);
+}
+
+#[test]
+fn test_last_duration_wins() {
+    let request = parse_quick_capture("Task 25m 45m").unwrap();
+    assert_eq!(request.intended_duration, 45);
+}
+
+#[test]
+fn test_duplicate_tags_are_ignored() {
+    let request = parse_quick_capture("Task 25m #a #A #b").unwrap();
+    assert_eq!(request.tags, vec!["a".to_string(), "b".to_string()]);
+}
+
+#[test]
+fn test_non_duration_words_stay_in_intention() {
+    let request = parse_quick_capture("Read m 3 chapters 20m @someone #").unwrap();
+    assert_eq!(request.intention, "Read m 3 chapters @someone #");
+    assert!(request.linear_issue_identifier.is_none());
+}
+
+#[test]
+fn test_missing_duration_is_an_error() {
+    assert!(parse_quick_capture("Write RFC draft #writing").is_err());
+}
+
+#[test]
+fn test_missing_intention_is_an_error() {
+    assert!(parse_quick_capture("45m #writing @ENG-1").is_err());
+    assert!(parse_quick_capture("   ").is_err());
+}
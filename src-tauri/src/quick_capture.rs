@@ -0,0 +1,29 @@
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// Label of the quick-capture window, the frontend renders a different root for it
+pub const QUICK_CAPTURE_WINDOW: &str = "quick-capture";
+
+/// Opens the quick-capture window, creating it on first use.
+pub fn open_quick_capture(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_CAPTURE_WINDOW) {
+        let _ = window.center();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+
+    let result = WebviewWindowBuilder::new(app, QUICK_CAPTURE_WINDOW, WebviewUrl::App("index.html".into()))
+        .title("Quick capture")
+        .inner_size(560.0, 120.0)
+        .resizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .center()
+        .focused(true)
+        .build();
+
+    if let Err(e) = result {
+        eprintln!("Failed to open quick capture window: {}", e);
+    }
+}
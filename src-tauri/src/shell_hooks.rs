@@ -0,0 +1,228 @@
+use crate::core::ShellHookService;
+use crate::models::{ShellHookRun, ShellHookRunStatus};
+use crate::state::AppState;
+use chrono::Local;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+// Events older than this when the worker sees them are skipped, e.g. ones queued
+// by the CLI while the app was closed. Muting notifications an hour late helps no one.
+const STALE_AFTER: chrono::Duration = chrono::Duration::minutes(2);
+// Output kept in the run log, per stream
+const MAX_OUTPUT_BYTES: usize = 4096;
+// How long to wait for the output pipes to close once the command has exited. Processes
+// it started in the background (`some-daemon &`) may hold them open for much longer.
+const OUTPUT_GRACE: Duration = Duration::from_millis(500);
+
+pub struct CommandOutcome {
+    pub status: ShellHookRunStatus,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Runs queued shell hook runs in the background, one at a time so that hooks for
+/// consecutive events (start, then pause) run in order.
+pub fn start_shell_hook_worker(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        if let Ok(conn) = app.state::<AppState>().db.lock() {
+            if let Err(e) = ShellHookService::new(&conn).fail_interrupted() {
+                eprintln!("Failed to clean up shell hook runs: {}", e);
+            }
+        }
+
+        loop {
+            if let Err(e) = run_pending(&app) {
+                eprintln!("Failed to run shell hooks: {}", e);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+fn run_pending(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+
+    let runs: Vec<(ShellHookRun, i64)> = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        let hooks = ShellHookService::new(&conn);
+        let not_before = (Local::now().naive_local() - STALE_AFTER)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        hooks
+            .take_pending(&not_before)?
+            .into_iter()
+            .map(|run| {
+                let timeout = hooks.get(run.hook_id).map(|h| h.timeout_seconds).unwrap_or(10);
+                (run, timeout)
+            })
+            .collect()
+    };
+
+    // The DB lock is not held while commands run
+    for (run, timeout_seconds) in runs {
+        let env: BTreeMap<String, String> = serde_json::from_str(&run.env).unwrap_or_default();
+        let outcome = run_command(&run.command, &env, Duration::from_secs(timeout_seconds as u64));
+
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        let run = ShellHookService::new(&conn).record_result(
+            run.id,
+            outcome.status,
+            outcome.exit_code,
+            &outcome.stdout,
+            &outcome.stderr,
+        )?;
+        drop(conn);
+
+        if run.status != ShellHookRunStatus::Succeeded {
+            notify_failure(app, &run);
+        }
+    }
+
+    Ok(())
+}
+
+fn notify_failure(app: &AppHandle, run: &ShellHookRun) {
+    let reason = match run.status {
+        ShellHookRunStatus::TimedOut => "timed out".to_string(),
+        _ => match run.exit_code {
+            Some(code) => format!("exited with {}", code),
+            None => "failed".to_string(),
+        },
+    };
+    let detail = run.stderr.as_deref().and_then(|s| s.lines().next()).unwrap_or("");
+
+    eprintln!("Shell hook '{}' {}: {}", run.command, reason, detail);
+    let _ = app
+        .notification()
+        .builder()
+        .title(format!("Shell hook {}", reason))
+        .body(format!("{}\n{}", run.command, detail).trim().to_string())
+        .show();
+}
+
+/// Runs a command through the platform shell with extra environment variables,
+/// killing it once `timeout` has passed.
+pub fn run_command(command: &str, env: &BTreeMap<String, String>, timeout: Duration) -> CommandOutcome {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    let spawned = shell
+        .arg(command)
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            return CommandOutcome {
+                status: ShellHookRunStatus::Failed,
+                exit_code: None,
+                stdout: String::new(),
+                stderr: format!("Failed to start command: {}", e),
+            }
+        }
+    };
+
+    // Read output on separate threads so a chatty command can't block on a full pipe
+    let stdout = child.stdout.take().map(read_limited);
+    let stderr = child.stderr.take().map(read_limited);
+
+    let started = Instant::now();
+    let (status, exit_code) = loop {
+        match child.try_wait() {
+            Ok(Some(exit)) if exit.success() => break (ShellHookRunStatus::Succeeded, exit.code()),
+            Ok(Some(exit)) => break (ShellHookRunStatus::Failed, exit.code()),
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break (ShellHookRunStatus::TimedOut, None);
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(e) => {
+                let _ = child.kill();
+                return CommandOutcome {
+                    status: ShellHookRunStatus::Failed,
+                    exit_code: None,
+                    stdout: String::new(),
+                    stderr: e.to_string(),
+                };
+            }
+        }
+    };
+
+    // A command that timed out already had its chance, so keep only what it printed so far
+    let timed_out = status == ShellHookRunStatus::TimedOut;
+    let deadline = Instant::now() + if timed_out { Duration::ZERO } else { OUTPUT_GRACE };
+    let collect = |reader: Option<OutputReader>| reader.map(|r| r.collect(deadline)).unwrap_or_default();
+    let stdout = collect(stdout);
+    let stderr = match timed_out {
+        true => format!("Timed out after {}s", timeout.as_secs()),
+        false => collect(stderr),
+    };
+
+    CommandOutcome {
+        status,
+        exit_code,
+        stdout,
+        stderr,
+    }
+}
+
+/// Output of one stream, captured on a background thread
+struct OutputReader {
+    buffer: Arc<Mutex<Vec<u8>>>,
+    closed: Receiver<()>,
+}
+
+impl OutputReader {
+    /// What was read so far, once the stream has closed or `deadline` has passed
+    fn collect(self, deadline: Instant) -> String {
+        let _ = self.closed.recv_timeout(deadline.saturating_duration_since(Instant::now()));
+        let buffer = self.buffer.lock().map(|b| b.clone()).unwrap_or_default();
+        String::from_utf8_lossy(&buffer).trim_end().to_string()
+    }
+}
+
+fn read_limited(stream: impl Read + Send + 'static) -> OutputReader {
+    let buffer = Arc::new(Mutex::new(Vec::new()));
+    let (closed_tx, closed) = mpsc::channel();
+
+    let shared = buffer.clone();
+    thread::spawn(move || {
+        let mut stream = stream;
+        let mut chunk = [0u8; 1024];
+        // Keep draining past the limit so the command doesn't block on a full pipe
+        while let Ok(n) = stream.read(&mut chunk) {
+            if n == 0 {
+                break;
+            }
+            if let Ok(mut buffer) = shared.lock() {
+                let keep = n.min(MAX_OUTPUT_BYTES.saturating_sub(buffer.len()));
+                buffer.extend_from_slice(&chunk[..keep]);
+            }
+        }
+        let _ = closed_tx.send(());
+    });
+
+    OutputReader { buffer, closed }
+}
@@ -0,0 +1,56 @@
+use crate::models::ShellHookRunStatus;
+use crate::shell_hooks::run_command;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+fn env() -> BTreeMap<String, String> {
+    let mut env = BTreeMap::new();
+    env.insert("TIMEBOXD_INTENTION".to_string(), "Write RFC".to_string());
+    env
+}
+
+#[test]
+fn test_passes_env_and_captures_output() {
+    let outcome = run_command("echo \"$TIMEBOXD_INTENTION\"; echo oops >&2", &env(), Duration::from_secs(5));
+
+    assert_eq!(outcome.status, ShellHookRunStatus::Succeeded);
+    assert_eq!(outcome.exit_code, Some(0));
+    assert_eq!(outcome.stdout, "Write RFC");
+    assert_eq!(outcome.stderr, "oops");
+}
+
+#[test]
+fn test_reports_exit_code() {
+    let outcome = run_command("exit 3", &env(), Duration::from_secs(5));
+
+    assert_eq!(outcome.status, ShellHookRunStatus::Failed);
+    assert_eq!(outcome.exit_code, Some(3));
+}
+
+#[test]
+fn test_kills_commands_that_time_out() {
+    let outcome = run_command("sleep 5", &env(), Duration::from_millis(200));
+
+    assert_eq!(outcome.status, ShellHookRunStatus::TimedOut);
+    assert_eq!(outcome.exit_code, None);
+}
+
+#[test]
+fn test_truncates_long_output() {
+    let outcome = run_command("yes | head -c 100000", &env(), Duration::from_secs(5));
+
+    assert_eq!(outcome.status, ShellHookRunStatus::Succeeded);
+    assert!(outcome.stdout.len() <= 4096);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_background_children_dont_hold_up_the_run() {
+    let started = Instant::now();
+    let outcome = run_command("echo started; sleep 5 &", &env(), Duration::from_secs(10));
+
+    // The backgrounded sleep keeps the pipes open, but the run is over once the shell exits
+    assert!(started.elapsed() < Duration::from_secs(3));
+    assert_eq!(outcome.status, ShellHookRunStatus::Succeeded);
+    assert_eq!(outcome.stdout, "started");
+}
@@ -0,0 +1,106 @@
+use crate::actions::{current_timebox, spawn_timebox_action, TimeboxAction};
+use crate::commands::ShortcutSettings;
+use crate::models::TimeboxStatus;
+use crate::quick_capture::open_quick_capture;
+use std::str::FromStr;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ShortcutAction {
+    TogglePause,
+    FinishCurrent,
+    QuickCreate,
+}
+
+impl ShortcutAction {
+    fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::TogglePause => "Pause/resume",
+            ShortcutAction::FinishCurrent => "Finish current",
+            ShortcutAction::QuickCreate => "Quick create",
+        }
+    }
+}
+
+/// Parses the configured shortcuts, rejecting invalid accelerators and shortcuts
+/// bound to more than one action.
+fn parse_shortcuts(settings: &ShortcutSettings) -> Result<Vec<(ShortcutAction, Shortcut)>, String> {
+    let configured = [
+        (ShortcutAction::TogglePause, &settings.toggle_pause),
+        (ShortcutAction::FinishCurrent, &settings.finish_current),
+        (ShortcutAction::QuickCreate, &settings.quick_create),
+    ];
+
+    let mut parsed: Vec<(ShortcutAction, Shortcut)> = Vec::new();
+
+    for (action, accelerator) in configured {
+        let Some(accelerator) = accelerator.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+            continue;
+        };
+
+        let shortcut = Shortcut::from_str(accelerator)
+            .map_err(|e| format!("{} shortcut '{}' is invalid: {}", action.label(), accelerator, e))?;
+
+        // Compare parsed shortcuts so "Shift+Alt+K" and "Alt+Shift+K" are caught too
+        if let Some((other, _)) = parsed.iter().find(|(_, existing)| *existing == shortcut) {
+            return Err(format!(
+                "{} and {} shortcuts are both set to '{}'",
+                other.label(),
+                action.label(),
+                accelerator
+            ));
+        }
+
+        parsed.push((action, shortcut));
+    }
+
+    Ok(parsed)
+}
+
+/// Replaces all registered global shortcuts with the given settings.
+pub fn register_shortcuts(app: &AppHandle, settings: &ShortcutSettings) -> Result<(), String> {
+    let shortcuts = parse_shortcuts(settings)?;
+    let global_shortcut = app.global_shortcut();
+
+    global_shortcut.unregister_all().map_err(|e| e.to_string())?;
+
+    for (action, shortcut) in shortcuts {
+        global_shortcut
+            .on_shortcut(shortcut, move |app, _shortcut, event| {
+                if event.state == ShortcutState::Pressed {
+                    dispatch_shortcut(app, action);
+                }
+            })
+            .map_err(|e| {
+                format!(
+                    "{} shortcut could not be registered, it may already be in use: {}",
+                    action.label(),
+                    e
+                )
+            })?;
+    }
+
+    Ok(())
+}
+
+fn dispatch_shortcut(app: &AppHandle, action: ShortcutAction) {
+    match action {
+        ShortcutAction::TogglePause => {
+            if let Some(current) = current_timebox(app) {
+                let toggle = if current.timebox.status == TimeboxStatus::InProgress {
+                    TimeboxAction::Pause
+                } else {
+                    TimeboxAction::Start
+                };
+                spawn_timebox_action(app, toggle, current.timebox.id);
+            }
+        }
+        ShortcutAction::FinishCurrent => {
+            if let Some(current) = current_timebox(app) {
+                spawn_timebox_action(app, TimeboxAction::Finish, current.timebox.id);
+            }
+        }
+        ShortcutAction::QuickCreate => open_quick_capture(app),
+    }
+}
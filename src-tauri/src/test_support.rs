@@ -0,0 +1,61 @@
+//! Fixtures shared by tests across modules
+
+use crate::core::TimeboxService;
+use crate::database::run_migrations;
+use crate::models::CreateTimeboxRequest;
+use crate::state::AppState;
+use rusqlite::{params, Connection};
+use serde_json::{json, Value};
+
+/// In-memory database with foreign keys on and every migration run
+pub fn test_db() -> Connection {
+    let conn = Connection::open_in_memory().expect("Failed to create in-memory database");
+    conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+    run_migrations(&conn).expect("Failed to run migrations");
+    conn
+}
+
+/// Connects an integration named after its type, returning its id
+pub fn add_integration(conn: &Connection, integration_type: &str, config: Value) -> i64 {
+    conn.execute(
+        "INSERT INTO integrations (connection_name, integration_type, connection_config) VALUES (?1, ?1, ?2)",
+        params![integration_type, config.to_string()],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+/// A 25 minute "Write RFC" timebox that isn't linked to anything. Tests that need more
+/// fill in the rest with `..timebox_request()`.
+pub fn timebox_request() -> CreateTimeboxRequest {
+    CreateTimeboxRequest {
+        intention: "Write RFC".to_string(),
+        intended_duration: 25,
+        notes: None,
+        linear_project_id: None,
+        linear_issue_id: None,
+        linear_issue_identifier: None,
+        linear_issue_url: None,
+        tags: Vec::new(),
+    }
+}
+
+/// Creates the `timebox_request()` timebox, returning its id
+pub fn create_timebox(conn: &Connection) -> i64 {
+    TimeboxService::new(conn).create(&timebox_request()).unwrap().id
+}
+
+/// App state on an in-memory database, with Linear connected at `api_base_url`
+pub fn linear_state(api_base_url: String) -> AppState {
+    let conn = test_db();
+    add_integration(&conn, "linear", json!({ "api_key": "key", "api_base_url": api_base_url }));
+    AppState::new(conn)
+}
+
+/// A GraphQL response holding the last page of the connection at `path`, e.g.
+/// `["project", "issues"]`
+pub fn graphql_page(path: &[&str], nodes: Vec<Value>) -> Value {
+    let page = json!({ "nodes": nodes, "pageInfo": { "hasNextPage": false, "endCursor": null } });
+    let data = path.iter().rev().fold(page, |inner, key| json!({ *key: inner }));
+    json!({ "data": data })
+}
@@ -0,0 +1,203 @@
+use crate::actions::{current_timebox, spawn_timebox_action, TimeboxAction};
+use crate::commands::get_today_timeboxes;
+use crate::core::TimeboxWithSessions;
+use crate::models::TimeboxStatus;
+use crate::quick_capture::open_quick_capture;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager, Wry};
+
+const TRAY_ID: &str = "timeboxd-tray";
+
+// Longest intention shown in the tray menu before truncating
+const MAX_MENU_LABEL_LENGTH: usize = 40;
+
+struct TrayState {
+    menu: Menu<Wry>,
+    // Layout of the last rendered menu, so we only rebuild it when something changed
+    layout: Mutex<String>,
+}
+
+/// Creates the tray icon and starts a background thread that keeps its
+/// countdown and menu in sync with the database.
+pub fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
+    // On Linux a tray menu can't be replaced once set, so we keep one menu and swap its items
+    let menu = Menu::new(app)?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip("timeboxd")
+        .on_menu_event(handle_menu_event);
+
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    builder.build(app)?;
+
+    app.manage(TrayState {
+        menu,
+        layout: Mutex::new(String::new()),
+    });
+
+    refresh_tray(app);
+
+    let handle = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs(1));
+        refresh_tray(&handle);
+    });
+
+    Ok(())
+}
+
+/// Updates the tray title/tooltip with the current countdown and rebuilds the menu if needed.
+pub fn refresh_tray(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let today = get_today_timeboxes(app.state()).unwrap_or_default();
+
+    let current = current_timebox(app);
+    let not_started: Vec<TimeboxWithSessions> = today
+        .into_iter()
+        .filter(|t| t.timebox.status == TimeboxStatus::NotStarted)
+        .collect();
+
+    let countdown = current.as_ref().map(|t| format_countdown(t.remaining_seconds()));
+
+    // Title is only shown next to the icon (macOS), so keep it to the running countdown
+    let title = current
+        .as_ref()
+        .filter(|t| t.timebox.status == TimeboxStatus::InProgress)
+        .and(countdown.clone());
+    let _ = tray.set_title(title);
+
+    let tooltip = match (&current, &countdown) {
+        (Some(t), Some(countdown)) if t.timebox.status == TimeboxStatus::Paused => {
+            format!("{} (paused, {})", t.timebox.intention, countdown)
+        }
+        (Some(t), Some(countdown)) => format!("{} ({})", t.timebox.intention, countdown),
+        _ => "timeboxd".to_string(),
+    };
+    let _ = tray.set_tooltip(Some(tooltip));
+
+    if let Err(e) = rebuild_menu_if_changed(app, current.as_ref(), &not_started) {
+        eprintln!("Failed to update tray menu: {}", e);
+    }
+}
+
+/// Formats seconds as mm:ss, prefixed with '-' for overtime (same as the frontend timer).
+fn format_countdown(seconds: i64) -> String {
+    let abs_seconds = seconds.abs();
+    let sign = if seconds < 0 { "-" } else { "" };
+    format!("{}{:02}:{:02}", sign, abs_seconds / 60, abs_seconds % 60)
+}
+
+fn menu_label(intention: &str) -> String {
+    if intention.chars().count() > MAX_MENU_LABEL_LENGTH {
+        let truncated: String = intention.chars().take(MAX_MENU_LABEL_LENGTH - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        intention.to_string()
+    }
+}
+
+fn rebuild_menu_if_changed(
+    app: &AppHandle,
+    current: Option<&TimeboxWithSessions>,
+    not_started: &[TimeboxWithSessions],
+) -> tauri::Result<()> {
+    let tray_state = app.state::<TrayState>();
+
+    let layout = format!(
+        "{:?}|{:?}",
+        current.map(|t| (t.timebox.id, t.timebox.status.as_str(), &t.timebox.intention)),
+        not_started
+            .iter()
+            .map(|t| (t.timebox.id, &t.timebox.intention))
+            .collect::<Vec<_>>()
+    );
+
+    let Ok(mut last_layout) = tray_state.layout.lock() else {
+        return Ok(());
+    };
+    if *last_layout == layout {
+        return Ok(());
+    }
+
+    let menu = &tray_state.menu;
+    for _ in 0..menu.items()?.len() {
+        menu.remove_at(0)?;
+    }
+
+    match current {
+        Some(t) => {
+            let id = t.timebox.id;
+            menu.append(&MenuItem::new(app, menu_label(&t.timebox.intention), false, None::<&str>)?)?;
+            if t.timebox.status == TimeboxStatus::InProgress {
+                menu.append(&MenuItem::with_id(app, format!("pause:{}", id), "Pause", true, None::<&str>)?)?;
+            } else {
+                menu.append(&MenuItem::with_id(app, format!("start:{}", id), "Resume", true, None::<&str>)?)?;
+            }
+            menu.append(&MenuItem::with_id(app, format!("stop:{}", id), "Stop", true, None::<&str>)?)?;
+            menu.append(&MenuItem::with_id(app, format!("finish:{}", id), "Finish", true, None::<&str>)?)?;
+        }
+        None => {
+            menu.append(&MenuItem::new(app, "No timebox running", false, None::<&str>)?)?;
+        }
+    }
+
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+
+    let start_menu = Submenu::new(app, "Start", !not_started.is_empty())?;
+    for t in not_started {
+        start_menu.append(&MenuItem::with_id(
+            app,
+            format!("start:{}", t.timebox.id),
+            menu_label(&t.timebox.intention),
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    menu.append(&start_menu)?;
+    menu.append(&MenuItem::with_id(app, "quick-capture", "Quick capture...", true, None::<&str>)?)?;
+
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, "show", "Show timeboxd", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, "quit", "Quit timeboxd", true, None::<&str>)?)?;
+
+    *last_layout = layout;
+    Ok(())
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let menu_id = event.id().as_ref().to_string();
+
+    match menu_id.as_str() {
+        "show" => show_main_window(app),
+        "quick-capture" => open_quick_capture(app),
+        "quit" => app.exit(0),
+        _ => {
+            let Some((action, id)) = menu_id.split_once(':') else {
+                return;
+            };
+            let (Some(action), Ok(id)) = (TimeboxAction::from_str(action), id.parse::<i64>()) else {
+                return;
+            };
+            spawn_timebox_action(app, action, id);
+        }
+    }
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
@@ -0,0 +1,121 @@
+use crate::core::{sign_webhook_payload, TimeboxService, WebhookConfig, WebhookService};
+use crate::integrations::http::http_client;
+use crate::models::WebhookDelivery;
+use crate::state::AppState;
+use chrono::Local;
+use serde_json::json;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// Deliveries sent per poll, the rest wait for the next one
+const BATCH_SIZE: i64 = 20;
+
+/// Sends queued webhook deliveries in the background. Also queues overtime events
+/// (for shell hooks too), which have no user action to hang off.
+pub fn start_webhook_worker(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            queue_overtime(&app);
+            if let Err(e) = deliver_due(&app).await {
+                eprintln!("Failed to deliver webhooks: {}", e);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+fn queue_overtime(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let Ok(conn) = state.db.lock() else {
+        return;
+    };
+    if let Err(e) = TimeboxService::new(&conn).notify_overtime_all() {
+        eprintln!("Failed to queue overtime events: {}", e);
+    }
+}
+
+async fn deliver_due(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+
+    let due: Vec<(WebhookDelivery, Option<String>)> = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        let webhooks = WebhookService::new(&conn);
+        webhooks
+            .due(BATCH_SIZE)?
+            .into_iter()
+            .map(|d| {
+                let secret = webhooks.secret_for(d.integration_id).ok().flatten();
+                (d, secret)
+            })
+            .collect()
+    };
+
+    // The DB lock is not held while waiting on the network
+    for (delivery, secret) in due {
+        let result = send_webhook(
+            &delivery.url,
+            &delivery.event,
+            Some(delivery.id),
+            &delivery.payload,
+            secret.as_deref(),
+        )
+        .await;
+
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        let webhooks = WebhookService::new(&conn);
+        match result {
+            Ok(status) => webhooks.record_success(delivery.id, status)?,
+            Err((status, error)) => webhooks.record_failure(delivery.id, status, &error)?,
+        };
+    }
+
+    Ok(())
+}
+
+/// Sends a `ping` event, used to check a webhook's URL and secret
+pub async fn ping_webhook(config: &WebhookConfig) -> Result<u16, (Option<u16>, String)> {
+    let payload = json!({
+        "event": "ping",
+        "occurred_at": Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    })
+    .to_string();
+
+    send_webhook(&config.url, "ping", None, &payload, config.secret.as_deref()).await
+}
+
+/// POSTs a payload, returning the response status on 2xx and (status, error) otherwise
+pub async fn send_webhook(
+    url: &str,
+    event: &str,
+    delivery_id: Option<i64>,
+    payload: &str,
+    secret: Option<&str>,
+) -> Result<u16, (Option<u16>, String)> {
+    let mut request = http_client()
+        .post(url)
+        .timeout(REQUEST_TIMEOUT)
+        .header("Content-Type", "application/json")
+        .header("User-Agent", concat!("timeboxd/", env!("CARGO_PKG_VERSION")))
+        .header("X-Timeboxd-Event", event)
+        .body(payload.to_string());
+
+    if let Some(id) = delivery_id {
+        request = request.header("X-Timeboxd-Delivery", id.to_string());
+    }
+    if let Some(secret) = secret {
+        request = request.header("X-Timeboxd-Signature", sign_webhook_payload(secret, payload));
+    }
+
+    let response = request.send().await.map_err(|e| (None, format!("Failed to send webhook: {}", e)))?;
+    let status = response.status();
+
+    if status.is_success() {
+        Ok(status.as_u16())
+    } else {
+        let body = response.text().await.unwrap_or_default();
+        Err((Some(status.as_u16()), format!("Webhook returned status {}: {}", status, body)))
+    }
+}
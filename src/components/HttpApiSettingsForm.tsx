@@ -0,0 +1,109 @@
+import { useState, useEffect } from 'react';
+import { commands } from '../lib/commands';
+import { CopyButton } from './CopyButton';
+import type { HttpApiSettings } from '../lib/types';
+
+export function HttpApiSettingsForm() {
+  const [settings, setSettings] = useState<HttpApiSettings | null>(null);
+  const [port, setPort] = useState('');
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    commands.getHttpApiSettings()
+      .then((loaded) => {
+        setSettings(loaded);
+        setPort(String(loaded.port));
+      })
+      .catch(console.error);
+  }, []);
+
+  const save = async (enabled: boolean) => {
+    const parsedPort = parseInt(port, 10);
+    if (isNaN(parsedPort) || parsedPort < 1024 || parsedPort > 65535) {
+      setError('Port must be between 1024 and 65535');
+      return;
+    }
+    setIsSaving(true);
+    setError(null);
+    try {
+      setSettings(await commands.setHttpApiSettings(enabled, parsedPort));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleRegenerate = async () => {
+    setIsSaving(true);
+    setError(null);
+    try {
+      setSettings(await commands.regenerateHttpApiToken());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  if (!settings) return null;
+
+  const exampleRequest = `curl -H "Authorization: Bearer ${settings.token}" http://127.0.0.1:${settings.port}/timeboxes/today`;
+
+  return (
+    <div className="bg-[#0a0a0a] rounded-lg p-4 border border-neutral-800">
+      <div className="flex items-center justify-between gap-4">
+        <div>
+          <p className="text-sm font-medium text-neutral-300">Local HTTP API</p>
+          <p className="text-xs text-neutral-500">Let scripts and editor plugins control timeboxes on 127.0.0.1</p>
+        </div>
+        <div className="flex items-center gap-2">
+          <input
+            type="number"
+            value={port}
+            onChange={(e) => setPort(e.target.value)}
+            disabled={isSaving || settings.enabled}
+            className="w-24 bg-neutral-900 border border-neutral-700 text-white rounded px-3 py-1.5 text-sm font-mono focus:outline-none focus:border-neutral-600 disabled:opacity-50"
+          />
+          <button
+            onClick={() => save(!settings.enabled)}
+            disabled={isSaving}
+            className={`px-3 py-1.5 text-sm rounded transition-colors disabled:opacity-50 disabled:cursor-not-allowed ${
+              settings.enabled
+                ? 'bg-neutral-800 text-neutral-200 hover:bg-neutral-700'
+                : 'bg-[#5E6AD2] text-white hover:bg-[#4f5ab8]'
+            }`}
+          >
+            {settings.enabled ? 'Disable' : 'Enable'}
+          </button>
+        </div>
+      </div>
+
+      {settings.enabled && (
+        <div className="mt-4 pt-4 border-t border-neutral-800 space-y-3">
+          <div className="flex items-center justify-between gap-3">
+            <p className="text-xs text-neutral-500">Bearer token</p>
+            <div className="flex items-center gap-2 min-w-0">
+              <span className="text-xs font-mono text-neutral-300 truncate">{settings.token}</span>
+              <CopyButton text={settings.token} />
+              <button
+                onClick={handleRegenerate}
+                disabled={isSaving}
+                className="text-xs text-neutral-400 hover:text-neutral-200 disabled:opacity-50"
+              >
+                Regenerate
+              </button>
+            </div>
+          </div>
+          <div className="relative group/example">
+            <pre className="text-xs font-mono text-neutral-400 bg-neutral-900 rounded p-2 overflow-x-auto">{exampleRequest}</pre>
+            <CopyButton text={exampleRequest} className="absolute top-1 right-1 opacity-0 group-hover/example:opacity-100" />
+          </div>
+        </div>
+      )}
+
+      {error && <p className="text-sm text-red-400 mt-3">{error}</p>}
+    </div>
+  );
+}
@@ -1,5 +1,6 @@
 import { useState } from 'react';
 import { commands } from '../lib/commands';
+import { describeApiError } from '../lib/utils';
 
 interface LinearConnectionFormProps {
   onSuccess: () => void;
@@ -11,6 +12,7 @@ type TestStatus = 'idle' | 'testing' | 'success' | 'error';
 export function LinearConnectionForm({ onSuccess, onCancel }: LinearConnectionFormProps) {
   const [connectionName, setConnectionName] = useState('Linear');
   const [apiKey, setApiKey] = useState('');
+  const [apiBaseUrl, setApiBaseUrl] = useState('');
   const [testStatus, setTestStatus] = useState<TestStatus>('idle');
   const [testResult, setTestResult] = useState<{ userName: string; userEmail: string } | null>(null);
   const [error, setError] = useState<string | null>(null);
@@ -27,7 +29,7 @@ export function LinearConnectionForm({ onSuccess, onCancel }: LinearConnectionFo
     setTestResult(null);
 
     try {
-      const result = await commands.testLinearConnection(apiKey.trim());
+      const result = await commands.testLinearConnection(apiKey.trim(), apiBaseUrl.trim() || null);
 
       if (result.success && result.user_name && result.user_email) {
         setTestStatus('success');
@@ -42,7 +44,7 @@ export function LinearConnectionForm({ onSuccess, onCancel }: LinearConnectionFo
     } catch (err) {
       console.error('Failed to test connection:', err);
       setTestStatus('error');
-      setError('Failed to connect to Linear. Please check your internet connection.');
+      setError(describeApiError(err, 'Linear', 'Failed to connect to Linear. Please check your internet connection.'));
     }
   };
 
@@ -58,6 +60,7 @@ export function LinearConnectionForm({ onSuccess, onCancel }: LinearConnectionFo
         integration_type: 'linear',
         connection_config: {
           api_key: apiKey.trim(),
+          ...(apiBaseUrl.trim() && { api_base_url: apiBaseUrl.trim() }),
         },
       });
 
@@ -79,6 +82,15 @@ export function LinearConnectionForm({ onSuccess, onCancel }: LinearConnectionFo
     }
   };
 
+  const handleApiBaseUrlChange = (e: React.ChangeEvent<HTMLInputElement>) => {
+    setApiBaseUrl(e.target.value);
+    if (testStatus !== 'idle') {
+      setTestStatus('idle');
+      setTestResult(null);
+      setError(null);
+    }
+  };
+
   const isDisabled = testStatus === 'testing' || isSaving;
 
   return (
@@ -126,6 +138,22 @@ export function LinearConnectionForm({ onSuccess, onCancel }: LinearConnectionFo
         />
       </div>
 
+      <div className="mb-4">
+        <label htmlFor="apiBaseUrl" className="block text-sm font-medium text-neutral-300 mb-2">
+          API Base URL <span className="text-neutral-500 font-normal">(optional)</span>
+        </label>
+        <input
+          type="url"
+          id="apiBaseUrl"
+          value={apiBaseUrl}
+          onChange={handleApiBaseUrlChange}
+          placeholder="https://api.linear.app"
+          className="w-full px-4 py-2 bg-neutral-900 border border-neutral-800 text-white placeholder-neutral-500 rounded-lg focus:outline-none focus:ring-2 focus:ring-[#5E6AD2]"
+          disabled={isDisabled}
+        />
+        <p className="text-xs text-neutral-500 mt-1">Only needed for a proxy or compatible server</p>
+      </div>
+
       {/* Test result success message */}
       {testStatus === 'success' && testResult && (
         <div className="mb-4 p-3 bg-green-500/10 border border-green-500/30 rounded-lg">
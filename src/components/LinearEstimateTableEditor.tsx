@@ -0,0 +1,114 @@
+import { useState, useEffect } from 'react';
+import { commands } from '../lib/commands';
+import type { EstimateMinutes, LinearConfig } from '../lib/types';
+
+interface LinearEstimateTableEditorProps {
+  integrationId: number;
+}
+
+export function LinearEstimateTableEditor({ integrationId }: LinearEstimateTableEditorProps) {
+  const [rows, setRows] = useState<EstimateMinutes[]>([]);
+  const [dirty, setDirty] = useState(false);
+  const [saving, setSaving] = useState(false);
+
+  const load = () => {
+    commands
+      .getLinearEstimateMinutes()
+      .then((table) => {
+        setRows(table);
+        setDirty(false);
+      })
+      .catch(console.error);
+  };
+
+  useEffect(load, [integrationId]);
+
+  const updateRow = (index: number, field: keyof EstimateMinutes, value: string) => {
+    setRows((prev) => prev.map((row, i) => (i === index ? { ...row, [field]: Number(value) } : row)));
+    setDirty(true);
+  };
+
+  const handleSave = async () => {
+    setSaving(true);
+    try {
+      // Get fresh integration data so other settings aren't overwritten
+      const integration = await commands.getIntegrationByType('linear');
+      if (!integration) return;
+      const currentConfig = integration.connection_config as unknown as LinearConfig;
+      await commands.updateIntegrationConfig(integrationId, {
+        ...currentConfig,
+        estimate_minutes: rows.filter((row) => row.minutes > 0),
+      });
+      load();
+    } catch (error) {
+      console.error('Failed to save estimate table:', error);
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const inputClassName =
+    'w-20 bg-neutral-900 border border-neutral-700 text-white rounded px-2 py-1 text-sm focus:outline-none focus:border-neutral-600';
+
+  return (
+    <div className="mt-4">
+      <p className="text-sm font-medium text-neutral-300">Estimate durations</p>
+      <p className="text-xs text-neutral-500 mb-2">
+        Minutes planned per estimate when creating timeboxes from issues. Estimates between rows use the next one up.
+      </p>
+      <div className="space-y-1">
+        {rows.map((row, index) => (
+          <div key={index} className="flex items-center gap-2 text-sm text-neutral-400">
+            <input
+              type="number"
+              min={0}
+              step={0.5}
+              value={row.points}
+              onChange={(e) => updateRow(index, 'points', e.target.value)}
+              className={inputClassName}
+            />
+            <span>points =</span>
+            <input
+              type="number"
+              min={1}
+              value={row.minutes}
+              onChange={(e) => updateRow(index, 'minutes', e.target.value)}
+              className={inputClassName}
+            />
+            <span>min</span>
+            <button
+              onClick={() => {
+                setRows((prev) => prev.filter((_, i) => i !== index));
+                setDirty(true);
+              }}
+              className="text-neutral-500 hover:text-red-400 transition-colors"
+            >
+              Remove
+            </button>
+          </div>
+        ))}
+      </div>
+      <div className="flex items-center gap-3 mt-2">
+        <button
+          onClick={() => {
+            const last = rows[rows.length - 1];
+            setRows((prev) => [...prev, { points: last ? last.points + 1 : 1, minutes: last ? last.minutes : 30 }]);
+            setDirty(true);
+          }}
+          className="text-sm text-neutral-400 hover:text-white transition-colors"
+        >
+          + Add row
+        </button>
+        {dirty && (
+          <button
+            onClick={handleSave}
+            disabled={saving}
+            className="px-3 py-1 bg-[#5E6AD2] text-white text-sm rounded hover:bg-[#4C58C0] disabled:opacity-50 transition-colors"
+          >
+            {saving ? 'Saving...' : 'Save'}
+          </button>
+        )}
+      </div>
+    </div>
+  );
+}
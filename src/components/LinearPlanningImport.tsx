@@ -0,0 +1,176 @@
+import { useState, useEffect } from 'react';
+import { commands } from '../lib/commands';
+import { describeApiError } from '../lib/utils';
+import type { EstimateMinutes, LinearPlanningIssue } from '../lib/types';
+
+interface LinearPlanningImportProps {
+  teamId: string | null;
+  onTimeboxCreated?: () => void;
+}
+
+type Source = 'mine' | 'cycle';
+
+// Issues without an estimate, matches the backend default
+const UNESTIMATED_MINUTES = 30;
+
+// Same rule as the backend: the estimate's row, the next larger one, or the last row scaled
+function estimateToMinutes(table: EstimateMinutes[], estimate: number | null): number {
+  if (estimate === null || estimate < 0) return UNESTIMATED_MINUTES;
+  const row = table.find((r) => r.points >= estimate);
+  if (row) return row.minutes;
+  const last = table[table.length - 1];
+  if (!last) return UNESTIMATED_MINUTES;
+  return last.points > 0 ? Math.round((last.minutes * estimate) / last.points) : last.minutes;
+}
+
+export function LinearPlanningImport({ teamId, onTimeboxCreated }: LinearPlanningImportProps) {
+  const [source, setSource] = useState<Source | null>(null);
+  const [issues, setIssues] = useState<LinearPlanningIssue[]>([]);
+  const [cycleLabel, setCycleLabel] = useState<string | null>(null);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [table, setTable] = useState<EstimateMinutes[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [creating, setCreating] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    commands.getLinearEstimateMinutes().then(setTable).catch(console.error);
+  }, []);
+
+  useEffect(() => {
+    if (!source) return;
+    const load = async () => {
+      setLoading(true);
+      setError(null);
+      setMessage(null);
+      setSelected(new Set());
+      try {
+        if (source === 'mine') {
+          setIssues(await commands.getLinearMyIssues());
+          setCycleLabel(null);
+        } else if (teamId) {
+          const cycle = await commands.getLinearCurrentCycle(teamId);
+          setIssues(cycle?.issues ?? []);
+          setCycleLabel(cycle ? cycle.name || `Cycle ${cycle.number}` : null);
+        }
+      } catch (err) {
+        console.error('Failed to load issues:', err);
+        setIssues([]);
+        setError(describeApiError(err, 'Linear', String(err)));
+      } finally {
+        setLoading(false);
+      }
+    };
+    load();
+  }, [source, teamId]);
+
+  const toggle = (id: string) => {
+    setSelected((prev) => {
+      const next = new Set(prev);
+      if (next.has(id)) {
+        next.delete(id);
+      } else {
+        next.add(id);
+      }
+      return next;
+    });
+  };
+
+  const handleCreate = async () => {
+    setCreating(true);
+    try {
+      const picked = issues
+        .filter((issue) => selected.has(issue.id))
+        .map((issue) => ({
+          id: issue.id,
+          identifier: issue.identifier,
+          title: issue.title,
+          url: issue.url,
+          estimate: issue.estimate,
+          project_id: issue.project?.id ?? null,
+        }));
+      const result = await commands.createTimeboxesFromLinearIssues(picked, UNESTIMATED_MINUTES);
+      setMessage(
+        `Created ${result.created.length} timebox${result.created.length === 1 ? '' : 'es'}` +
+          (result.skipped.length > 0 ? `, skipped ${result.skipped.join(', ')} (already planned)` : '')
+      );
+      setSelected(new Set());
+      onTimeboxCreated?.();
+    } catch (err) {
+      console.error('Failed to create timeboxes:', err);
+      setError(String(err));
+    } finally {
+      setCreating(false);
+    }
+  };
+
+  const tabClassName = (active: boolean) =>
+    `px-3 py-1.5 text-sm rounded transition-colors ${
+      active ? 'bg-[#5E6AD2] text-white' : 'bg-neutral-800 text-neutral-300 hover:bg-neutral-700'
+    } disabled:opacity-50 disabled:cursor-not-allowed`;
+
+  const plannedMinutes = issues
+    .filter((issue) => selected.has(issue.id))
+    .reduce((sum, issue) => sum + estimateToMinutes(table, issue.estimate), 0);
+
+  return (
+    <div className="mb-8">
+      <h3 className="text-lg font-medium text-neutral-300 mb-4">Plan from Linear</h3>
+      <div className="flex items-center gap-2 mb-4">
+        <button onClick={() => setSource('mine')} className={tabClassName(source === 'mine')}>
+          My issues
+        </button>
+        <button
+          onClick={() => setSource('cycle')}
+          disabled={!teamId}
+          title={teamId ? undefined : 'Select a team first'}
+          className={tabClassName(source === 'cycle')}
+        >
+          Current cycle
+        </button>
+        {cycleLabel && source === 'cycle' && <span className="text-sm text-neutral-500">{cycleLabel}</span>}
+      </div>
+
+      {error && <p className="text-sm text-red-400 mb-3">{error}</p>}
+      {message && <p className="text-sm text-green-400 mb-3">{message}</p>}
+
+      {source && (loading ? (
+        <p className="text-neutral-500">Loading issues...</p>
+      ) : issues.length === 0 ? (
+        <p className="text-neutral-500">
+          {source === 'cycle' && !cycleLabel ? 'The team has no active cycle.' : 'No open issues.'}
+        </p>
+      ) : (
+        <>
+          <div className="space-y-1 mb-4 max-h-80 overflow-auto">
+            {issues.map((issue) => (
+              <label
+                key={issue.id}
+                className="flex items-center gap-3 bg-[#0a0a0a] rounded p-2 border border-neutral-800 cursor-pointer hover:border-neutral-700"
+              >
+                <input type="checkbox" checked={selected.has(issue.id)} onChange={() => toggle(issue.id)} />
+                <span className="text-xs text-neutral-500 w-16 shrink-0">{issue.identifier}</span>
+                <span className="text-sm text-white flex-1 truncate">{issue.title}</span>
+                {issue.project && <span className="text-xs text-neutral-500 truncate">{issue.project.name}</span>}
+                <span className="text-xs text-neutral-400 w-20 text-right shrink-0">
+                  {issue.estimate !== null ? `${issue.estimate} pt · ` : ''}
+                  {estimateToMinutes(table, issue.estimate)}m
+                </span>
+              </label>
+            ))}
+          </div>
+          <button
+            onClick={handleCreate}
+            disabled={selected.size === 0 || creating}
+            className="px-4 py-2 bg-[#5E6AD2] text-white text-sm rounded hover:bg-[#4C58C0] disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+          >
+            {creating
+              ? 'Creating...'
+              : `Create ${selected.size} timebox${selected.size === 1 ? '' : 'es'}${selected.size > 0 ? ` (${plannedMinutes}m)` : ''}`}
+          </button>
+        </>
+      ))}
+    </div>
+  );
+}
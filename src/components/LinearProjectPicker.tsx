@@ -4,6 +4,7 @@ import { useProjectSearch } from '../hooks/useProjectSearch';
 
 interface LinearProjectPickerProps {
   apiKey: string;
+  apiBaseUrl?: string | null;
   savedProjects: LinearProject[];
   selectedProject: SelectedLinearProject | null;
   onSelect: (project: SelectedLinearProject | null) => void;
@@ -17,6 +18,7 @@ type ProjectPickerItem =
 
 export function LinearProjectPicker({
   apiKey,
+  apiBaseUrl,
   savedProjects,
   selectedProject,
   onSelect,
@@ -36,7 +38,7 @@ export function LinearProjectPicker({
     isSearching,
     error,
     clearSearch,
-  } = useProjectSearch({ apiKey });
+  } = useProjectSearch({ apiKey, apiBaseUrl });
 
   // Close dropdown when clicking outside
   useEffect(() => {
@@ -0,0 +1,174 @@
+import { useState, useEffect, useCallback } from 'react';
+import { commands } from '../lib/commands';
+import type {
+  LinearStateRule,
+  LinearStateRuleEvent,
+  LinearStateTransition,
+  LinearTeamWorkflowState,
+  TimeboxWithSessions,
+} from '../lib/types';
+
+interface LinearStateRulesEditorProps {
+  localProjectId: number;
+  teamId: string;
+}
+
+const EVENTS: { event: LinearStateRuleEvent; label: string }[] = [
+  { event: 'started', label: 'When a timebox starts' },
+  { event: 'stopped', label: 'When a timebox is stopped' },
+  { event: 'finished', label: 'When a timebox is finished' },
+];
+
+const EVENT_LABELS: Record<LinearStateRuleEvent, string> = {
+  started: 'Start',
+  stopped: 'Stop',
+  finished: 'Finish',
+};
+
+const selectClassName =
+  'bg-neutral-900 border border-neutral-700 text-white rounded px-3 py-1.5 text-sm focus:outline-none focus:border-neutral-600';
+
+export function LinearStateRulesEditor({ localProjectId, teamId }: LinearStateRulesEditorProps) {
+  const [isOpen, setIsOpen] = useState(false);
+  const [states, setStates] = useState<LinearTeamWorkflowState[]>([]);
+  const [rules, setRules] = useState<LinearStateRule[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [refreshing, setRefreshing] = useState(false);
+  const [timeboxes, setTimeboxes] = useState<TimeboxWithSessions[]>([]);
+  const [previewTimeboxId, setPreviewTimeboxId] = useState<number | null>(null);
+  const [preview, setPreview] = useState<LinearStateTransition[]>([]);
+
+  const loadStates = useCallback(async (refresh: boolean) => {
+    try {
+      setStates(await commands.getLinearWorkflowStates(teamId, refresh));
+      setError(null);
+    } catch (err) {
+      console.error('Failed to load workflow states:', err);
+      setError(String(err));
+    }
+  }, [teamId]);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    loadStates(false);
+    commands.getLinearStateRules(localProjectId).then(setRules).catch(console.error);
+    commands.getTodayTimeboxes()
+      .then((data) => setTimeboxes(data.filter((tb) => tb.linear_project_id === localProjectId && tb.linear_issue_id)))
+      .catch(console.error);
+  }, [isOpen, localProjectId, loadStates]);
+
+  useEffect(() => {
+    if (previewTimeboxId === null) {
+      setPreview([]);
+      return;
+    }
+    commands.previewLinearStateTransitions(previewTimeboxId).then(setPreview).catch(console.error);
+  }, [previewTimeboxId, rules, states]);
+
+  const handleRefresh = async () => {
+    setRefreshing(true);
+    await loadStates(true);
+    setRefreshing(false);
+  };
+
+  const handleRuleChange = async (event: LinearStateRuleEvent, stateName: string) => {
+    try {
+      setRules(await commands.setLinearStateRule(localProjectId, event, stateName || null));
+      setError(null);
+    } catch (err) {
+      console.error('Failed to save state rule:', err);
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="mb-6 bg-neutral-900/50 border border-neutral-800 rounded-lg">
+      <button
+        onClick={() => setIsOpen(!isOpen)}
+        className="w-full flex items-center justify-between px-4 py-3 text-sm font-medium text-neutral-300 hover:text-white transition-colors"
+      >
+        Issue state automation
+        <svg
+          className={`w-3 h-3 text-neutral-400 transition-transform ${isOpen ? 'rotate-180' : ''}`}
+          fill="none"
+          stroke="currentColor"
+          viewBox="0 0 24 24"
+        >
+          <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M19 9l-7 7-7-7" />
+        </svg>
+      </button>
+
+      {isOpen && (
+        <div className="px-4 pb-4 space-y-3">
+          {EVENTS.map(({ event, label }) => {
+            const stateName = rules.find((r) => r.event === event)?.state_name ?? '';
+            const known = states.some((s) => s.name.toLowerCase() === stateName.toLowerCase());
+            return (
+              <div key={event} className="flex items-center justify-between">
+                <span className="text-sm text-neutral-400">{label}</span>
+                <select
+                  value={stateName}
+                  onChange={(e) => handleRuleChange(event, e.target.value)}
+                  className={selectClassName}
+                >
+                  <option value="">Don't change the issue</option>
+                  {stateName && !known && <option value={stateName}>{stateName} (not found)</option>}
+                  {states.map((state) => (
+                    <option key={state.id} value={state.name}>{state.name}</option>
+                  ))}
+                </select>
+              </div>
+            );
+          })}
+
+          <div className="flex items-center justify-between">
+            <p className="text-xs text-neutral-500">Workflow states are cached so rules work offline</p>
+            <button
+              onClick={handleRefresh}
+              disabled={refreshing}
+              className="text-xs text-neutral-400 hover:text-white disabled:opacity-50 transition-colors"
+            >
+              {refreshing ? 'Refreshing...' : 'Refresh states'}
+            </button>
+          </div>
+
+          {error && <p className="text-xs text-red-400">{error}</p>}
+
+          {timeboxes.length > 0 && (
+            <div className="pt-3 border-t border-neutral-800">
+              <div className="flex items-center justify-between mb-2">
+                <span className="text-sm text-neutral-400">Preview</span>
+                <select
+                  value={previewTimeboxId ?? ''}
+                  onChange={(e) => setPreviewTimeboxId(e.target.value ? Number(e.target.value) : null)}
+                  className={selectClassName}
+                >
+                  <option value="">Pick a timebox</option>
+                  {timeboxes.map((tb) => (
+                    <option key={tb.id} value={tb.id}>
+                      {tb.linear_issue_identifier ? `${tb.linear_issue_identifier}: ` : ''}{tb.intention}
+                    </option>
+                  ))}
+                </select>
+              </div>
+              <ul className="space-y-1">
+                {preview.map((transition) => (
+                  <li key={transition.event} className="text-xs">
+                    <span className="text-neutral-300">{EVENT_LABELS[transition.event]}:</span>{' '}
+                    {transition.skipped_reason ? (
+                      <span className="text-neutral-500">{transition.skipped_reason}</span>
+                    ) : (
+                      <span className="text-[#5E6AD2]">
+                        moves {transition.issue_identifier ?? 'the issue'} to {transition.state_name}
+                      </span>
+                    )}
+                  </li>
+                ))}
+              </ul>
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
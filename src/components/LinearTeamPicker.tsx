@@ -4,6 +4,7 @@ import { useTeamSearch } from '../hooks/useTeamSearch';
 
 interface LinearTeamPickerProps {
   apiKey: string;
+  apiBaseUrl?: string | null;
   teams: LinearTeam[];
   selectedTeamId: string | null;
   onSelect: (teamId: string | null) => void;
@@ -12,6 +13,7 @@ interface LinearTeamPickerProps {
 
 export function LinearTeamPicker({
   apiKey,
+  apiBaseUrl,
   teams,
   selectedTeamId,
   onSelect,
@@ -31,7 +33,7 @@ export function LinearTeamPicker({
     isSearching,
     error,
     clearSearch,
-  } = useTeamSearch({ apiKey });
+  } = useTeamSearch({ apiKey, apiBaseUrl });
 
   const isShowingSearchResults = searchTerm.length >= 2;
 
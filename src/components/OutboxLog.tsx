@@ -0,0 +1,96 @@
+import { useState, useEffect, useCallback } from 'react';
+import { commands } from '../lib/commands';
+import type { Integration, OutboxItem } from '../lib/types';
+
+interface OutboxLogProps {
+  integrations: Integration[];
+}
+
+const STATUS_CLASSES: Record<OutboxItem['status'], string> = {
+  pending: 'text-yellow-400',
+  delivered: 'text-green-400',
+  failed: 'text-red-400',
+  discarded: 'text-neutral-500',
+};
+
+const ACTION_LABELS: Record<OutboxItem['action'], string> = {
+  todoist_create_completed_task: 'Todoist task',
+  linear_post_comment: 'Linear comment',
+  linear_update_issue_state: 'Linear state',
+};
+
+export function OutboxLog({ integrations }: OutboxLogProps) {
+  const [items, setItems] = useState<OutboxItem[]>([]);
+
+  const load = useCallback(() => {
+    commands.getOutboxItems(undefined, 20)
+      .then(setItems)
+      .catch(console.error);
+  }, []);
+
+  useEffect(() => {
+    load();
+    // Items are delivered in the background, keep the log current while it's open
+    const interval = setInterval(load, 5000);
+    return () => clearInterval(interval);
+  }, [load]);
+
+  const handleAction = async (id: number, action: 'retry' | 'discard') => {
+    try {
+      if (action === 'retry') {
+        await commands.retryOutboxItem(id);
+      } else {
+        await commands.discardOutboxItem(id);
+      }
+      load();
+    } catch (error) {
+      console.error(`Failed to ${action} outbox item:`, error);
+    }
+  };
+
+  const connectionName = (integrationId: number) =>
+    integrations.find((i) => i.id === integrationId)?.connection_name ?? 'Removed';
+
+  if (items.length === 0) {
+    return <p className="text-xs text-neutral-500">Nothing sent yet</p>;
+  }
+
+  return (
+    <ul className="space-y-1">
+      {items.map((item) => (
+        <li key={item.id} className="flex items-center gap-3 text-xs">
+          <span className="text-neutral-500 font-mono shrink-0">{item.created_at}</span>
+          <span className="text-neutral-300 w-28 shrink-0 truncate">{connectionName(item.integration_id)}</span>
+          <span className="text-neutral-300 w-28 shrink-0">{ACTION_LABELS[item.action] ?? item.action}</span>
+          <span className={`${STATUS_CLASSES[item.status]} shrink-0`}>
+            {item.status}
+            {item.status === 'pending' && item.attempts > 0 && ` (attempt ${item.attempts + 1})`}
+          </span>
+          {item.last_error && item.status !== 'delivered' && (
+            <span className="text-neutral-500 truncate" title={item.last_error}>
+              {item.last_error}
+            </span>
+          )}
+          {(item.status === 'failed' || item.status === 'pending') && (
+            <div className="ml-auto flex gap-1 shrink-0">
+              {item.status === 'failed' && (
+                <button
+                  onClick={() => handleAction(item.id, 'retry')}
+                  className="px-2 py-0.5 text-neutral-300 hover:bg-neutral-800 rounded transition-colors"
+                >
+                  Retry
+                </button>
+              )}
+              <button
+                onClick={() => handleAction(item.id, 'discard')}
+                className="px-2 py-0.5 text-neutral-400 hover:bg-neutral-800 rounded transition-colors"
+              >
+                Discard
+              </button>
+            </div>
+          )}
+        </li>
+      ))}
+    </ul>
+  );
+}
@@ -1,11 +1,12 @@
 import { useState, useEffect, useCallback } from 'react';
 import { commands } from '../lib/commands';
+import { describeApiError } from '../lib/utils';
 import type { LinearApiIssue } from '../lib/types';
 import { IssueCard } from './IssueCard';
+import { LinearStateRulesEditor } from './LinearStateRulesEditor';
 import { TimeboxForm } from './TimeboxForm';
 
 interface ProjectIssuesViewProps {
-  apiKey: string;
   projectId: string;
   projectName: string;
   localProjectId?: number;
@@ -49,7 +50,6 @@ function groupIssuesByState(issues: LinearApiIssue[]): Record<StateType, LinearA
 }
 
 export function ProjectIssuesView({
-  apiKey,
   projectId,
   projectName,
   localProjectId,
@@ -60,7 +60,8 @@ export function ProjectIssuesView({
 }: ProjectIssuesViewProps) {
   const [issues, setIssues] = useState<LinearApiIssue[]>([]);
   const [loading, setLoading] = useState(true);
-  const [error, setError] = useState<string | null>(null);
+  const [syncedAt, setSyncedAt] = useState<string | null>(null);
+  const [syncError, setSyncError] = useState<string | null>(null);
   const [existingIssueIds, setExistingIssueIds] = useState<Set<string>>(new Set());
 
   // Load existing timeboxes to check for duplicates
@@ -79,35 +80,45 @@ export function ProjectIssuesView({
     }
   }, []);
 
+  // Issues as of the last sync, available offline
+  const loadCachedIssues = useCallback(async () => {
+    const cached = await commands.getCachedLinearProjectIssues(projectId);
+    setIssues(cached.issues);
+    setSyncedAt(cached.synced_at);
+    return cached;
+  }, [projectId]);
+
+  // Fetch what changed in Linear into the local cache, then show it
+  const refreshIssues = useCallback(async () => {
+    try {
+      await commands.syncLinearProjectIssues(projectId);
+      setSyncError(null);
+    } catch (err) {
+      console.error('Failed to sync issues:', err);
+      setSyncError(describeApiError(err, 'Linear', String(err)));
+    }
+    await loadCachedIssues();
+  }, [projectId, loadCachedIssues]);
+
   useEffect(() => {
     const loadData = async () => {
       setLoading(true);
-      setError(null);
       try {
-        const [issuesData] = await Promise.all([
-          commands.getLinearProjectIssues(apiKey, projectId),
-          loadExistingTimeboxes(),
-        ]);
-        setIssues(issuesData);
+        const [cached] = await Promise.all([loadCachedIssues(), loadExistingTimeboxes()]);
+        // Cached issues show right away while the sync runs
+        if (cached.synced_at) {
+          setLoading(false);
+        }
+        await refreshIssues();
       } catch (err) {
         console.error('Failed to load issues:', err);
-        setError('Failed to load issues from Linear');
+        setSyncError('Failed to load issues');
       } finally {
         setLoading(false);
       }
     };
     loadData();
-  }, [apiKey, projectId, loadExistingTimeboxes]);
-
-  // Refresh issues list from Linear
-  const refreshIssues = useCallback(async () => {
-    try {
-      const issuesData = await commands.getLinearProjectIssues(apiKey, projectId);
-      setIssues(issuesData);
-    } catch (err) {
-      console.error('Failed to refresh issues:', err);
-    }
-  }, [apiKey, projectId]);
+  }, [loadCachedIssues, loadExistingTimeboxes, refreshIssues]);
 
   // Refresh existing timeboxes and issues list after a new one is created
   const handleTimeboxCreated = useCallback(async () => {
@@ -147,6 +158,10 @@ export function ProjectIssuesView({
         <h2 className="text-2xl font-bold text-white">{projectName}</h2>
       </div>
 
+      {localProjectId !== undefined && (
+        <LinearStateRulesEditor localProjectId={localProjectId} teamId={teamId} />
+      )}
+
       {/* Create Timebox Form - linked to this project with auto-create Linear issue */}
       <TimeboxForm
         onCreated={handleTimeboxCreated}
@@ -157,11 +172,17 @@ export function ProjectIssuesView({
         }}
       />
 
+      {syncError && syncedAt && (
+        <p className="text-xs text-neutral-500 mb-4">
+          {syncError} Showing issues as of {syncedAt}.
+        </p>
+      )}
+
       {loading ? (
         <p className="text-neutral-500">Loading issues...</p>
-      ) : error ? (
+      ) : syncError && !syncedAt ? (
         <div className="bg-red-500/10 border border-red-500/20 rounded-lg p-4">
-          <p className="text-red-400">{error}</p>
+          <p className="text-red-400">{syncError}</p>
         </div>
       ) : issues.length === 0 ? (
         <p className="text-neutral-500">No issues found for this project.</p>
@@ -0,0 +1,207 @@
+import { useState } from 'react';
+import { commands } from '../lib/commands';
+import { TIMEBOX_EVENTS } from './WebhookConnectionForm';
+import type { ConfigField, ProviderDescriptor, TimeboxEvent } from '../lib/types';
+
+interface ProviderConnectionFormProps {
+  provider: ProviderDescriptor;
+  onSuccess: () => void;
+  onCancel: () => void;
+}
+
+type TestStatus = 'idle' | 'testing' | 'success' | 'error';
+
+const inputClassName =
+  'w-full px-4 py-2 bg-neutral-900 border border-neutral-800 text-white placeholder-neutral-500 rounded-lg focus:outline-none focus:ring-2 focus:ring-neutral-600';
+
+function isFilled(field: ConfigField, value: unknown) {
+  if (field.kind === 'events') return Array.isArray(value) && value.length > 0;
+  if (field.kind === 'boolean') return true;
+  return typeof value === 'string' && value.trim() !== '';
+}
+
+/** Connection form built from a provider's config schema, for providers without their own form */
+export function ProviderConnectionForm({ provider, onSuccess, onCancel }: ProviderConnectionFormProps) {
+  const [connectionName, setConnectionName] = useState(provider.name);
+  const [config, setConfig] = useState<Record<string, unknown>>({});
+  const [testStatus, setTestStatus] = useState<TestStatus>('idle');
+  const [accountName, setAccountName] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const setValue = (key: string, value: unknown) => {
+    setConfig((current) => ({ ...current, [key]: value }));
+    setTestStatus('idle');
+  };
+
+  const toggleEvent = (key: string, event: TimeboxEvent) => {
+    const current = (config[key] as TimeboxEvent[] | undefined) ?? [];
+    setValue(key, current.includes(event) ? current.filter((e) => e !== event) : [...current, event]);
+  };
+
+  const handleTest = async () => {
+    setTestStatus('testing');
+    setError(null);
+    try {
+      const result = await commands.testIntegrationConnection(provider.integration_type, config);
+      if (result.success) {
+        setTestStatus('success');
+        setAccountName(result.account_name);
+      } else {
+        setTestStatus('error');
+        setError(result.error || 'Connection failed');
+      }
+    } catch (err) {
+      setTestStatus('error');
+      setError(String(err));
+    }
+  };
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    setError(null);
+    try {
+      await commands.createIntegration({
+        connection_name: connectionName.trim() || provider.name,
+        integration_type: provider.integration_type,
+        connection_config: config,
+      });
+      onSuccess();
+    } catch (err) {
+      setError(String(err));
+      setIsSaving(false);
+    }
+  };
+
+  const isDisabled = testStatus === 'testing' || isSaving;
+  const isComplete = provider.config_schema.every((field) => !field.required || isFilled(field, config[field.key]));
+
+  return (
+    <div className="max-w-md">
+      <h3 className="text-xl font-semibold text-white mb-2">Connect {provider.name}</h3>
+      <p className="text-neutral-400 text-sm mb-6">{provider.description}</p>
+
+      <div className="mb-4">
+        <label htmlFor="providerConnectionName" className="block text-sm font-medium text-neutral-300 mb-2">
+          Connection Name
+        </label>
+        <input
+          type="text"
+          id="providerConnectionName"
+          value={connectionName}
+          onChange={(e) => setConnectionName(e.target.value)}
+          className={inputClassName}
+          disabled={isDisabled}
+        />
+      </div>
+
+      {provider.config_schema.map((field) => (
+        <div key={field.key} className="mb-4">
+          {field.kind === 'boolean' ? (
+            <label className="flex items-center gap-2 text-sm text-neutral-300">
+              <input
+                type="checkbox"
+                checked={Boolean(config[field.key])}
+                onChange={(e) => setValue(field.key, e.target.checked)}
+                disabled={isDisabled}
+              />
+              {field.label}
+            </label>
+          ) : field.kind === 'events' ? (
+            <>
+              <p className="block text-sm font-medium text-neutral-300 mb-2">{field.label}</p>
+              <div className="grid grid-cols-2 gap-2">
+                {TIMEBOX_EVENTS.map(({ value, label }) => (
+                  <label key={value} className="flex items-center gap-2 text-sm text-neutral-300">
+                    <input
+                      type="checkbox"
+                      checked={((config[field.key] as TimeboxEvent[] | undefined) ?? []).includes(value)}
+                      onChange={() => toggleEvent(field.key, value)}
+                      disabled={isDisabled}
+                    />
+                    {label}
+                  </label>
+                ))}
+              </div>
+            </>
+          ) : field.kind === 'choice' ? (
+            <>
+              <label htmlFor={`provider-${field.key}`} className="block text-sm font-medium text-neutral-300 mb-2">
+                {field.label}
+              </label>
+              <select
+                id={`provider-${field.key}`}
+                value={(config[field.key] as string | undefined) ?? field.options[0]?.value ?? ''}
+                onChange={(e) => setValue(field.key, e.target.value)}
+                className={inputClassName}
+                disabled={isDisabled}
+              >
+                {field.options.map(({ value, label }) => (
+                  <option key={value} value={value}>{label}</option>
+                ))}
+              </select>
+            </>
+          ) : (
+            <>
+              <label htmlFor={`provider-${field.key}`} className="block text-sm font-medium text-neutral-300 mb-2">
+                {field.label}
+                {!field.required && <span className="text-neutral-500 font-normal"> (optional)</span>}
+              </label>
+              <input
+                type={field.kind === 'secret' ? 'password' : 'url'}
+                id={`provider-${field.key}`}
+                value={(config[field.key] as string | undefined) ?? ''}
+                onChange={(e) => setValue(field.key, e.target.value)}
+                placeholder={field.placeholder ?? undefined}
+                className={inputClassName}
+                disabled={isDisabled}
+              />
+            </>
+          )}
+          {field.help && <p className="text-xs text-neutral-500 mt-1">{field.help}</p>}
+        </div>
+      ))}
+
+      {testStatus === 'success' && (
+        <div className="mb-4 p-3 bg-green-500/10 border border-green-500/30 rounded-lg">
+          <span className="text-green-400 text-sm font-medium">
+            Connected{accountName ? ` as ${accountName}` : ''}
+          </span>
+        </div>
+      )}
+
+      {error && (
+        <div className="mb-4 p-3 bg-red-500/10 border border-red-500/30 rounded-lg">
+          <span className="text-red-400 text-sm">{error}</span>
+        </div>
+      )}
+
+      <div className="flex gap-3">
+        <button
+          type="button"
+          onClick={onCancel}
+          disabled={isDisabled}
+          className="px-4 py-2 bg-neutral-800 text-neutral-300 rounded-lg hover:bg-neutral-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+        >
+          Cancel
+        </button>
+        <button
+          type="button"
+          onClick={handleTest}
+          disabled={isDisabled || !isComplete}
+          className="px-4 py-2 bg-neutral-800 text-neutral-300 rounded-lg hover:bg-neutral-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+        >
+          {testStatus === 'testing' ? 'Testing...' : 'Test'}
+        </button>
+        <button
+          type="button"
+          onClick={handleSave}
+          disabled={isDisabled || !isComplete}
+          className="flex-1 px-4 py-2 bg-green-600 text-white rounded-lg hover:bg-green-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors font-medium"
+        >
+          {isSaving ? 'Saving...' : 'Connect'}
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,111 @@
+import { useState, useEffect, useRef } from 'react';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
+import { commands } from '../lib/commands';
+import type { CreateTimeboxRequest } from '../lib/types';
+
+const quickCaptureWindow = getCurrentWebviewWindow();
+
+export function QuickCapture() {
+  const [input, setInput] = useState('');
+  const [preview, setPreview] = useState<CreateTimeboxRequest | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [isSubmitting, setIsSubmitting] = useState(false);
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  // The window is hidden rather than closed, so reset and refocus whenever it comes back
+  useEffect(() => {
+    inputRef.current?.focus();
+    const unlisten = quickCaptureWindow.onFocusChanged(({ payload: focused }) => {
+      if (focused) {
+        inputRef.current?.focus();
+      } else {
+        quickCaptureWindow.hide();
+      }
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!input.trim()) {
+      setPreview(null);
+      setError(null);
+      return;
+    }
+    let cancelled = false;
+    commands.parseQuickCapture(input)
+      .then((request) => {
+        if (cancelled) return;
+        setPreview(request);
+        setError(null);
+      })
+      .catch((err) => {
+        if (cancelled) return;
+        setPreview(null);
+        setError(String(err));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [input]);
+
+  const close = () => {
+    setInput('');
+    setError(null);
+    quickCaptureWindow.hide();
+  };
+
+  const handleSubmit = async (start: boolean) => {
+    if (!input.trim() || isSubmitting) return;
+    setIsSubmitting(true);
+    try {
+      await commands.quickCaptureTimebox(input, start);
+      close();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsSubmitting(false);
+    }
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent<HTMLInputElement>) => {
+    if (e.key === 'Escape') {
+      close();
+    } else if (e.key === 'Enter') {
+      e.preventDefault();
+      handleSubmit(e.metaKey || e.ctrlKey);
+    }
+  };
+
+  return (
+    <div className="h-screen bg-[#0a0a0a] border border-neutral-800 rounded-lg p-3 flex flex-col gap-2">
+      <input
+        ref={inputRef}
+        type="text"
+        value={input}
+        onChange={(e) => setInput(e.target.value)}
+        onKeyDown={handleKeyDown}
+        placeholder="Write RFC draft 45m #writing @ENG-123"
+        disabled={isSubmitting}
+        className="w-full bg-neutral-900 border border-neutral-700 text-white placeholder-neutral-600 rounded px-3 py-2 focus:outline-none focus:border-neutral-600"
+      />
+      <div className="flex items-center justify-between gap-3 text-xs">
+        {error ? (
+          <p className="text-red-400 truncate">{error}</p>
+        ) : preview ? (
+          <p className="text-neutral-400 truncate">
+            {preview.intention} · {preview.intended_duration} min
+            {preview.tags?.map((tag) => ` #${tag}`).join('')}
+            {preview.linear_issue_identifier && ` · ${preview.linear_issue_identifier}`}
+          </p>
+        ) : (
+          <p className="text-neutral-500">Add a duration like 25m or 1h30m</p>
+        )}
+        <p className="text-neutral-500 shrink-0">
+          <span className="font-mono">Enter</span> add · <span className="font-mono">Ctrl+Enter</span> add and start
+        </p>
+      </div>
+    </div>
+  );
+}
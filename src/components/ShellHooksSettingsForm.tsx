@@ -0,0 +1,199 @@
+import { useState, useEffect, useCallback } from 'react';
+import { commands } from '../lib/commands';
+import { TIMEBOX_EVENTS } from './WebhookConnectionForm';
+import type { ShellHook, ShellHookRun, TimeboxEvent } from '../lib/types';
+
+const RUN_STATUS_CLASSES: Record<ShellHookRun['status'], string> = {
+  pending: 'text-neutral-400',
+  running: 'text-yellow-400',
+  succeeded: 'text-green-400',
+  failed: 'text-red-400',
+  timed_out: 'text-red-400',
+  skipped: 'text-neutral-500',
+};
+
+export function ShellHooksSettingsForm() {
+  const [enabled, setEnabled] = useState(false);
+  const [hooks, setHooks] = useState<ShellHook[]>([]);
+  const [runs, setRuns] = useState<ShellHookRun[]>([]);
+  const [event, setEvent] = useState<TimeboxEvent>('started');
+  const [command, setCommand] = useState('');
+  const [timeout, setTimeoutSeconds] = useState('10');
+  const [error, setError] = useState<string | null>(null);
+
+  const load = useCallback(() => {
+    Promise.all([commands.getShellHooksEnabled(), commands.getShellHooks(), commands.getShellHookRuns(10)])
+      .then(([loadedEnabled, loadedHooks, loadedRuns]) => {
+        setEnabled(loadedEnabled);
+        setHooks(loadedHooks);
+        setRuns(loadedRuns);
+      })
+      .catch(console.error);
+  }, []);
+
+  useEffect(() => {
+    load();
+    // Hooks run in the background, keep the run log current
+    const interval = setInterval(load, 5000);
+    return () => clearInterval(interval);
+  }, [load]);
+
+  const handleToggleEnabled = async () => {
+    try {
+      await commands.setShellHooksEnabled(!enabled);
+      setEnabled(!enabled);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleAdd = async () => {
+    setError(null);
+    try {
+      await commands.createShellHook({
+        event,
+        command,
+        timeout_seconds: parseInt(timeout, 10) || undefined,
+      });
+      setCommand('');
+      load();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleToggleHook = async (hook: ShellHook) => {
+    try {
+      await commands.updateShellHook(hook.id, {
+        event: hook.event,
+        command: hook.command,
+        timeout_seconds: hook.timeout_seconds,
+        enabled: !hook.enabled,
+      });
+      load();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await commands.deleteShellHook(id);
+      load();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="bg-[#0a0a0a] rounded-lg p-4 border border-neutral-800">
+      <div className="flex items-center justify-between gap-4">
+        <div>
+          <p className="text-sm font-medium text-neutral-300">Shell hooks</p>
+          <p className="text-xs text-neutral-500">
+            Run local commands on timebox events. Only add commands you trust.
+          </p>
+        </div>
+        <button
+          onClick={handleToggleEnabled}
+          className={`relative w-11 h-6 rounded-full transition-colors ${
+            enabled ? 'bg-green-600' : 'bg-neutral-700'
+          }`}
+        >
+          <span
+            className={`absolute top-1 left-1 w-4 h-4 bg-white rounded-full transition-transform ${
+              enabled ? 'translate-x-5' : 'translate-x-0'
+            }`}
+          />
+        </button>
+      </div>
+
+      {enabled && (
+        <div className="mt-4 pt-4 border-t border-neutral-800 space-y-4">
+          {hooks.length > 0 && (
+            <ul className="space-y-2">
+              {hooks.map((hook) => (
+                <li key={hook.id} className="flex items-center gap-3 text-sm">
+                  <span className="text-neutral-400 w-24 shrink-0">{hook.event}</span>
+                  <span className={`font-mono truncate ${hook.enabled ? 'text-white' : 'text-neutral-500 line-through'}`}>
+                    {hook.command}
+                  </span>
+                  <span className="text-xs text-neutral-500 shrink-0">{hook.timeout_seconds}s</span>
+                  <div className="ml-auto flex gap-1 shrink-0">
+                    <button
+                      onClick={() => handleToggleHook(hook)}
+                      className="px-2 py-0.5 text-xs text-neutral-300 hover:bg-neutral-800 rounded transition-colors"
+                    >
+                      {hook.enabled ? 'Disable' : 'Enable'}
+                    </button>
+                    <button
+                      onClick={() => handleDelete(hook.id)}
+                      className="px-2 py-0.5 text-xs text-red-400 hover:bg-red-400/10 rounded transition-colors"
+                    >
+                      Remove
+                    </button>
+                  </div>
+                </li>
+              ))}
+            </ul>
+          )}
+
+          <div className="flex items-center gap-2">
+            <select
+              value={event}
+              onChange={(e) => setEvent(e.target.value as TimeboxEvent)}
+              className="bg-neutral-900 border border-neutral-700 text-white rounded px-2 py-1.5 text-sm focus:outline-none focus:border-neutral-600"
+            >
+              {TIMEBOX_EVENTS.map(({ value, label }) => (
+                <option key={value} value={value}>{label}</option>
+              ))}
+            </select>
+            <input
+              type="text"
+              value={command}
+              onChange={(e) => setCommand(e.target.value)}
+              placeholder='notify-send "Started $TIMEBOXD_INTENTION"'
+              className="flex-1 bg-neutral-900 border border-neutral-700 text-white placeholder-neutral-600 rounded px-3 py-1.5 text-sm font-mono focus:outline-none focus:border-neutral-600"
+            />
+            <input
+              type="number"
+              value={timeout}
+              onChange={(e) => setTimeoutSeconds(e.target.value)}
+              title="Timeout in seconds"
+              className="w-16 bg-neutral-900 border border-neutral-700 text-white rounded px-2 py-1.5 text-sm font-mono focus:outline-none focus:border-neutral-600"
+            />
+            <button
+              onClick={handleAdd}
+              disabled={!command.trim()}
+              className="px-3 py-1.5 text-sm bg-neutral-800 text-neutral-200 rounded hover:bg-neutral-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+            >
+              Add
+            </button>
+          </div>
+
+          {runs.length > 0 && (
+            <div>
+              <p className="text-xs font-medium text-neutral-400 mb-1">Recent runs</p>
+              <ul className="space-y-1">
+                {runs.map((run) => (
+                  <li key={run.id} className="flex items-center gap-3 text-xs">
+                    <span className="text-neutral-500 font-mono shrink-0">{run.created_at}</span>
+                    <span className={`${RUN_STATUS_CLASSES[run.status]} shrink-0`}>
+                      {run.status.replace('_', ' ')}
+                      {run.exit_code !== null && run.exit_code !== 0 && ` (${run.exit_code})`}
+                    </span>
+                    <span className="text-neutral-400 font-mono truncate" title={run.stderr || run.stdout || ''}>
+                      {run.command}
+                    </span>
+                  </li>
+                ))}
+              </ul>
+            </div>
+          )}
+        </div>
+      )}
+
+      {error && <p className="text-xs text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
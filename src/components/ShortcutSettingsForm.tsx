@@ -0,0 +1,82 @@
+import { useState, useEffect } from 'react';
+import { commands } from '../lib/commands';
+import type { ShortcutSettings } from '../lib/types';
+
+const SHORTCUT_FIELDS: { key: keyof ShortcutSettings; label: string; description: string }[] = [
+  { key: 'toggle_pause', label: 'Pause / resume', description: 'Pause or resume the most recent timebox' },
+  { key: 'finish_current', label: 'Finish current', description: 'Finish the timebox you are working on' },
+  { key: 'quick_create', label: 'Quick create', description: 'Open the quick capture window' },
+];
+
+export function ShortcutSettingsForm() {
+  const [settings, setSettings] = useState<ShortcutSettings | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [saved, setSaved] = useState(false);
+
+  useEffect(() => {
+    commands.getShortcutSettings().then(setSettings).catch(console.error);
+  }, []);
+
+  const handleChange = (key: keyof ShortcutSettings, value: string) => {
+    if (!settings) return;
+    setSettings({ ...settings, [key]: value.trim() ? value : null });
+    setSaved(false);
+    setError(null);
+  };
+
+  const handleSave = async () => {
+    if (!settings) return;
+    setIsSaving(true);
+    setError(null);
+    try {
+      await commands.setShortcutSettings(settings);
+      setSaved(true);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  if (!settings) return null;
+
+  return (
+    <div className="bg-[#0a0a0a] rounded-lg p-4 border border-neutral-800">
+      <div className="space-y-4">
+        {SHORTCUT_FIELDS.map(({ key, label, description }) => (
+          <div key={key} className="flex items-center justify-between gap-4">
+            <div>
+              <p className="text-sm font-medium text-neutral-300">{label}</p>
+              <p className="text-xs text-neutral-500">{description}</p>
+            </div>
+            <input
+              type="text"
+              value={settings[key] ?? ''}
+              onChange={(e) => handleChange(key, e.target.value)}
+              placeholder="Disabled"
+              disabled={isSaving}
+              className="w-64 bg-neutral-900 border border-neutral-700 text-white placeholder-neutral-600 rounded px-3 py-1.5 text-sm font-mono focus:outline-none focus:border-neutral-600"
+            />
+          </div>
+        ))}
+      </div>
+
+      <div className="flex items-center justify-between gap-3 mt-4 pt-4 border-t border-neutral-800">
+        <p className="text-xs text-neutral-500">
+          Use accelerators like <span className="font-mono">CommandOrControl+Alt+Shift+Space</span>. Leave empty to disable.
+        </p>
+        <button
+          onClick={handleSave}
+          disabled={isSaving}
+          className="px-3 py-1.5 bg-[#5E6AD2] text-white text-sm rounded hover:bg-[#4f5ab8] disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+        >
+          {isSaving ? 'Saving...' : 'Save'}
+        </button>
+      </div>
+
+      {error && <p className="text-sm text-red-400 mt-3">{error}</p>}
+      {saved && !error && <p className="text-sm text-green-400 mt-3">Shortcuts updated</p>}
+    </div>
+  );
+}
@@ -1,5 +1,5 @@
 import { useState, useEffect, useRef } from 'react';
-import type { TimeboxWithSessions, LinearProject, LinearConfig } from '../lib/types';
+import type { TimeboxWithSessions, LinearProject } from '../lib/types';
 import { commands } from '../lib/commands';
 import { useLinear } from '../contexts/AppContext';
 import { useLinearIssueCreation } from '../hooks/useLinearIssueCreation';
@@ -34,6 +34,12 @@ const statusLabels: Record<string, string> = {
   cancelled: 'Cancelled',
 };
 
+const linkStatusLabels: Record<string, string> = {
+  deleted: 'Issue deleted',
+  archived: 'Issue archived',
+  moved: 'Issue moved to another project',
+};
+
 export function TimeboxCard({ timebox, onUpdate, showDragHandle, isArchived, dragHandleProps, isHighlighted }: TimeboxCardProps) {
   // Fully editable when not_started or paused (can edit duration)
   const isFullyEditable = timebox.status === 'not_started' || timebox.status === 'paused';
@@ -151,29 +157,6 @@ export function TimeboxCard({ timebox, onUpdate, showDragHandle, isArchived, dra
   const handleStart = async () => {
     try {
       await commands.startTimebox(timebox.id);
-
-      // Sync Linear issue status to "In Progress" if issue exists
-      if (timebox.linear_issue_id && timebox.linear_project_id) {
-        try {
-          // Fetch the project to get team_id (can't rely on activeProjects being loaded)
-          const project = await commands.getLinearProjectById(timebox.linear_project_id);
-          if (project) {
-            const integration = await commands.getIntegrationByType('linear');
-            if (integration) {
-              const config = integration.connection_config as unknown as LinearConfig;
-              const states = await commands.getLinearTeamStates(config.api_key, project.linear_team_id);
-              const inProgressState = states.find(s => s.state_type === 'started');
-              if (inProgressState) {
-                await commands.updateLinearIssueState(config.api_key, timebox.linear_issue_id, inProgressState.id);
-              }
-            }
-          }
-        } catch (syncError) {
-          console.error('Failed to sync Linear issue status:', syncError);
-          // Don't fail the start operation
-        }
-      }
-
       onUpdate();
     } catch (error) {
       console.error('Failed to start timebox:', error);
@@ -230,6 +213,11 @@ export function TimeboxCard({ timebox, onUpdate, showDragHandle, isArchived, dra
     }
   };
 
+  // Set when the last link check found the issue gone from where the timebox expects it
+  const brokenLink = timebox.linear_issue_link_status && timebox.linear_issue_link_status !== 'ok'
+    ? linkStatusLabels[timebox.linear_issue_link_status]
+    : null;
+
   const handleOpenIssue = () => {
     if (timebox.linear_issue_url) {
       openLinearUrl(timebox.linear_issue_url, linearOpenInNativeApp);
@@ -365,6 +353,17 @@ export function TimeboxCard({ timebox, onUpdate, showDragHandle, isArchived, dra
           </span>
         </div>
 
+        {/* Tags */}
+        {timebox.tags && timebox.tags.length > 0 && (
+          <div className="flex flex-wrap gap-1 mb-2">
+            {timebox.tags.map((tag) => (
+              <span key={tag} className="text-xs px-2 py-0.5 rounded-full bg-neutral-800 text-neutral-400">
+                #{tag}
+              </span>
+            ))}
+          </div>
+        )}
+
         {/* Notes section */}
         <div className="mb-3 relative group/notes">
           <MarkdownEditor
@@ -446,6 +445,7 @@ export function TimeboxCard({ timebox, onUpdate, showDragHandle, isArchived, dra
                   text={timebox.linear_issue_url}
                   className="w-7 h-7 bg-[#5E6AD2]/20 text-[#5E6AD2] rounded hover:bg-[#5E6AD2]/30"
                 />
+                {brokenLink && <span className="text-xs text-amber-400 ml-1">{brokenLink}</span>}
               </div>
             ) : currentProject && (
               <button
@@ -672,6 +672,7 @@ export function TimeboxCard({ timebox, onUpdate, showDragHandle, isArchived, dra
               text={timebox.linear_issue_url}
               className="w-6 h-6 bg-[#5E6AD2]/20 text-[#5E6AD2] rounded hover:bg-[#5E6AD2]/30"
             />
+            {brokenLink && <span className="text-xs text-amber-400 ml-1">{brokenLink}</span>}
           </div>
         )}
       </div>
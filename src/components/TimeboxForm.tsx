@@ -3,7 +3,7 @@ import { commands } from '../lib/commands';
 import { MarkdownEditor } from './MarkdownEditor';
 import { LinearProjectPicker } from './LinearProjectPicker';
 import { useLinear } from '../contexts/AppContext';
-import type { LinearProject, LinearConfig, SelectedLinearProject } from '../lib/types';
+import type { LinearProject, LinearConfig, SelectedLinearProject, ParsedDuration } from '../lib/types';
 
 interface TimeboxFormProps {
   onCreated: () => void;
@@ -15,18 +15,17 @@ interface TimeboxFormProps {
   };
 }
 
-const PRESET_DURATIONS = [5, 15, 45];
-
 export function TimeboxForm({ onCreated, linearProjectId, linearProjectDetails }: TimeboxFormProps) {
   const [intention, setIntention] = useState('');
   const [notes, setNotes] = useState('');
-  const [selectedDuration, setSelectedDuration] = useState<number | null>(null);
-  const [customDuration, setCustomDuration] = useState('');
-  const [isCustom, setIsCustom] = useState(false);
+  const [durationPresets, setDurationPresets] = useState<string[]>([]);
+  const [durationInput, setDurationInput] = useState('');
+  const [parsedDuration, setParsedDuration] = useState<ParsedDuration | null>(null);
+  const [durationError, setDurationError] = useState<string | null>(null);
   const [isSubmitting, setIsSubmitting] = useState(false);
 
   // Linear integration
-  const { apiKey: linearApiKey } = useLinear();
+  const { apiKey: linearApiKey, apiBaseUrl: linearApiBaseUrl } = useLinear();
 
   // Linear project state
   const [activeProjects, setActiveProjects] = useState<LinearProject[]>([]);
@@ -35,35 +34,38 @@ export function TimeboxForm({ onCreated, linearProjectId, linearProjectDetails }
   // Load active projects on mount
   useEffect(() => {
     commands.getActiveTimeboxProjects().then(setActiveProjects).catch(console.error);
+    commands.getDurationPresets().then(setDurationPresets).catch(console.error);
   }, []);
 
-  const handlePresetClick = (duration: number) => {
-    setSelectedDuration(duration);
-    setIsCustom(false);
-    setCustomDuration('');
-  };
-
-  const handleCustomClick = () => {
-    setIsCustom(true);
-    setSelectedDuration(null);
-  };
-
-  const handleCustomDurationChange = (e: React.ChangeEvent<HTMLInputElement>) => {
-    const value = e.target.value;
-    setCustomDuration(value);
-    const parsed = parseInt(value, 10);
-    if (!isNaN(parsed) && parsed > 0) {
-      setSelectedDuration(parsed);
-    } else {
-      setSelectedDuration(null);
+  // Parse typed durations ("1h30", "until 3pm", "2 pomodoros") in the backend
+  useEffect(() => {
+    if (!durationInput.trim()) {
+      setParsedDuration(null);
+      setDurationError(null);
+      return;
     }
-  };
+    let cancelled = false;
+    commands.parseDuration(durationInput, navigator.language)
+      .then((parsed) => {
+        if (cancelled) return;
+        setParsedDuration(parsed);
+        setDurationError(null);
+      })
+      .catch((err) => {
+        if (cancelled) return;
+        setParsedDuration(null);
+        setDurationError(String(err));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [durationInput]);
 
-  const incrementCustomDuration = (delta: number) => {
-    const current = parseInt(customDuration, 10) || 0;
-    const newValue = Math.max(5, current + delta);
-    setCustomDuration(String(newValue));
-    setSelectedDuration(newValue);
+  const selectedDuration = parsedDuration?.intended_duration ?? null;
+
+  const incrementDuration = (delta: number) => {
+    const newValue = Math.max(5, (selectedDuration ?? 0) + delta);
+    setDurationInput(`${newValue}m`);
   };
 
   const handleSubmit = async () => {
@@ -95,11 +97,12 @@ export function TimeboxForm({ onCreated, linearProjectId, linearProjectDetails }
               description: notes.trim() || undefined,
               project_id: projectToCreateIssueFor.linearProjectId,
               team_id: projectToCreateIssueFor.linearTeamId,
-            });
+            }, config.api_base_url);
             if (result.success && result.issue) {
               await commands.setTimeboxLinearIssue(
                 timebox.id,
                 result.issue.id,
+                result.issue.identifier,
                 result.issue.url
               );
             }
@@ -112,9 +115,7 @@ export function TimeboxForm({ onCreated, linearProjectId, linearProjectDetails }
 
       setIntention('');
       setNotes('');
-      setSelectedDuration(null);
-      setCustomDuration('');
-      setIsCustom(false);
+      setDurationInput('');
       setSelectedProject(null);
       onCreated();
     } catch (error) {
@@ -138,51 +139,55 @@ export function TimeboxForm({ onCreated, linearProjectId, linearProjectDetails }
       />
 
       <div className="flex flex-wrap gap-2 items-center mb-4">
-        {PRESET_DURATIONS.map((duration) => (
+        {durationPresets.map((preset) => (
           <button
-            key={duration}
-            onClick={() => handlePresetClick(duration)}
+            key={preset}
+            onClick={() => setDurationInput(preset)}
             disabled={isSubmitting}
             className={`px-4 py-2 rounded-lg transition-colors ${
-              selectedDuration === duration && !isCustom
+              durationInput === preset
                 ? 'bg-[#5E6AD2] text-white'
                 : 'bg-neutral-900 text-neutral-300 hover:bg-neutral-800'
             } disabled:opacity-50 disabled:cursor-not-allowed`}
           >
-            {duration} min
+            {preset}
           </button>
         ))}
 
         <div className="flex gap-1 items-center">
           <button
-            onClick={() => incrementCustomDuration(-5)}
-            disabled={isSubmitting || !isCustom}
+            onClick={() => incrementDuration(-5)}
+            disabled={isSubmitting || selectedDuration === null}
             className="px-2 py-2 bg-neutral-900 text-neutral-300 rounded-lg hover:bg-neutral-800 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
           >
             -
           </button>
           <input
-            type="number"
-            value={customDuration}
-            onChange={handleCustomDurationChange}
-            onFocus={handleCustomClick}
-            placeholder="Custom"
-            min="1"
-            step="5"
-            className={`w-20 px-2 py-2 bg-neutral-900 border text-white placeholder-neutral-500 rounded-lg focus:outline-none focus:ring-2 focus:ring-[#5E6AD2] ${
-              isCustom ? 'border-[#5E6AD2]' : 'border-neutral-800'
+            type="text"
+            value={durationInput}
+            onChange={(e) => setDurationInput(e.target.value)}
+            placeholder="1h30, until 3pm"
+            className={`w-36 px-2 py-2 bg-neutral-900 border text-white placeholder-neutral-500 rounded-lg focus:outline-none focus:ring-2 focus:ring-[#5E6AD2] ${
+              durationError ? 'border-red-500/60' : 'border-neutral-800'
             }`}
             disabled={isSubmitting}
           />
           <button
-            onClick={() => incrementCustomDuration(5)}
-            disabled={isSubmitting || !isCustom}
+            onClick={() => incrementDuration(5)}
+            disabled={isSubmitting || selectedDuration === null}
             className="px-2 py-2 bg-neutral-900 text-neutral-300 rounded-lg hover:bg-neutral-800 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
           >
             +
           </button>
-          <span className="text-neutral-400 text-sm ml-1">min</span>
         </div>
+
+        {durationError ? (
+          <span className="text-red-400 text-sm">{durationError}</span>
+        ) : parsedDuration && (
+          <span className="text-neutral-400 text-sm">
+            {parsedDuration.intended_duration} min · ends {parsedDuration.ends_at.slice(11, 16)}
+          </span>
+        )}
       </div>
 
       <div className="mb-4">
@@ -201,6 +206,7 @@ export function TimeboxForm({ onCreated, linearProjectId, linearProjectDetails }
         <div className="mb-4">
           <LinearProjectPicker
             apiKey={linearApiKey}
+            apiBaseUrl={linearApiBaseUrl}
             savedProjects={activeProjects}
             selectedProject={selectedProject}
             onSelect={setSelectedProject}
@@ -1,5 +1,6 @@
 import { useState } from 'react';
 import { commands } from '../lib/commands';
+import { describeApiError } from '../lib/utils';
 
 interface TodoistConnectionFormProps {
   onSuccess: () => void;
@@ -11,6 +12,7 @@ type TestStatus = 'idle' | 'testing' | 'success' | 'error';
 export function TodoistConnectionForm({ onSuccess, onCancel }: TodoistConnectionFormProps) {
   const [connectionName, setConnectionName] = useState('Todoist');
   const [apiToken, setApiToken] = useState('');
+  const [apiBaseUrl, setApiBaseUrl] = useState('');
   const [testStatus, setTestStatus] = useState<TestStatus>('idle');
   const [testResult, setTestResult] = useState<{ userName: string } | null>(null);
   const [error, setError] = useState<string | null>(null);
@@ -27,7 +29,7 @@ export function TodoistConnectionForm({ onSuccess, onCancel }: TodoistConnection
     setTestResult(null);
 
     try {
-      const result = await commands.testTodoistConnection(apiToken.trim());
+      const result = await commands.testTodoistConnection(apiToken.trim(), apiBaseUrl.trim() || null);
 
       if (result.success && result.user_name) {
         setTestStatus('success');
@@ -41,7 +43,7 @@ export function TodoistConnectionForm({ onSuccess, onCancel }: TodoistConnection
     } catch (err) {
       console.error('Failed to test connection:', err);
       setTestStatus('error');
-      setError('Failed to connect to Todoist. Please check your internet connection.');
+      setError(describeApiError(err, 'Todoist', 'Failed to connect to Todoist. Please check your internet connection.'));
     }
   };
 
@@ -57,6 +59,7 @@ export function TodoistConnectionForm({ onSuccess, onCancel }: TodoistConnection
         integration_type: 'todoist',
         connection_config: {
           api_token: apiToken.trim(),
+          ...(apiBaseUrl.trim() && { api_base_url: apiBaseUrl.trim() }),
         },
       });
 
@@ -77,6 +80,15 @@ export function TodoistConnectionForm({ onSuccess, onCancel }: TodoistConnection
     }
   };
 
+  const handleApiBaseUrlChange = (e: React.ChangeEvent<HTMLInputElement>) => {
+    setApiBaseUrl(e.target.value);
+    if (testStatus !== 'idle') {
+      setTestStatus('idle');
+      setTestResult(null);
+      setError(null);
+    }
+  };
+
   const isDisabled = testStatus === 'testing' || isSaving;
 
   return (
@@ -124,6 +136,22 @@ export function TodoistConnectionForm({ onSuccess, onCancel }: TodoistConnection
         />
       </div>
 
+      <div className="mb-4">
+        <label htmlFor="apiBaseUrl" className="block text-sm font-medium text-neutral-300 mb-2">
+          API Base URL <span className="text-neutral-500 font-normal">(optional)</span>
+        </label>
+        <input
+          type="url"
+          id="apiBaseUrl"
+          value={apiBaseUrl}
+          onChange={handleApiBaseUrlChange}
+          placeholder="https://api.todoist.com"
+          className="w-full px-4 py-2 bg-neutral-900 border border-neutral-800 text-white placeholder-neutral-500 rounded-lg focus:outline-none focus:ring-2 focus:ring-[#E44332]"
+          disabled={isDisabled}
+        />
+        <p className="text-xs text-neutral-500 mt-1">Only needed for a proxy or compatible server</p>
+      </div>
+
       {testStatus === 'success' && testResult && (
         <div className="mb-4 p-3 bg-green-500/10 border border-green-500/30 rounded-lg">
           <div className="flex items-center gap-2 mb-1">
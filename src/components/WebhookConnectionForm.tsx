@@ -0,0 +1,192 @@
+import { useState } from 'react';
+import { commands } from '../lib/commands';
+import type { TimeboxEvent } from '../lib/types';
+
+interface WebhookConnectionFormProps {
+  onSuccess: () => void;
+  onCancel: () => void;
+}
+
+type TestStatus = 'idle' | 'testing' | 'success' | 'error';
+
+export const TIMEBOX_EVENTS: { value: TimeboxEvent; label: string }[] = [
+  { value: 'created', label: 'Created' },
+  { value: 'started', label: 'Started' },
+  { value: 'paused', label: 'Paused' },
+  { value: 'stopped', label: 'Stopped' },
+  { value: 'finished', label: 'Finished' },
+  { value: 'overtime', label: 'Overtime' },
+  { value: 'auto_stopped', label: 'Auto-stopped' },
+];
+
+export function WebhookConnectionForm({ onSuccess, onCancel }: WebhookConnectionFormProps) {
+  const [connectionName, setConnectionName] = useState('Webhook');
+  const [url, setUrl] = useState('');
+  const [secret, setSecret] = useState('');
+  const [events, setEvents] = useState<TimeboxEvent[]>(['started', 'paused', 'stopped', 'finished']);
+  const [testStatus, setTestStatus] = useState<TestStatus>('idle');
+  const [error, setError] = useState<string | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const toggleEvent = (event: TimeboxEvent) => {
+    setEvents((current) =>
+      current.includes(event) ? current.filter((e) => e !== event) : [...current, event]
+    );
+  };
+
+  const handleTest = async () => {
+    setTestStatus('testing');
+    setError(null);
+    try {
+      const result = await commands.testWebhook(url.trim(), secret.trim() || undefined);
+      if (result.success) {
+        setTestStatus('success');
+      } else {
+        setTestStatus('error');
+        setError(result.error || 'The webhook did not respond with a 2xx status');
+      }
+    } catch (err) {
+      setTestStatus('error');
+      setError(String(err));
+    }
+  };
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    setError(null);
+    try {
+      await commands.createIntegration({
+        connection_name: connectionName.trim() || 'Webhook',
+        integration_type: 'webhook',
+        connection_config: {
+          url: url.trim(),
+          secret: secret.trim() || null,
+          events,
+        },
+      });
+      onSuccess();
+    } catch (err) {
+      setError(String(err));
+      setIsSaving(false);
+    }
+  };
+
+  const isDisabled = testStatus === 'testing' || isSaving;
+
+  return (
+    <div className="max-w-md">
+      <h3 className="text-xl font-semibold text-white mb-2">Add Webhook</h3>
+      <p className="text-neutral-400 text-sm mb-6">
+        timeboxd will POST a JSON payload with the timebox and its sessions to this URL.
+        Failed deliveries are retried with backoff.
+      </p>
+
+      <div className="mb-4">
+        <label htmlFor="webhookName" className="block text-sm font-medium text-neutral-300 mb-2">
+          Connection Name
+        </label>
+        <input
+          type="text"
+          id="webhookName"
+          value={connectionName}
+          onChange={(e) => setConnectionName(e.target.value)}
+          placeholder="Webhook"
+          className="w-full px-4 py-2 bg-neutral-900 border border-neutral-800 text-white placeholder-neutral-500 rounded-lg focus:outline-none focus:ring-2 focus:ring-neutral-600"
+          disabled={isDisabled}
+        />
+      </div>
+
+      <div className="mb-4">
+        <label htmlFor="webhookUrl" className="block text-sm font-medium text-neutral-300 mb-2">
+          URL
+        </label>
+        <input
+          type="url"
+          id="webhookUrl"
+          value={url}
+          onChange={(e) => {
+            setUrl(e.target.value);
+            setTestStatus('idle');
+          }}
+          placeholder="https://example.com/hooks/timeboxd"
+          className="w-full px-4 py-2 bg-neutral-900 border border-neutral-800 text-white placeholder-neutral-500 rounded-lg focus:outline-none focus:ring-2 focus:ring-neutral-600"
+          disabled={isDisabled}
+        />
+      </div>
+
+      <div className="mb-4">
+        <label htmlFor="webhookSecret" className="block text-sm font-medium text-neutral-300 mb-2">
+          Secret <span className="text-neutral-500 font-normal">(optional)</span>
+        </label>
+        <input
+          type="password"
+          id="webhookSecret"
+          value={secret}
+          onChange={(e) => setSecret(e.target.value)}
+          placeholder="Used to sign payloads with HMAC-SHA256"
+          className="w-full px-4 py-2 bg-neutral-900 border border-neutral-800 text-white placeholder-neutral-500 rounded-lg focus:outline-none focus:ring-2 focus:ring-neutral-600"
+          disabled={isDisabled}
+        />
+        <p className="text-xs text-neutral-500 mt-1">
+          Sent as <span className="font-mono">X-Timeboxd-Signature: sha256=&lt;hex&gt;</span>
+        </p>
+      </div>
+
+      <div className="mb-4">
+        <p className="block text-sm font-medium text-neutral-300 mb-2">Events</p>
+        <div className="grid grid-cols-2 gap-2">
+          {TIMEBOX_EVENTS.map(({ value, label }) => (
+            <label key={value} className="flex items-center gap-2 text-sm text-neutral-300">
+              <input
+                type="checkbox"
+                checked={events.includes(value)}
+                onChange={() => toggleEvent(value)}
+                disabled={isDisabled}
+              />
+              {label}
+            </label>
+          ))}
+        </div>
+      </div>
+
+      {testStatus === 'success' && (
+        <div className="mb-4 p-3 bg-green-500/10 border border-green-500/30 rounded-lg">
+          <span className="text-green-400 text-sm font-medium">Test ping delivered</span>
+        </div>
+      )}
+
+      {error && (
+        <div className="mb-4 p-3 bg-red-500/10 border border-red-500/30 rounded-lg">
+          <span className="text-red-400 text-sm">{error}</span>
+        </div>
+      )}
+
+      <div className="flex gap-3">
+        <button
+          type="button"
+          onClick={onCancel}
+          disabled={isDisabled}
+          className="px-4 py-2 bg-neutral-800 text-neutral-300 rounded-lg hover:bg-neutral-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+        >
+          Cancel
+        </button>
+        <button
+          type="button"
+          onClick={handleTest}
+          disabled={isDisabled || !url.trim()}
+          className="px-4 py-2 bg-neutral-800 text-neutral-300 rounded-lg hover:bg-neutral-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+        >
+          {testStatus === 'testing' ? 'Sending...' : 'Send Test'}
+        </button>
+        <button
+          type="button"
+          onClick={handleSave}
+          disabled={isDisabled || !url.trim() || events.length === 0}
+          className="flex-1 px-4 py-2 bg-green-600 text-white rounded-lg hover:bg-green-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors font-medium"
+        >
+          {isSaving ? 'Saving...' : 'Add Webhook'}
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,71 @@
+import { useState, useEffect, useCallback } from 'react';
+import { commands } from '../lib/commands';
+import type { WebhookDelivery } from '../lib/types';
+
+interface WebhookDeliveryLogProps {
+  integrationId: number;
+}
+
+const STATUS_CLASSES: Record<WebhookDelivery['status'], string> = {
+  pending: 'text-yellow-400',
+  delivered: 'text-green-400',
+  failed: 'text-red-400',
+};
+
+export function WebhookDeliveryLog({ integrationId }: WebhookDeliveryLogProps) {
+  const [deliveries, setDeliveries] = useState<WebhookDelivery[]>([]);
+
+  const load = useCallback(() => {
+    commands.getWebhookDeliveries(integrationId, 20)
+      .then(setDeliveries)
+      .catch(console.error);
+  }, [integrationId]);
+
+  useEffect(() => {
+    load();
+    // Deliveries are sent in the background, keep the log current while it's open
+    const interval = setInterval(load, 5000);
+    return () => clearInterval(interval);
+  }, [load]);
+
+  const handleRetry = async (id: number) => {
+    try {
+      await commands.retryWebhookDelivery(id);
+      load();
+    } catch (error) {
+      console.error('Failed to retry webhook delivery:', error);
+    }
+  };
+
+  if (deliveries.length === 0) {
+    return <p className="text-xs text-neutral-500">No deliveries yet</p>;
+  }
+
+  return (
+    <ul className="space-y-1">
+      {deliveries.map((delivery) => (
+        <li key={delivery.id} className="flex items-center gap-3 text-xs">
+          <span className="text-neutral-500 font-mono shrink-0">{delivery.created_at}</span>
+          <span className="text-neutral-300 w-24 shrink-0">{delivery.event}</span>
+          <span className={`${STATUS_CLASSES[delivery.status]} shrink-0`}>
+            {delivery.status}
+            {delivery.response_status !== null && ` (${delivery.response_status})`}
+          </span>
+          {delivery.last_error && (
+            <span className="text-neutral-500 truncate" title={delivery.last_error}>
+              {delivery.last_error}
+            </span>
+          )}
+          {delivery.status === 'failed' && (
+            <button
+              onClick={() => handleRetry(delivery.id)}
+              className="ml-auto px-2 py-0.5 text-neutral-300 hover:bg-neutral-800 rounded transition-colors"
+            >
+              Retry
+            </button>
+          )}
+        </li>
+      ))}
+    </ul>
+  );
+}
@@ -1,6 +1,7 @@
 import { createContext, useContext, useCallback, useState, useEffect, useMemo, type ReactNode } from 'react';
+import { listen } from '@tauri-apps/api/event';
 import { commands } from '../lib/commands';
-import type { TimeboxWithSessions, Integration, LinearConfig, IntegrationType, IdleSettings } from '../lib/types';
+import type { TimeboxWithSessions, Integration, LinearConfig, LinearTimeComment, IntegrationType, IdleSettings } from '../lib/types';
 import type { Page } from '../components/LeftNav';
 
 // ============================================
@@ -11,7 +12,9 @@ import type { Page } from '../components/LeftNav';
 interface LinearSettings {
   isConnected: boolean;
   openInNativeApp: boolean;
+  timeComment: LinearTimeComment;
   apiKey: string | null;
+  apiBaseUrl: string | null;
 }
 
 interface TodoistSettings {
@@ -30,6 +33,7 @@ interface IntegrationsState {
 interface IntegrationsActions {
   refreshIntegrations: () => Promise<void>;
   updateLinearOpenInNativeApp: (value: boolean) => Promise<void>;
+  updateLinearTimeComment: (value: LinearTimeComment) => Promise<void>;
   checkConnection: (type: IntegrationType) => Promise<boolean>;
   updateIdleSettings: (settings: IdleSettings) => Promise<void>;
 }
@@ -72,7 +76,9 @@ interface AppContextValue {
 const defaultLinearSettings: LinearSettings = {
   isConnected: false,
   openInNativeApp: false,
+  timeComment: 'never',
   apiKey: null,
+  apiBaseUrl: null,
 };
 
 const defaultTodoistSettings: TodoistSettings = {
@@ -140,7 +146,9 @@ export function AppProvider({ children }: AppProviderProps) {
         setLinearSettings({
           isConnected: true,
           openInNativeApp: config.open_in_native_app ?? false,
+          timeComment: config.time_comment ?? 'never',
           apiKey: config.api_key,
+          apiBaseUrl: config.api_base_url ?? null,
         });
       } else {
         setLinearSettings(defaultLinearSettings);
@@ -195,6 +203,26 @@ export function AppProvider({ children }: AppProviderProps) {
     }
   }, []);
 
+  const updateLinearTimeComment = useCallback(async (value: LinearTimeComment) => {
+    const currentIntegrations = await commands.getIntegrations();
+    const linearIntegration = currentIntegrations.find(i => i.integration_type === 'linear');
+    if (!linearIntegration) return;
+
+    const currentConfig = linearIntegration.connection_config as unknown as LinearConfig;
+
+    try {
+      await commands.updateIntegrationConfig(linearIntegration.id, {
+        ...currentConfig,
+        time_comment: value,
+      });
+
+      setLinearSettings(prev => ({ ...prev, timeComment: value }));
+    } catch (error) {
+      console.error('Failed to update Linear setting:', error);
+      throw error;
+    }
+  }, []);
+
   const checkConnection = useCallback(async (type: IntegrationType): Promise<boolean> => {
     try {
       const integration = await commands.getIntegrationByType(type);
@@ -281,6 +309,16 @@ export function AppProvider({ children }: AppProviderProps) {
     initialize();
   }, [refreshIntegrations, refreshData]);
 
+  // Refresh when timeboxes are changed outside this window (e.g. from the tray menu)
+  useEffect(() => {
+    const unlisten = listen('timeboxes-changed', () => {
+      refreshData();
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [refreshData]);
+
   // ========================================
   // MEMOIZED CONTEXT VALUE
   // ========================================
@@ -293,6 +331,7 @@ export function AppProvider({ children }: AppProviderProps) {
       idleSettings,
       refreshIntegrations,
       updateLinearOpenInNativeApp,
+      updateLinearTimeComment,
       checkConnection,
       updateIdleSettings,
     },
@@ -321,6 +360,7 @@ export function AppProvider({ children }: AppProviderProps) {
     idleSettings,
     refreshIntegrations,
     updateLinearOpenInNativeApp,
+    updateLinearTimeComment,
     checkConnection,
     updateIdleSettings,
     currentPage,
@@ -378,6 +418,7 @@ export function useLinear() {
   return {
     ...integrations.linear,
     updateOpenInNativeApp: integrations.updateLinearOpenInNativeApp,
+    updateTimeComment: integrations.updateLinearTimeComment,
     refresh: integrations.refreshIntegrations,
   };
 }
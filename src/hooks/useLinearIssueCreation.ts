@@ -39,17 +39,17 @@ export function useLinearIssueCreation({
         description: timebox.notes || undefined,
         project_id: currentProject.linear_project_id,
         team_id: currentProject.linear_team_id,
-      });
+      }, config.api_base_url);
 
       if (result.success && result.issue) {
-        await commands.setTimeboxLinearIssue(timebox.id, result.issue.id, result.issue.url);
+        await commands.setTimeboxLinearIssue(timebox.id, result.issue.id, result.issue.identifier, result.issue.url);
 
         // Sync to In Progress state if requested (for active timeboxes)
         if (syncToInProgress) {
-          const states = await commands.getLinearTeamStates(config.api_key, currentProject.linear_team_id);
+          const states = await commands.getLinearTeamStates(config.api_key, currentProject.linear_team_id, config.api_base_url);
           const inProgressState = states.find(s => s.state_type === 'started');
           if (inProgressState) {
-            await commands.updateLinearIssueState(config.api_key, result.issue.id, inProgressState.id);
+            await commands.updateLinearIssueState(config.api_key, result.issue.id, inProgressState.id, config.api_base_url);
           }
         }
 
@@ -1,9 +1,11 @@
 import { useState, useCallback, useRef, useEffect } from 'react';
 import { commands } from '../lib/commands';
+import { describeApiError } from '../lib/utils';
 import type { LinearTeam } from '../lib/types';
 
 interface UseTeamSearchOptions {
   apiKey: string;
+  apiBaseUrl?: string | null;
   debounceMs?: number;
   minSearchLength?: number;
 }
@@ -19,6 +21,7 @@ interface UseTeamSearchReturn {
 
 export function useTeamSearch({
   apiKey,
+  apiBaseUrl,
   debounceMs = 300,
   minSearchLength = 2,
 }: UseTeamSearchOptions): UseTeamSearchReturn {
@@ -57,7 +60,7 @@ export function useTeamSearch({
       abortControllerRef.current = controller;
 
       try {
-        const searchResults = await commands.searchLinearTeams(apiKey, term.trim());
+        const searchResults = await commands.searchLinearTeams(apiKey, term.trim(), apiBaseUrl);
 
         // Check if this request was aborted
         if (controller.signal.aborted) return;
@@ -69,7 +72,7 @@ export function useTeamSearch({
         if (controller.signal.aborted) return;
 
         console.error('Team search failed:', err);
-        setError('Search failed');
+        setError(describeApiError(err, 'Linear', 'Search failed'));
         setResults([]);
       } finally {
         if (!controller.signal.aborted) {
@@ -77,7 +80,7 @@ export function useTeamSearch({
         }
       }
     }, debounceMs);
-  }, [apiKey, debounceMs, minSearchLength]);
+  }, [apiKey, apiBaseUrl, debounceMs, minSearchLength]);
 
   const clearSearch = useCallback(() => {
     setSearchTermState('');
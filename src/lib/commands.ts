@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Timebox, TimeboxWithSessions, Session, CreateTimeboxRequest, UpdateTimeboxRequest, ReorderTimeboxRequest, Integration, CreateIntegrationRequest, LinearTestResult, TodoistTestResult, LinearTeam, LinearApiProject, LinearSearchProject, LinearProject, SaveLinearProjectRequest, LinearApiIssue, CreateLinearIssueRequest, CreateLinearIssueResult, LinearTeamWorkflowState, IdleSettings } from './types';
+import type { Timebox, TimeboxWithSessions, Session, CreateTimeboxRequest, UpdateTimeboxRequest, ReorderTimeboxRequest, Integration, CreateIntegrationRequest, ProviderDescriptor, ConnectionTestResult, ProviderTask, LinearTestResult, TodoistTestResult, LinearTeam, LinearApiProject, LinearSearchProject, LinearProject, SaveLinearProjectRequest, LinearApiIssue, CachedLinearProjectIssues, LinearIssueSyncResult, LinearPlanningIssue, LinearCycle, EstimateMinutes, PlannedIssue, PlannedTimeboxes, LinearLinkValidationResult, LinearProjectRefreshResult, CreateLinearIssueRequest, CreateLinearIssueResult, LinearTeamWorkflowState, LinearStateRule, LinearStateRuleEvent, LinearStateTransition, IdleSettings, ShortcutSettings, ParsedDuration, HttpApiSettings, WebhookDelivery, WebhookTestResult, OutboxItem, OutboxStatus, ShellHook, SaveShellHookRequest, ShellHookRun } from './types';
 
 export const commands = {
   createTimebox: (request: CreateTimeboxRequest) =>
@@ -66,18 +66,28 @@ export const commands = {
   updateIntegrationConfig: (id: number, connectionConfig: Record<string, unknown>) =>
     invoke<Integration>('update_integration_config', { id, connectionConfig }),
 
-  testLinearConnection: (apiKey: string) =>
-    invoke<LinearTestResult>('test_linear_connection', { apiKey }),
+  getIntegrationProviders: () =>
+    invoke<ProviderDescriptor[]>('get_integration_providers'),
 
-  testTodoistConnection: (apiToken: string) =>
-    invoke<TodoistTestResult>('test_todoist_connection', { apiToken }),
+  testIntegrationConnection: (integrationType: string, connectionConfig: Record<string, unknown>) =>
+    invoke<ConnectionTestResult>('test_integration_connection', { integrationType, connectionConfig }),
+
+  fetchIntegrationTasks: (id: number) =>
+    invoke<ProviderTask[]>('fetch_integration_tasks', { id }),
+
+  // apiBaseUrl overrides the production API, see `api_base_url` in connection configs
+  testLinearConnection: (apiKey: string, apiBaseUrl?: string | null) =>
+    invoke<LinearTestResult>('test_linear_connection', { apiKey, apiBaseUrl }),
+
+  testTodoistConnection: (apiToken: string, apiBaseUrl?: string | null) =>
+    invoke<TodoistTestResult>('test_todoist_connection', { apiToken, apiBaseUrl }),
 
   // Linear project commands
-  getLinearTeams: (apiKey: string) =>
-    invoke<LinearTeam[]>('get_linear_teams', { apiKey }),
+  getLinearTeams: (apiKey: string, apiBaseUrl?: string | null) =>
+    invoke<LinearTeam[]>('get_linear_teams', { apiKey, apiBaseUrl }),
 
-  getLinearTeamProjects: (apiKey: string, teamId: string) =>
-    invoke<LinearApiProject[]>('get_linear_team_projects', { apiKey, teamId }),
+  getLinearTeamProjects: (apiKey: string, teamId: string, apiBaseUrl?: string | null) =>
+    invoke<LinearApiProject[]>('get_linear_team_projects', { apiKey, teamId, apiBaseUrl }),
 
   saveLinearProject: (request: SaveLinearProjectRequest) =>
     invoke<LinearProject>('save_linear_project', { request }),
@@ -100,32 +110,70 @@ export const commands = {
   deleteLinearProject: (linearProjectId: string) =>
     invoke<void>('delete_linear_project', { linearProjectId }),
 
+  // Linear planning commands
+  getLinearMyIssues: () =>
+    invoke<LinearPlanningIssue[]>('get_linear_my_issues'),
+
+  getLinearCurrentCycle: (teamId: string) =>
+    invoke<LinearCycle | null>('get_linear_current_cycle', { teamId }),
+
+  getLinearEstimateMinutes: () =>
+    invoke<EstimateMinutes[]>('get_linear_estimate_minutes'),
+
+  createTimeboxesFromLinearIssues: (issues: PlannedIssue[], unestimatedMinutes?: number) =>
+    invoke<PlannedTimeboxes>('create_timeboxes_from_linear_issues', { issues, unestimatedMinutes }),
+
+  validateLinearIssueLinks: () =>
+    invoke<LinearLinkValidationResult>('validate_linear_issue_links'),
+
+  refreshLinearProjects: () =>
+    invoke<LinearProjectRefreshResult>('refresh_linear_projects'),
+
   // Linear search commands
-  searchLinearTeams: (apiKey: string, searchTerm: string) =>
-    invoke<LinearTeam[]>('search_linear_teams', { apiKey, searchTerm }),
+  searchLinearTeams: (apiKey: string, searchTerm: string, apiBaseUrl?: string | null) =>
+    invoke<LinearTeam[]>('search_linear_teams', { apiKey, searchTerm, apiBaseUrl }),
 
-  searchLinearProjects: (apiKey: string, searchTerm: string) =>
-    invoke<LinearSearchProject[]>('search_linear_projects', { apiKey, searchTerm }),
+  searchLinearProjects: (apiKey: string, searchTerm: string, apiBaseUrl?: string | null) =>
+    invoke<LinearSearchProject[]>('search_linear_projects', { apiKey, searchTerm, apiBaseUrl }),
 
   // Linear issue commands
-  createLinearIssue: (apiKey: string, request: CreateLinearIssueRequest) =>
-    invoke<CreateLinearIssueResult>('create_linear_issue', { apiKey, request }),
+  createLinearIssue: (apiKey: string, request: CreateLinearIssueRequest, apiBaseUrl?: string | null) =>
+    invoke<CreateLinearIssueResult>('create_linear_issue', { apiKey, request, apiBaseUrl }),
+
+  getLinearTeamStates: (apiKey: string, teamId: string, apiBaseUrl?: string | null) =>
+    invoke<LinearTeamWorkflowState[]>('get_linear_team_states', { apiKey, teamId, apiBaseUrl }),
 
-  getLinearTeamStates: (apiKey: string, teamId: string) =>
-    invoke<LinearTeamWorkflowState[]>('get_linear_team_states', { apiKey, teamId }),
+  updateLinearIssueState: (apiKey: string, issueId: string, stateId: string, apiBaseUrl?: string | null) =>
+    invoke<boolean>('update_linear_issue_state', { apiKey, issueId, stateId, apiBaseUrl }),
 
-  updateLinearIssueState: (apiKey: string, issueId: string, stateId: string) =>
-    invoke<boolean>('update_linear_issue_state', { apiKey, issueId, stateId }),
+  getLinearProjectIssues: (apiKey: string, projectId: string, apiBaseUrl?: string | null) =>
+    invoke<LinearApiIssue[]>('get_linear_project_issues', { apiKey, projectId, apiBaseUrl }),
 
-  getLinearProjectIssues: (apiKey: string, projectId: string) =>
-    invoke<LinearApiIssue[]>('get_linear_project_issues', { apiKey, projectId }),
+  getCachedLinearProjectIssues: (projectId: string) =>
+    invoke<CachedLinearProjectIssues>('get_cached_linear_project_issues', { projectId }),
 
-  setTimeboxLinearIssue: (timeboxId: number, linearIssueId: string, linearIssueUrl: string) =>
-    invoke<Timebox>('set_timebox_linear_issue', { timeboxId, linearIssueId, linearIssueUrl }),
+  syncLinearProjectIssues: (projectId: string, full = false) =>
+    invoke<LinearIssueSyncResult>('sync_linear_project_issues', { projectId, full }),
+
+  setTimeboxLinearIssue: (timeboxId: number, linearIssueId: string, linearIssueIdentifier: string, linearIssueUrl: string) =>
+    invoke<Timebox>('set_timebox_linear_issue', { timeboxId, linearIssueId, linearIssueIdentifier, linearIssueUrl }),
 
   setTimeboxLinearProject: (timeboxId: number, linearProjectId: number | null) =>
     invoke<Timebox>('set_timebox_linear_project', { timeboxId, linearProjectId }),
 
+  // Linear state transition commands
+  getLinearWorkflowStates: (teamId: string, refresh = false) =>
+    invoke<LinearTeamWorkflowState[]>('get_linear_workflow_states', { teamId, refresh }),
+
+  getLinearStateRules: (linearProjectId: number) =>
+    invoke<LinearStateRule[]>('get_linear_state_rules', { linearProjectId }),
+
+  setLinearStateRule: (linearProjectId: number, event: LinearStateRuleEvent, stateName: string | null) =>
+    invoke<LinearStateRule[]>('set_linear_state_rule', { linearProjectId, event, stateName }),
+
+  previewLinearStateTransitions: (timeboxId: number) =>
+    invoke<LinearStateTransition[]>('preview_linear_state_transitions', { timeboxId }),
+
   // Idle detection commands
   getSystemIdleTime: () =>
     invoke<number>('get_system_idle_time'),
@@ -139,6 +187,79 @@ export const commands = {
   setIdleSettings: (settings: IdleSettings) =>
     invoke<void>('set_idle_settings', { settings }),
 
+  // Shortcut commands
+  getShortcutSettings: () =>
+    invoke<ShortcutSettings>('get_shortcut_settings'),
+
+  setShortcutSettings: (settings: ShortcutSettings) =>
+    invoke<void>('set_shortcut_settings', { settings }),
+
+  // HTTP API commands
+  getHttpApiSettings: () =>
+    invoke<HttpApiSettings>('get_http_api_settings'),
+
+  setHttpApiSettings: (enabled: boolean, port: number) =>
+    invoke<HttpApiSettings>('set_http_api_settings', { enabled, port }),
+
+  regenerateHttpApiToken: () =>
+    invoke<HttpApiSettings>('regenerate_http_api_token'),
+
+  // Webhook commands
+  getWebhookDeliveries: (integrationId?: number, limit?: number) =>
+    invoke<WebhookDelivery[]>('get_webhook_deliveries', { integrationId, limit }),
+
+  retryWebhookDelivery: (id: number) =>
+    invoke<WebhookDelivery>('retry_webhook_delivery', { id }),
+
+  testWebhook: (url: string, secret?: string) =>
+    invoke<WebhookTestResult>('test_webhook', { url, secret }),
+
+  // Outbox commands
+  getOutboxItems: (status?: OutboxStatus, limit?: number) =>
+    invoke<OutboxItem[]>('get_outbox_items', { status, limit }),
+
+  retryOutboxItem: (id: number) =>
+    invoke<OutboxItem>('retry_outbox_item', { id }),
+
+  discardOutboxItem: (id: number) =>
+    invoke<OutboxItem>('discard_outbox_item', { id }),
+
+  // Shell hook commands
+  getShellHooksEnabled: () =>
+    invoke<boolean>('get_shell_hooks_enabled'),
+
+  setShellHooksEnabled: (enabled: boolean) =>
+    invoke<void>('set_shell_hooks_enabled', { enabled }),
+
+  getShellHooks: () =>
+    invoke<ShellHook[]>('get_shell_hooks'),
+
+  createShellHook: (request: SaveShellHookRequest) =>
+    invoke<ShellHook>('create_shell_hook', { request }),
+
+  updateShellHook: (id: number, request: SaveShellHookRequest) =>
+    invoke<ShellHook>('update_shell_hook', { id, request }),
+
+  deleteShellHook: (id: number) =>
+    invoke<void>('delete_shell_hook', { id }),
+
+  getShellHookRuns: (limit?: number) =>
+    invoke<ShellHookRun[]>('get_shell_hook_runs', { limit }),
+
+  // Quick capture commands
+  parseQuickCapture: (input: string) =>
+    invoke<CreateTimeboxRequest>('parse_quick_capture', { input }),
+
+  quickCaptureTimebox: (input: string, start: boolean) =>
+    invoke<Timebox>('quick_capture_timebox', { input, start }),
+
+  // Duration commands
+  parseDuration: (input: string, locale?: string) =>
+    invoke<ParsedDuration>('parse_duration', { input, locale }),
+
+  getDurationPresets: () =>
+    invoke<string[]>('get_duration_presets'),
+
   // Sound commands
   playSystemSound: (soundName?: string) =>
     invoke<void>('play_system_sound', { soundName }),
@@ -20,8 +20,14 @@ export interface Timebox {
   linear_issue_id?: string;
   linear_issue_identifier?: string;
   linear_issue_url?: string;
+  tags?: string[];
+  linear_issue_link_status?: LinearIssueLinkStatus | null;
+  linear_issue_checked_at?: string | null;
 }
 
+// Result of the last check of a timebox's Linear issue; anything but 'ok' is a broken link
+export type LinearIssueLinkStatus = 'ok' | 'deleted' | 'archived' | 'moved';
+
 export interface ReorderTimeboxRequest {
   id: number;
   display_order: number;
@@ -49,6 +55,13 @@ export interface CreateTimeboxRequest {
   linear_issue_id?: string;
   linear_issue_identifier?: string;
   linear_issue_url?: string;
+  tags?: string[];
+}
+
+export interface ParsedDuration {
+  seconds: number;
+  intended_duration: number;
+  ends_at: string;
 }
 
 export interface UpdateTimeboxRequest {
@@ -58,7 +71,47 @@ export interface UpdateTimeboxRequest {
 }
 
 // Integration types
-export type IntegrationType = 'linear' | 'todoist';
+// Providers come from the backend registry, these are the ones with their own UI
+export type IntegrationType = 'linear' | 'todoist' | 'webhook' | (string & {});
+
+export type ConfigFieldKind = 'secret' | 'url' | 'boolean' | 'events' | 'choice';
+
+export interface ConfigOption {
+  value: string;
+  label: string;
+}
+
+export interface ConfigField {
+  key: string;
+  label: string;
+  kind: ConfigFieldKind;
+  required: boolean;
+  placeholder: string | null;
+  help: string | null;
+  // Allowed values of a 'choice' field, empty otherwise
+  options: ConfigOption[];
+}
+
+export interface ProviderDescriptor {
+  integration_type: IntegrationType;
+  name: string;
+  description: string;
+  allow_multiple: boolean;
+  config_schema: ConfigField[];
+}
+
+export interface ConnectionTestResult {
+  success: boolean;
+  account_name: string | null;
+  error: string | null;
+}
+
+export interface ProviderTask {
+  id: string;
+  title: string;
+  url: string | null;
+  identifier: string | null;
+}
 
 export interface Integration {
   id: number;
@@ -78,6 +131,22 @@ export interface CreateIntegrationRequest {
 export interface LinearConfig {
   api_key: string;
   open_in_native_app?: boolean;
+  time_comment?: LinearTimeComment;
+  estimate_minutes?: EstimateMinutes[];
+  api_base_url?: string;
+}
+
+// When to post time spent as a comment on the linked issue
+export type LinearTimeComment = 'never' | 'on_finish' | 'on_stop';
+
+// Errors from Linear and Todoist commands
+export type ApiErrorKind = 'auth' | 'rate_limited' | 'offline' | 'server' | 'rejected' | 'invalid_response';
+
+export interface ApiError {
+  kind: ApiErrorKind;
+  message: string;
+  status: number | null;
+  retry_after_secs: number | null;
 }
 
 export interface LinearTestResult {
@@ -173,6 +242,29 @@ export interface LinearTeamWorkflowState {
   state_type: string;
 }
 
+// Timebox events a project can move its issues on
+export type LinearStateRuleEvent = 'started' | 'stopped' | 'finished';
+
+export interface LinearStateRule {
+  id: number;
+  linear_project_id: number;
+  event: LinearStateRuleEvent;
+  state_name: string;
+  state_type: string | null; // Default rules follow the team's first state of this type
+  created_at: string;
+  updated_at: string;
+}
+
+// What an event would do to a timebox's issue (dry run)
+export interface LinearStateTransition {
+  event: LinearStateRuleEvent;
+  issue_id: string | null;
+  issue_identifier: string | null;
+  state_name: string | null;
+  state_id: string | null;
+  skipped_reason: string | null;
+}
+
 // Linear Issue types for project issues view
 export interface LinearWorkflowState {
   id: string;
@@ -206,6 +298,90 @@ export interface LinearApiIssue {
   state: LinearWorkflowState | null;
   assignee: LinearUser | null;
   labels: { nodes: LinearLabel[] } | null;
+  updated_at: string | null;
+}
+
+// An issue offered for planning, from "My issues" or a cycle
+export interface LinearPlanningIssue {
+  id: string;
+  identifier: string;
+  title: string;
+  url: string;
+  priority: number | null;
+  priority_label: string | null;
+  estimate: number | null;
+  state: LinearWorkflowState | null;
+  project: { id: string; name: string } | null;
+}
+
+export interface LinearCycle {
+  id: string;
+  number: number;
+  name: string | null;
+  starts_at: string;
+  ends_at: string;
+  issues: LinearPlanningIssue[];
+}
+
+// One row of the table that turns issue estimates into timebox minutes
+export interface EstimateMinutes {
+  points: number;
+  minutes: number;
+}
+
+export interface PlannedIssue {
+  id: string;
+  identifier: string;
+  title: string;
+  url: string;
+  estimate: number | null;
+  project_id: string | null;
+}
+
+export interface PlannedTimeboxes {
+  created: Timebox[];
+  skipped: string[]; // identifiers of issues that already had an open timebox
+}
+
+// A timebox whose Linear issue was deleted, archived or moved
+export interface BrokenLinearLink {
+  timebox_id: number;
+  linear_issue_identifier: string | null;
+  status: LinearIssueLinkStatus;
+}
+
+export interface LinearLinkValidationResult {
+  checked: number;
+  broken: BrokenLinearLink[];
+  checked_at: string;
+}
+
+export interface LinearProjectChange {
+  linear_project_id: string;
+  name: string;
+  fields: string[]; // 'name', 'description' and/or 'state'
+  archived: boolean; // archived locally because it was archived or completed in Linear
+}
+
+export interface LinearProjectRefreshResult {
+  checked: number;
+  changed: LinearProjectChange[];
+  missing: string[]; // saved projects Linear no longer returns
+  refreshed_at: string;
+}
+
+// A project's issues from the local cache
+export interface CachedLinearProjectIssues {
+  issues: LinearApiIssue[];
+  synced_at: string | null; // null if the project was never synced
+}
+
+export interface LinearIssueSyncResult {
+  full: boolean;
+  updated: number;
+  removed: number;
+  truncated: boolean; // Linear had more issues than a sync fetches
+  synced_at: string;
 }
 
 // Idle detection settings
@@ -213,3 +389,109 @@ export interface IdleSettings {
   enabled: boolean;
   timeout_minutes: number;
 }
+
+// Global keyboard shortcuts (null disables a shortcut)
+export interface ShortcutSettings {
+  toggle_pause: string | null;
+  finish_current: string | null;
+  quick_create: string | null;
+}
+
+export interface HttpApiSettings {
+  enabled: boolean;
+  port: number;
+  token: string;
+}
+
+// Timebox lifecycle events for webhooks and shell hooks
+export type TimeboxEvent = 'created' | 'started' | 'paused' | 'stopped' | 'finished' | 'overtime' | 'auto_stopped';
+
+export interface WebhookConfig {
+  url: string;
+  secret?: string | null;
+  events: TimeboxEvent[];
+}
+
+export type WebhookDeliveryStatus = 'pending' | 'delivered' | 'failed';
+
+export interface WebhookDelivery {
+  id: number;
+  integration_id: number;
+  timebox_id: number | null;
+  event: TimeboxEvent;
+  url: string;
+  payload: string;
+  status: WebhookDeliveryStatus;
+  attempts: number;
+  response_status: number | null;
+  last_error: string | null;
+  next_attempt_at: string | null;
+  delivered_at: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export type OutboxStatus = 'pending' | 'delivered' | 'failed' | 'discarded';
+
+export type OutboxActionType =
+  | 'todoist_create_completed_task'
+  | 'linear_post_comment'
+  | 'linear_update_issue_state';
+
+/** A side effect on an integration, queued with the timebox change that caused it */
+export interface OutboxItem {
+  id: number;
+  integration_id: number;
+  timebox_id: number | null;
+  action: OutboxActionType;
+  payload: string;
+  status: OutboxStatus;
+  attempts: number;
+  last_error: string | null;
+  next_attempt_at: string | null;
+  delivered_at: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface WebhookTestResult {
+  success: boolean;
+  response_status: number | null;
+  error: string | null;
+}
+
+// Shell hooks
+export interface ShellHook {
+  id: number;
+  event: TimeboxEvent;
+  command: string;
+  timeout_seconds: number;
+  enabled: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface SaveShellHookRequest {
+  event: TimeboxEvent;
+  command: string;
+  timeout_seconds?: number;
+  enabled?: boolean;
+}
+
+export type ShellHookRunStatus = 'pending' | 'running' | 'succeeded' | 'failed' | 'timed_out' | 'skipped';
+
+export interface ShellHookRun {
+  id: number;
+  hook_id: number;
+  timebox_id: number | null;
+  event: TimeboxEvent;
+  command: string;
+  env: string;
+  status: ShellHookRunStatus;
+  exit_code: number | null;
+  stdout: string | null;
+  stderr: string | null;
+  started_at: string | null;
+  finished_at: string | null;
+  created_at: string;
+}
@@ -1,4 +1,5 @@
 import { openUrl } from '@tauri-apps/plugin-opener';
+import type { ApiError } from './types';
 
 /**
  * Opens a Linear URL, optionally transforming it to use the native app URL scheme.
@@ -17,3 +18,29 @@ export async function openLinearUrl(webUrl: string, useNativeApp: boolean): Prom
     await openUrl(webUrl);
   }
 }
+
+export function isApiError(err: unknown): err is ApiError {
+  return typeof err === 'object' && err !== null && 'kind' in err && 'message' in err;
+}
+
+/**
+ * User-facing text for an error from an integration API command.
+ * Anything that isn't a structured API error gets the fallback.
+ */
+export function describeApiError(err: unknown, service: string, fallback: string): string {
+  if (!isApiError(err)) return fallback;
+  switch (err.kind) {
+    case 'auth':
+      return `${service} rejected the API key. Check it in Integrations.`;
+    case 'rate_limited':
+      return err.retry_after_secs
+        ? `${service} rate limit reached. Try again in ${Math.ceil(err.retry_after_secs / 60)} min.`
+        : `${service} rate limit reached. Try again shortly.`;
+    case 'offline':
+      return `Can't reach ${service}. Please check your internet connection.`;
+    case 'server':
+      return `${service} is having problems. Try again later.`;
+    default:
+      return fallback;
+  }
+}
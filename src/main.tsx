@@ -1,9 +1,14 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import App from "./App";
+import { QuickCapture } from "./components/QuickCapture";
+
+// The quick-capture window loads the same bundle but only needs the capture input
+const isQuickCapture = getCurrentWebviewWindow().label === "quick-capture";
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {isQuickCapture ? <QuickCapture /> : <App />}
   </React.StrictMode>,
 );
@@ -2,10 +2,21 @@ import { useState, useEffect } from 'react';
 import { commands } from '../lib/commands';
 import { LinearConnectionForm } from '../components/LinearConnectionForm';
 import { TodoistConnectionForm } from '../components/TodoistConnectionForm';
+import { ShortcutSettingsForm } from '../components/ShortcutSettingsForm';
+import { HttpApiSettingsForm } from '../components/HttpApiSettingsForm';
+import { ShellHooksSettingsForm } from '../components/ShellHooksSettingsForm';
+import { WebhookConnectionForm } from '../components/WebhookConnectionForm';
+import { WebhookDeliveryLog } from '../components/WebhookDeliveryLog';
+import { OutboxLog } from '../components/OutboxLog';
+import { ProviderConnectionForm } from '../components/ProviderConnectionForm';
+import { LinearEstimateTableEditor } from '../components/LinearEstimateTableEditor';
 import { useLinear, useIdleSettings } from '../contexts/AppContext';
-import type { Integration } from '../lib/types';
+import type { Integration, LinearTimeComment, ProviderDescriptor } from '../lib/types';
 
-type View = 'list' | 'connect-linear' | 'connect-todoist' | 'success';
+type View = 'list' | 'connect-linear' | 'connect-todoist' | 'connect-webhook' | 'connect-provider' | 'success';
+
+// Providers with a hand-built card and form, the rest use ProviderConnectionForm
+const CUSTOM_PROVIDERS = ['linear', 'todoist', 'webhook'];
 
 interface IntegrationsPageProps {
   onLinearConnectionChange?: () => void;
@@ -14,10 +25,17 @@ interface IntegrationsPageProps {
 export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageProps) {
   const [view, setView] = useState<View>('list');
   const [integrations, setIntegrations] = useState<Integration[]>([]);
+  const [providers, setProviders] = useState<ProviderDescriptor[]>([]);
+  const [selectedProvider, setSelectedProvider] = useState<ProviderDescriptor | null>(null);
   const [loading, setLoading] = useState(true);
 
   // Use context for Linear settings - no page reload needed!
-  const { openInNativeApp: linearOpenInNativeApp, updateOpenInNativeApp } = useLinear();
+  const {
+    openInNativeApp: linearOpenInNativeApp,
+    updateOpenInNativeApp,
+    timeComment: linearTimeComment,
+    updateTimeComment,
+  } = useLinear();
 
   // Use context for idle settings
   const { enabled: idleEnabled, timeout_minutes: idleTimeoutMinutes, updateIdleSettings } = useIdleSettings();
@@ -63,8 +81,17 @@ export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageP
     }
   };
 
+  const handleLinearTimeCommentChange = async (value: LinearTimeComment) => {
+    try {
+      await updateTimeComment(value);
+    } catch (error) {
+      console.error('Failed to update Linear setting:', error);
+    }
+  };
+
   useEffect(() => {
     loadIntegrations();
+    commands.getIntegrationProviders().then(setProviders).catch(console.error);
   }, []);
 
   const handleConnectionSuccess = () => {
@@ -85,6 +112,11 @@ export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageP
 
   const isLinearConnected = integrations.some(i => i.integration_type === 'linear');
   const isTodoistConnected = integrations.some(i => i.integration_type === 'todoist');
+  const otherProviders = providers.filter(
+    (p) =>
+      !CUSTOM_PROVIDERS.includes(p.integration_type) &&
+      (p.allow_multiple || !integrations.some((i) => i.integration_type === p.integration_type))
+  );
 
   if (loading) {
     return (
@@ -153,6 +185,37 @@ export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageP
     );
   }
 
+  // Add webhook form view
+  if (view === 'connect-webhook') {
+    return (
+      <div className="p-6">
+        <h2 className="text-2xl font-bold text-white mb-6">Integrations</h2>
+        <div className="bg-[#0a0a0a] rounded-lg p-6 border border-neutral-800">
+          <WebhookConnectionForm
+            onSuccess={handleConnectionSuccess}
+            onCancel={() => setView('list')}
+          />
+        </div>
+      </div>
+    );
+  }
+
+  // Connect form for any other registered provider
+  if (view === 'connect-provider' && selectedProvider) {
+    return (
+      <div className="p-6">
+        <h2 className="text-2xl font-bold text-white mb-6">Integrations</h2>
+        <div className="bg-[#0a0a0a] rounded-lg p-6 border border-neutral-800">
+          <ProviderConnectionForm
+            provider={selectedProvider}
+            onSuccess={handleConnectionSuccess}
+            onCancel={() => setView('list')}
+          />
+        </div>
+      </div>
+    );
+  }
+
   // List view
   return (
     <div className="p-6">
@@ -185,9 +248,22 @@ export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageP
                         </svg>
                       </div>
                     )}
+                    {integration.integration_type === 'webhook' && (
+                      <div className="w-10 h-10 bg-neutral-700/40 rounded-lg flex items-center justify-center">
+                        <svg className="w-5 h-5 text-neutral-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                          <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={1.5} d="M13.828 10.172a4 4 0 00-5.656 0l-4 4a4 4 0 105.656 5.656l1.102-1.101m-.758-4.899a4 4 0 005.656 0l4-4a4 4 0 00-5.656-5.656l-1.1 1.1" />
+                        </svg>
+                      </div>
+                    )}
                     <div>
                       <p className="font-medium text-white">{integration.connection_name}</p>
-                      <p className="text-sm text-neutral-500 capitalize">{integration.integration_type}</p>
+                      {integration.integration_type === 'webhook' ? (
+                        <p className="text-sm text-neutral-500 font-mono truncate">
+                          {String(integration.connection_config.url ?? '')}
+                        </p>
+                      ) : (
+                        <p className="text-sm text-neutral-500 capitalize">{integration.integration_type}</p>
+                      )}
                     </div>
                   </div>
                   <button
@@ -198,6 +274,14 @@ export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageP
                   </button>
                 </div>
 
+                {/* Webhook delivery log */}
+                {integration.integration_type === 'webhook' && (
+                  <div className="mt-4 pt-4 border-t border-neutral-800">
+                    <p className="text-sm font-medium text-neutral-300 mb-2">Recent deliveries</p>
+                    <WebhookDeliveryLog integrationId={integration.id} />
+                  </div>
+                )}
+
                 {/* Linear settings */}
                 {integration.integration_type === 'linear' && (
                   <div className="mt-4 pt-4 border-t border-neutral-800">
@@ -219,6 +303,22 @@ export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageP
                         />
                       </button>
                     </div>
+                    <div className="flex items-center justify-between mt-4">
+                      <div>
+                        <p className="text-sm font-medium text-neutral-300">Post time spent on the issue</p>
+                        <p className="text-xs text-neutral-500">Comment sessions, time spent and notes on the linked issue</p>
+                      </div>
+                      <select
+                        value={linearTimeComment}
+                        onChange={(e) => handleLinearTimeCommentChange(e.target.value as LinearTimeComment)}
+                        className="bg-neutral-900 border border-neutral-700 text-white rounded px-3 py-1.5 text-sm focus:outline-none focus:border-neutral-600"
+                      >
+                        <option value="never">Never</option>
+                        <option value="on_finish">When finished</option>
+                        <option value="on_stop">When stopped or finished</option>
+                      </select>
+                    </div>
+                    <LinearEstimateTableEditor integrationId={integration.id} />
                   </div>
                 )}
               </div>
@@ -227,6 +327,16 @@ export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageP
         </div>
       )}
 
+      {/* Queued Todoist and Linear updates */}
+      {integrations.some((i) => i.integration_type !== 'webhook') && (
+        <div className="mb-8">
+          <h3 className="text-lg font-medium text-neutral-300 mb-4">Sync Activity</h3>
+          <div className="bg-[#0a0a0a] rounded-lg p-4 border border-neutral-800">
+            <OutboxLog integrations={integrations} />
+          </div>
+        </div>
+      )}
+
       {/* Auto-Stop Settings */}
       <div className="mb-8">
         <h3 className="text-lg font-medium text-neutral-300 mb-4">Auto-Stop Settings</h3>
@@ -271,6 +381,21 @@ export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageP
         </div>
       </div>
 
+      {/* Keyboard Shortcuts */}
+      <div className="mb-8">
+        <h3 className="text-lg font-medium text-neutral-300 mb-4">Keyboard Shortcuts</h3>
+        <ShortcutSettingsForm />
+      </div>
+
+      {/* Automation */}
+      <div className="mb-8">
+        <h3 className="text-lg font-medium text-neutral-300 mb-4">Automation</h3>
+        <div className="space-y-3">
+          <HttpApiSettingsForm />
+          <ShellHooksSettingsForm />
+        </div>
+      </div>
+
       {/* Available integrations */}
       <div>
         <h3 className="text-lg font-medium text-neutral-300 mb-4">
@@ -318,9 +443,46 @@ export function IntegrationsPage({ onLinearConnectionChange }: IntegrationsPageP
             </button>
           )}
 
-          {isLinearConnected && isTodoistConnected && (
-            <p className="text-neutral-500 text-sm">All available integrations are connected.</p>
-          )}
+          {/* Any number of webhooks can be added */}
+          <button
+            onClick={() => setView('connect-webhook')}
+            className="flex items-center gap-4 bg-[#0a0a0a] rounded-lg p-4 border border-neutral-800 hover:border-neutral-700 transition-colors text-left"
+          >
+            <div className="w-12 h-12 bg-neutral-700/40 rounded-lg flex items-center justify-center">
+              <svg className="w-6 h-6 text-neutral-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={1.5} d="M13.828 10.172a4 4 0 00-5.656 0l-4 4a4 4 0 105.656 5.656l1.102-1.101m-.758-4.899a4 4 0 005.656 0l4-4a4 4 0 00-5.656-5.656l-1.1 1.1" />
+              </svg>
+            </div>
+            <div className="flex-1">
+              <p className="font-medium text-white">Webhook</p>
+              <p className="text-sm text-neutral-500">POST timebox events to Slack, dashboards or your own scripts</p>
+            </div>
+            <svg className="w-5 h-5 text-neutral-500" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+              <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={1.5} d="M9 5l7 7-7 7" />
+            </svg>
+          </button>
+
+          {otherProviders.map((provider) => (
+            <button
+              key={provider.integration_type}
+              onClick={() => {
+                setSelectedProvider(provider);
+                setView('connect-provider');
+              }}
+              className="flex items-center gap-4 bg-[#0a0a0a] rounded-lg p-4 border border-neutral-800 hover:border-neutral-700 transition-colors text-left"
+            >
+              <div className="w-12 h-12 bg-neutral-700/40 rounded-lg flex items-center justify-center text-neutral-300 font-semibold">
+                {provider.name.charAt(0)}
+              </div>
+              <div className="flex-1">
+                <p className="font-medium text-white">{provider.name}</p>
+                <p className="text-sm text-neutral-500">{provider.description}</p>
+              </div>
+              <svg className="w-5 h-5 text-neutral-500" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={1.5} d="M9 5l7 7-7 7" />
+              </svg>
+            </button>
+          ))}
         </div>
       </div>
     </div>
@@ -1,8 +1,10 @@
 import { useState, useEffect, useCallback, useMemo } from 'react';
 import { commands } from '../lib/commands';
+import { describeApiError } from '../lib/utils';
 import type { LinearTeam, LinearApiProject, LinearProject, LinearConfig } from '../lib/types';
 import { ProjectIssuesView } from '../components/ProjectIssuesView';
 import { LinearTeamPicker } from '../components/LinearTeamPicker';
+import { LinearPlanningImport } from '../components/LinearPlanningImport';
 
 interface SelectedProject {
   projectId: string;
@@ -18,6 +20,7 @@ interface LinearPageProps {
 
 export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPageProps) {
   const [apiKey, setApiKey] = useState<string | null>(null);
+  const [apiBaseUrl, setApiBaseUrl] = useState<string | null>(null);
   const [teams, setTeams] = useState<LinearTeam[]>([]);
   const [selectedTeamId, setSelectedTeamId] = useState<string | null>(null);
   const [apiProjects, setApiProjects] = useState<LinearApiProject[]>([]);
@@ -27,15 +30,19 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
   const [error, setError] = useState<string | null>(null);
   const [selectedProject, setSelectedProject] = useState<SelectedProject | null>(null);
   const [projectSearchTerm, setProjectSearchTerm] = useState('');
+  const [refreshingProjects, setRefreshingProjects] = useState(false);
+  const [checkingLinks, setCheckingLinks] = useState(false);
+  const [linkCheckMessage, setLinkCheckMessage] = useState<string | null>(null);
 
-  // Load API key from integration
-  const loadApiKey = useCallback(async () => {
+  // Load API key and base URL from integration
+  const loadConfig = useCallback(async () => {
     try {
       const integration = await commands.getIntegrationByType('linear');
       if (integration) {
         const config = integration.connection_config as unknown as LinearConfig;
         setApiKey(config.api_key);
-        return config.api_key;
+        setApiBaseUrl(config.api_base_url ?? null);
+        return config;
       }
       return null;
     } catch (err) {
@@ -46,14 +53,14 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
   }, []);
 
   // Load teams from Linear API
-  const loadTeams = useCallback(async (key: string) => {
+  const loadTeams = useCallback(async (config: LinearConfig) => {
     try {
-      const teamsData = await commands.getLinearTeams(key);
+      const teamsData = await commands.getLinearTeams(config.api_key, config.api_base_url);
       setTeams(teamsData);
       setError(null);
     } catch (err) {
       console.error('Failed to load teams:', err);
-      setError('Failed to load teams from Linear');
+      setError(describeApiError(err, 'Linear', 'Failed to load teams from Linear'));
     }
   }, []);
 
@@ -71,14 +78,14 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
   useEffect(() => {
     const init = async () => {
       setLoading(true);
-      const key = await loadApiKey();
-      if (key) {
-        await Promise.all([loadTeams(key), loadSavedProjects()]);
+      const config = await loadConfig();
+      if (config) {
+        await Promise.all([loadTeams(config), loadSavedProjects()]);
       }
       setLoading(false);
     };
     init();
-  }, [loadApiKey, loadTeams, loadSavedProjects]);
+  }, [loadConfig, loadTeams, loadSavedProjects]);
 
   // Load projects when team is selected
   useEffect(() => {
@@ -90,17 +97,17 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
     const loadProjects = async () => {
       setLoadingProjects(true);
       try {
-        const projects = await commands.getLinearTeamProjects(apiKey, selectedTeamId);
+        const projects = await commands.getLinearTeamProjects(apiKey, selectedTeamId, apiBaseUrl);
         setApiProjects(projects);
       } catch (err) {
         console.error('Failed to load projects:', err);
-        setError('Failed to load projects from Linear');
+        setError(describeApiError(err, 'Linear', 'Failed to load projects from Linear'));
       } finally {
         setLoadingProjects(false);
       }
     };
     loadProjects();
-  }, [selectedTeamId, apiKey]);
+  }, [selectedTeamId, apiKey, apiBaseUrl]);
 
   // Filter projects based on search term
   const filteredProjects = useMemo(() => {
@@ -143,6 +150,37 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
     }
   };
 
+  const handleRefreshProjects = async () => {
+    setRefreshingProjects(true);
+    try {
+      await commands.refreshLinearProjects();
+      await loadSavedProjects();
+    } catch (err) {
+      console.error('Failed to refresh projects:', err);
+    } finally {
+      setRefreshingProjects(false);
+    }
+  };
+
+  const handleCheckLinks = async () => {
+    setCheckingLinks(true);
+    try {
+      const result = await commands.validateLinearIssueLinks();
+      const broken = result.broken.map((link) => `${link.linear_issue_identifier ?? 'issue'} (${link.status})`);
+      setLinkCheckMessage(
+        broken.length === 0
+          ? `All ${result.checked} linked issues are fine.`
+          : `${broken.length} of ${result.checked} links are broken: ${broken.join(', ')}`
+      );
+      onTimeboxCreated?.();
+    } catch (err) {
+      console.error('Failed to check issue links:', err);
+      setLinkCheckMessage(describeApiError(err, 'Linear', 'Failed to check issue links'));
+    } finally {
+      setCheckingLinks(false);
+    }
+  };
+
   const getSavedProject = (linearProjectId: string) => {
     return savedProjects.find((p) => p.linear_project_id === linearProjectId);
   };
@@ -173,7 +211,6 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
   if (selectedProject && apiKey) {
     return (
       <ProjectIssuesView
-        apiKey={apiKey}
         projectId={selectedProject.projectId}
         projectName={selectedProject.projectName}
         localProjectId={selectedProject.localProjectId}
@@ -187,7 +224,20 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
 
   return (
     <div className="p-6">
-      <h2 className="text-2xl font-bold text-white mb-6">Linear</h2>
+      <div className="flex items-center justify-between mb-6">
+        <h2 className="text-2xl font-bold text-white">Linear</h2>
+        {apiKey && (
+          <button
+            onClick={handleCheckLinks}
+            disabled={checkingLinks}
+            className="px-3 py-1.5 bg-neutral-800 text-neutral-200 text-sm rounded hover:bg-neutral-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+            title="Check that linked issues still exist and weren't archived or moved"
+          >
+            {checkingLinks ? 'Checking...' : 'Check issue links'}
+          </button>
+        )}
+      </div>
+      {linkCheckMessage && <p className="text-sm text-neutral-400 mb-6">{linkCheckMessage}</p>}
 
       {/* Active Timebox Projects Section */}
       {activeProjects.length > 0 && (
@@ -234,6 +284,7 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
         {apiKey && (
           <LinearTeamPicker
             apiKey={apiKey}
+            apiBaseUrl={apiBaseUrl}
             teams={teams}
             selectedTeamId={selectedTeamId}
             onSelect={setSelectedTeamId}
@@ -241,6 +292,9 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
         )}
       </div>
 
+      {/* Planning */}
+      {apiKey && <LinearPlanningImport teamId={selectedTeamId} onTimeboxCreated={onTimeboxCreated} />}
+
       {/* Projects List */}
       {selectedTeamId && (
         <div>
@@ -369,7 +423,17 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
       {/* Saved Projects Summary */}
       {savedProjects.length > 0 && !selectedTeamId && (
         <div>
-          <h3 className="text-lg font-medium text-neutral-300 mb-4">Saved Projects</h3>
+          <div className="flex items-center justify-between mb-4">
+            <h3 className="text-lg font-medium text-neutral-300">Saved Projects</h3>
+            <button
+              onClick={handleRefreshProjects}
+              disabled={refreshingProjects}
+              className="px-3 py-1 text-sm text-neutral-400 hover:text-white hover:bg-neutral-800 rounded disabled:opacity-50 transition-colors"
+              title="Update names and states from Linear, archiving projects archived or completed there"
+            >
+              {refreshingProjects ? 'Refreshing...' : 'Refresh'}
+            </button>
+          </div>
           <div className="space-y-2">
             {savedProjects.map((project) => (
               <div
@@ -385,7 +449,10 @@ export function LinearPage({ onTimeboxCreated, onNavigateToTimebox }: LinearPage
                 className="flex items-center justify-between bg-[#0a0a0a] rounded-lg p-4 border border-neutral-800 cursor-pointer hover:border-neutral-700 transition-colors"
               >
                 <div>
-                  <p className="font-medium text-white">{project.name}</p>
+                  <p className="font-medium text-white">
+                    {project.name}
+                    {project.archived_at && <span className="ml-2 text-xs text-neutral-500">Archived</span>}
+                  </p>
                   {project.description && (
                     <p className="text-sm text-neutral-500 mt-1">{project.description}</p>
                   )}